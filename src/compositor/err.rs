@@ -0,0 +1,36 @@
+use std::error::Error;
+use std::fmt;
+
+/// The enum `CompositorError` defines the possible errors from the Compositor.
+
+#[derive(Clone, Copy, Debug)]
+pub enum CompositorError {
+    /// There isn't pane for this session.
+    NotFound,
+    /// The session has already a pane.
+    AlreadyExists,
+}
+
+impl fmt::Display for CompositorError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl Error for CompositorError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            CompositorError::NotFound => "There isn't pane for this session.",
+            CompositorError::AlreadyExists => "The session has already a pane.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
@@ -0,0 +1,164 @@
+use std::ops::BitAnd;
+
+use ::libc;
+
+use ::session::SessionId;
+use ::shell::display::Coordinate;
+use ::shell::display::winsz::Winszed;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Split {
+    /// The panes are side by side, separated by a vertical border.
+    Horizontal,
+    /// The panes are stacked, separated by a horizontal border.
+    Vertical,
+}
+
+/// The struct `Rect` is a area of the frame.
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Rect {
+    /// The top left cell.
+    pub origin: Coordinate,
+    /// The number of columns (x) and of rows (y).
+    pub size: Coordinate,
+}
+
+impl Rect {
+
+    /// The accessor method `contains` returns true when the cell is inside the area.
+    pub fn contains(&self, x: libc::size_t, y: libc::size_t) -> bool {
+        x.ge(&self.origin.x).bitand(x.lt(&(self.origin.x + self.size.x)))
+         .bitand(y.ge(&self.origin.y)).bitand(y.lt(&(self.origin.y + self.size.y)))
+    }
+
+    /// The accessor method `as_winszed` returns the window size of the area.
+    pub fn as_winszed(&self) -> Winszed {
+        Winszed {
+            ws_row: self.size.y as libc::c_ushort,
+            ws_col: self.size.x as libc::c_ushort,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        }
+    }
+}
+
+impl From<Winszed> for Rect {
+    fn from(size: Winszed) -> Rect {
+        Rect {
+            origin: Coordinate::default(),
+            size: Coordinate::from(size),
+        }
+    }
+}
+
+/// The enum `Layout` is the tree of panes, each split divides
+/// its area in two halves and a border.
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// The area of a session.
+    Pane(SessionId),
+    /// The area divided between two layouts.
+    Split(Split, Box<Layout>, Box<Layout>),
+}
+
+impl Layout {
+
+    /// The accessor method `contains` returns true when the session has a pane.
+    pub fn contains(&self, id: SessionId) -> bool {
+        match *self {
+            Layout::Pane(current) => current.eq(&id),
+            Layout::Split(_, ref first, ref second) => first.contains(id) || second.contains(id),
+        }
+    }
+
+    /// The method `split` divides the pane of `target` and gives
+    /// the second half to `id`, returns false when the target isn't found.
+    pub fn split(&mut self, target: SessionId, split: Split, id: SessionId) -> bool {
+        match *self {
+            Layout::Pane(current) if current.eq(&target) => {
+                *self = Layout::Split(split,
+                                      Box::new(Layout::Pane(current)),
+                                      Box::new(Layout::Pane(id)));
+                true
+            },
+            Layout::Pane(_) => false,
+            Layout::Split(_, ref mut first, ref mut second) => {
+                first.split(target, split, id) || second.split(target, split, id)
+            },
+        }
+    }
+
+    /// The method `remove` returns the layout without the pane of `id`,
+    /// the sibling of the removed pane takes the whole area of their split.
+    pub fn remove(self, id: SessionId) -> Option<Layout> {
+        match self {
+            Layout::Pane(current) if current.eq(&id) => None,
+            Layout::Pane(current) => Some(Layout::Pane(current)),
+            Layout::Split(split, first, second) => {
+                match (first.remove(id), second.remove(id)) {
+                    (Some(first), Some(second)) => {
+                        Some(Layout::Split(split, Box::new(first), Box::new(second)))
+                    },
+                    (Some(left), None) | (None, Some(left)) => Some(left),
+                    (None, None) => None,
+                }
+            },
+        }
+    }
+
+    /// The method `arrange` computes the area of each pane and of each border.
+    pub fn arrange(
+        &self,
+        area: Rect,
+        panes: &mut Vec<(SessionId, Rect)>,
+        borders: &mut Vec<(Split, Rect)>,
+    ) {
+        match *self {
+            Layout::Pane(id) => panes.push((id, area)),
+            Layout::Split(split, ref first, ref second) => {
+                let (first_area, border, second_area): (Rect, Rect, Rect) = match split {
+                    Split::Horizontal => {
+                        let len: libc::size_t = area.size.x.checked_sub(1).unwrap_or_default() / 2;
+                        let border: libc::size_t = area.size.x.checked_sub(len).map(|rest| rest.min(1)).unwrap_or_default();
+
+                        (Rect {
+                            origin: area.origin,
+                            size: Coordinate::from((len, area.size.y)),
+                        },
+                        Rect {
+                            origin: Coordinate::from((area.origin.x + len, area.origin.y)),
+                            size: Coordinate::from((border, area.size.y)),
+                        },
+                        Rect {
+                            origin: Coordinate::from((area.origin.x + len + border, area.origin.y)),
+                            size: Coordinate::from((area.size.x - len - border, area.size.y)),
+                        })
+                    },
+                    Split::Vertical => {
+                        let len: libc::size_t = area.size.y.checked_sub(1).unwrap_or_default() / 2;
+                        let border: libc::size_t = area.size.y.checked_sub(len).map(|rest| rest.min(1)).unwrap_or_default();
+
+                        (Rect {
+                            origin: area.origin,
+                            size: Coordinate::from((area.size.x, len)),
+                        },
+                        Rect {
+                            origin: Coordinate::from((area.origin.x, area.origin.y + len)),
+                            size: Coordinate::from((area.size.x, border)),
+                        },
+                        Rect {
+                            origin: Coordinate::from((area.origin.x, area.origin.y + len + border)),
+                            size: Coordinate::from((area.size.x, area.size.y - len - border)),
+                        })
+                    },
+                };
+
+                first.arrange(first_area, panes, borders);
+                borders.push((split, border));
+                second.arrange(second_area, panes, borders);
+            },
+        }
+    }
+}
@@ -0,0 +1,241 @@
+mod err;
+mod layout;
+
+use std::mem;
+
+use ::libc;
+
+use ::parent::Parent;
+use ::session::{SessionId, SessionManager};
+use ::shell::display::Coordinate;
+use ::shell::display::winsz::Winszed;
+use ::shell::display::character::{color, Character};
+use ::shell::device::control::operate::mouse::Mouse;
+
+pub use self::err::CompositorError;
+pub use self::layout::{Layout, Rect, Split};
+
+/// The glyph of the border between two side by side panes.
+const BORDER_VERTICAL: char = '│';
+/// The glyph of the border between two stacked panes.
+const BORDER_HORIZONTAL: char = '─';
+/// The glyph of the crossing of two borders.
+const BORDER_CROSS: char = '┼';
+/// The color of the borders around the focused pane.
+const BORDER_FOCUS: [libc::c_uchar; 3] = color::GREEN;
+
+/// The struct `Compositor` tiles the `Display`s of many sessions
+/// into one output frame.
+
+#[derive(Debug, Clone)]
+pub struct Compositor {
+    /// The size of the frame.
+    size: Winszed,
+    /// The tree of panes.
+    layout: Option<Layout>,
+}
+
+impl Compositor {
+
+    /// The constructor method `new` returns a Compositor without pane.
+    pub fn new(size: Winszed) -> Self {
+        Compositor {
+            size: size,
+            layout: None,
+        }
+    }
+
+    /// The accessor `get_window_size` returns the size of the frame.
+    pub fn get_window_size(&self) -> &Winszed {
+        &self.size
+    }
+
+    /// The mutator `set_window_size` replaces the size of the frame,
+    /// the sessions must be resized after with `resize`.
+    pub fn set_window_size(&mut self, size: &Winszed) {
+        self.size = *size;
+    }
+
+    /// The accessor `get_layout` returns the tree of panes.
+    pub fn get_layout(&self) -> Option<&Layout> {
+        self.layout.as_ref()
+    }
+
+    /// The method `split` divides the pane of `target` to give a half
+    /// to `id`, the first pane takes the whole frame without target.
+    pub fn split(
+        &mut self,
+        target: Option<SessionId>,
+        split: Split,
+        id: SessionId,
+    ) -> Result<(), CompositorError> {
+        if self.layout.as_ref().map(|layout| layout.contains(id)).unwrap_or_default() {
+            return Err(CompositorError::AlreadyExists);
+        }
+        match (self.layout.as_mut(), target) {
+            (None, _) => {
+                self.layout = Some(Layout::Pane(id));
+                Ok(())
+            },
+            (Some(layout), Some(target)) => {
+                if layout.split(target, split, id) {
+                    Ok(())
+                } else {
+                    Err(CompositorError::NotFound)
+                }
+            },
+            (Some(_), None) => Err(CompositorError::NotFound),
+        }
+    }
+
+    /// The method `remove` forgets the pane of a session.
+    pub fn remove(&mut self, id: SessionId) -> Result<(), CompositorError> {
+        match self.layout.take() {
+            Some(layout) => {
+                let found: bool = layout.contains(id);
+
+                self.layout = layout.remove(id);
+                if found {
+                    Ok(())
+                } else {
+                    Err(CompositorError::NotFound)
+                }
+            },
+            None => Err(CompositorError::NotFound),
+        }
+    }
+
+    /// The accessor method `get_panes` returns the area of each pane.
+    pub fn get_panes(&self) -> Vec<(SessionId, Rect)> {
+        self.arrange().0
+    }
+
+    fn arrange(&self) -> (Vec<(SessionId, Rect)>, Vec<(Split, Rect)>) {
+        let mut panes: Vec<(SessionId, Rect)> = Vec::new();
+        let mut borders: Vec<(Split, Rect)> = Vec::new();
+
+        if let Some(ref layout) = self.layout {
+            layout.arrange(Rect::from(self.size), &mut panes, &mut borders);
+        }
+        (panes, borders)
+    }
+
+    /// The method `resize` redimentionnes each session to the size of its pane.
+    pub fn resize(&self, manager: &mut SessionManager) {
+        self.get_panes().into_iter().all(|(id, area)| {
+            if let (Some(shell), false) = (manager.get_mut(id), area.size.x.eq(&0) || area.size.y.eq(&0)) {
+                shell.set_window_size_with(&area.as_winszed());
+            }
+            true
+        });
+    }
+
+    /// The accessor method `pane_at` returns the session under a cell
+    /// of the frame with the cell's coordinate inside the pane.
+    pub fn pane_at(&self, x: libc::size_t, y: libc::size_t) -> Option<(SessionId, Coordinate)> {
+        self.get_panes().into_iter()
+                        .find(|&(_, area)| area.contains(x, y))
+                        .map(|(id, area)| {
+                            (id, Coordinate::from((x - area.origin.x, y - area.origin.y)))
+                        })
+    }
+
+    /// The method `translate` returns the session under a Mouse event
+    /// with the event moved into the pane's coordinates.
+    pub fn translate(&self, mouse: Mouse) -> Option<(SessionId, Mouse)> {
+        match (mouse.x.checked_sub(1), mouse.y.checked_sub(1)) {
+            (Some(x), Some(y)) => {
+                self.pane_at(x as libc::size_t, y as libc::size_t).map(|(id, at)| {
+                    (id, Mouse {
+                        x: at.x as libc::c_ushort + 1,
+                        y: at.y as libc::c_ushort + 1,
+                        ..mouse
+                    })
+                })
+            },
+            _ => None,
+        }
+    }
+
+    /// The method `render` returns the frame with the screen of each pane,
+    /// the borders and the focus highlight.
+    pub fn render(&self, manager: &SessionManager) -> String {
+        let col: libc::size_t = self.size.get_col();
+        let mut frame: Vec<Character> = (0..self.size.row_by_col()).map(|_: usize|
+                                             Character::default()
+                                         ).collect::<Vec<Character>>();
+        let (panes, borders) = self.arrange();
+
+        panes.iter().all(|&(id, area)| {
+            if let Some(shell) = manager.get(id) {
+                let width: libc::size_t = shell.get_window_size().get_col();
+
+                if width > 0 {
+                    shell.get_screen().into_iter().as_slice()
+                         .chunks(width)
+                         .take(area.size.y)
+                         .enumerate()
+                         .all(|(y, characters)| {
+                             characters.iter().take(area.size.x).enumerate().all(|(x, character)| {
+                                 frame[(area.origin.y + y) * col + area.origin.x + x] = *character;
+                                 true
+                             })
+                         });
+                }
+            }
+            true
+        });
+        borders.iter().all(|&(split, area)| {
+            (area.origin.y..area.origin.y + area.size.y).all(|y| {
+                (area.origin.x..area.origin.x + area.size.x).all(|x| {
+                    let cell: &mut Character = &mut frame[y * col + x];
+
+                    match (split, cell.get_glyph()) {
+                        (Split::Horizontal, BORDER_HORIZONTAL) |
+                        (Split::Vertical, BORDER_VERTICAL) => cell.set_glyph(BORDER_CROSS),
+                        (Split::Horizontal, _) => *cell = Character::from(BORDER_VERTICAL),
+                        (Split::Vertical, _) => *cell = Character::from(BORDER_HORIZONTAL),
+                    }
+                    true
+                })
+            })
+        });
+        if let Some(&(_, area)) = manager.get_focus().and_then(|focus|
+            panes.iter().find(|&&(id, _)| id.eq(&focus))
+        ) {
+            let (x_min, y_min) = (area.origin.x.checked_sub(1).unwrap_or_default(),
+                                  area.origin.y.checked_sub(1).unwrap_or_default());
+            let (x_max, y_max) = ((area.origin.x + area.size.x + 1).min(col),
+                                  (area.origin.y + area.size.y + 1).min(self.size.get_row()));
+
+            (y_min..y_max).all(|y| {
+                (x_min..x_max).filter(|&x| area.contains(x, y).eq(&false)).all(|x| {
+                    let cell: &mut Character = &mut frame[y * col + x];
+
+                    match cell.get_glyph() {
+                        BORDER_VERTICAL | BORDER_HORIZONTAL | BORDER_CROSS => {
+                            cell.set_foreground(BORDER_FOCUS);
+                        },
+                        _ => {},
+                    }
+                    true
+                })
+            });
+        }
+
+        let mut disp: String = String::with_capacity(frame.len() * mem::size_of::<char>());
+
+        if col > 0 {
+            frame.chunks(col).all(|characters| {
+                characters.iter().all(|character| {
+                    disp.push_str(format!("{}", character).as_str());
+                    true
+                });
+                disp.push('\n');
+                true
+            });
+        }
+        disp.pop();
+        disp
+    }
+}
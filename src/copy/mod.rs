@@ -0,0 +1,455 @@
+use std::cmp;
+
+use ::libc;
+
+use ::shell::display::{Display, Coordinate, Selection, SelectionMode, Search, SearchDirection};
+use ::shell::display::selection::DEFAULT_SEPARATORS;
+use ::shell::device::control::operate::key::{Key, KeyCode, NONE, CTRL, ALT};
+
+/// The enum `CopyStyle` is the set of keys of the copy mode.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum CopyStyle {
+    /// `hjkl`, `w`, `v`, `y`... as vi.
+    Vi,
+    /// `C-f`, `M-f`, `C-Space`, `M-w`... as emacs.
+    Emacs,
+}
+
+impl Default for CopyStyle {
+    fn default() -> CopyStyle {
+        CopyStyle::Vi
+    }
+}
+
+/// The enum `CopyFeed` is the result of a key in the copy mode.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CopyFeed {
+    /// The cursor or the selection has moved, the overlay is redrawn.
+    Redraw,
+    /// The key isn't bound in the copy mode.
+    Ignore,
+    /// The selection is yanked to the buffer, the copy mode ends.
+    Yank(String),
+    /// The copy mode ends without yank.
+    Exit,
+}
+
+/// The enum `Command` is what a key does in the copy mode.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Command {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    LineFirst,
+    LineEnd,
+    WordNext,
+    WordPrevious,
+    WordEnd,
+    ScreenTop,
+    ScreenMiddle,
+    ScreenBottom,
+    HalfUp,
+    HalfDown,
+    PageUp,
+    PageDown,
+    Search(SearchDirection),
+    /// Starts a selection at the cursor.
+    Begin,
+    /// Starts, changes or stops a selection of a mode.
+    Toggle(SelectionMode),
+    /// Switches the selection between linear and block.
+    Rectangle,
+    /// Clears the selection, or ends without selection.
+    Clear,
+    Yank,
+    Exit,
+    Count(libc::size_t),
+}
+
+/// The enum `Class` is the kind of a glyph for the word's motions.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Class {
+    Space,
+    Separator,
+    Word,
+}
+
+/// The struct `CopyMode` browses a `Display` with a virtual cursor,
+/// independent of the child's cursor, to select and yank its text
+/// with the keyboard à la tmux.
+/// The `Display` keeps no scrollback, so the motions stop at the screen's
+/// edges: the top and the bottom (`g`, `G`, `M-<`, `M->`) are those of
+/// the screen and the pages (`C-u`, `C-b`, `PageUp`...) don't scroll.
+
+#[derive(Clone, Debug)]
+pub struct CopyMode {
+    style: CopyStyle,
+    /// The virtual cursor.
+    cursor: Coordinate,
+    selection: Option<Selection>,
+    /// The last search, repeated by `n` and `N` (`C-s` and `C-r`).
+    search: Option<Search>,
+    /// The repeat's count typed before a vi's motion.
+    count: libc::size_t,
+    /// The last yanked text.
+    buffer: String,
+}
+
+impl CopyMode {
+
+    /// The constructor method `new` returns a copy mode whose cursor
+    /// starts on the child's cursor.
+    pub fn new(display: &Display, style: CopyStyle) -> Self {
+        let mut copy: CopyMode = CopyMode {
+            style: style,
+            cursor: *display.get_cursor_coords(),
+            selection: None,
+            search: None,
+            count: 0,
+            buffer: String::new(),
+        };
+
+        copy.cursor = copy.clamp(display, copy.cursor.x, copy.cursor.y);
+        copy
+    }
+
+    /// The accessor method `get_style` returns the set of keys.
+    pub fn get_style(&self) -> CopyStyle {
+        self.style
+    }
+
+    /// The accessor method `get_cursor` returns the virtual cursor.
+    pub fn get_cursor(&self) -> &Coordinate {
+        &self.cursor
+    }
+
+    /// The accessor method `get_selection` returns the selection.
+    pub fn get_selection(&self) -> Option<&Selection> {
+        self.selection.as_ref()
+    }
+
+    /// The accessor method `get_buffer` returns the last yanked text.
+    pub fn get_buffer(&self) -> &str {
+        self.buffer.as_str()
+    }
+
+    /// The mutator method `set_search` changes the search repeated
+    /// by `n` and `N` (`C-s` and `C-r` as emacs).
+    pub fn set_search(&mut self, search: Option<Search>) {
+        self.search = search;
+    }
+
+    /// The mutator method `set_cursor` moves the virtual cursor.
+    pub fn set_cursor(&mut self, display: &Display, at: Coordinate) {
+        self.cursor = self.clamp(display, at.x, at.y);
+        if let Some(selection) = self.selection.as_mut() {
+            selection.extend(self.cursor);
+        }
+    }
+
+    /// The function `command` returns the command of a key for a style.
+    fn command(style: CopyStyle, count: libc::size_t, key: Key) -> Option<Command> {
+        match (style, key) {
+            (_, Key::Code(KeyCode::Left, NONE)) => Some(Command::Left),
+            (_, Key::Code(KeyCode::Right, NONE)) => Some(Command::Right),
+            (_, Key::Code(KeyCode::Up, NONE)) => Some(Command::Up),
+            (_, Key::Code(KeyCode::Down, NONE)) => Some(Command::Down),
+            (_, Key::Code(KeyCode::Home, NONE)) => Some(Command::LineStart),
+            (_, Key::Code(KeyCode::End, NONE)) => Some(Command::LineEnd),
+            (_, Key::Code(KeyCode::PageUp, NONE)) => Some(Command::PageUp),
+            (_, Key::Code(KeyCode::PageDown, NONE)) => Some(Command::PageDown),
+            (CopyStyle::Vi, Key::Code(KeyCode::Char('u'), CTRL)) => Some(Command::HalfUp),
+            (CopyStyle::Vi, Key::Code(KeyCode::Char('d'), CTRL)) => Some(Command::HalfDown),
+            (CopyStyle::Vi, Key::Code(KeyCode::Char('b'), CTRL)) => Some(Command::PageUp),
+            (CopyStyle::Vi, Key::Code(KeyCode::Char('f'), CTRL)) => Some(Command::PageDown),
+            (CopyStyle::Vi, Key::Code(KeyCode::Char('v'), CTRL)) => Some(Command::Toggle(SelectionMode::Block)),
+            (CopyStyle::Vi, Key::Code(KeyCode::Enter, NONE)) => Some(Command::Yank),
+            (CopyStyle::Vi, Key::Code(KeyCode::Escape, NONE)) => Some(Command::Clear),
+            (CopyStyle::Vi, key) => match key.is_utf8() {
+                Some(digit @ '1'...'9') => Some(Command::Count(digit as libc::size_t - '0' as libc::size_t)),
+                Some('0') if count > 0 => Some(Command::Count(0)),
+                Some('h') => Some(Command::Left),
+                Some('l') => Some(Command::Right),
+                Some('k') => Some(Command::Up),
+                Some('j') => Some(Command::Down),
+                Some('0') => Some(Command::LineStart),
+                Some('^') => Some(Command::LineFirst),
+                Some('$') => Some(Command::LineEnd),
+                Some('w') => Some(Command::WordNext),
+                Some('b') => Some(Command::WordPrevious),
+                Some('e') => Some(Command::WordEnd),
+                Some('g') => Some(Command::ScreenTop),
+                Some('G') => Some(Command::ScreenBottom),
+                Some('H') => Some(Command::ScreenTop),
+                Some('M') => Some(Command::ScreenMiddle),
+                Some('L') => Some(Command::ScreenBottom),
+                Some('n') => Some(Command::Search(SearchDirection::Forward)),
+                Some('N') => Some(Command::Search(SearchDirection::Backward)),
+                Some(' ') => Some(Command::Begin),
+                Some('v') => Some(Command::Toggle(SelectionMode::Linear)),
+                Some('V') => Some(Command::Toggle(SelectionMode::Line)),
+                Some('y') => Some(Command::Yank),
+                Some('q') => Some(Command::Exit),
+                _ => None,
+            },
+            (CopyStyle::Emacs, Key::Code(KeyCode::Char(glyph), CTRL)) => match glyph {
+                'b' => Some(Command::Left),
+                'f' => Some(Command::Right),
+                'p' => Some(Command::Up),
+                'n' => Some(Command::Down),
+                'a' => Some(Command::LineStart),
+                'e' => Some(Command::LineEnd),
+                'v' => Some(Command::PageDown),
+                's' => Some(Command::Search(SearchDirection::Forward)),
+                'r' => Some(Command::Search(SearchDirection::Backward)),
+                ' ' => Some(Command::Begin),
+                'w' => Some(Command::Yank),
+                'g' => Some(Command::Clear),
+                _ => None,
+            },
+            (CopyStyle::Emacs, Key::Code(KeyCode::Char(glyph), ALT)) => match glyph {
+                'f' => Some(Command::WordEnd),
+                'b' => Some(Command::WordPrevious),
+                'm' => Some(Command::LineFirst),
+                'v' => Some(Command::PageUp),
+                '<' => Some(Command::ScreenTop),
+                '>' => Some(Command::ScreenBottom),
+                'w' => Some(Command::Yank),
+                _ => None,
+            },
+            (CopyStyle::Emacs, Key::Code(KeyCode::Escape, NONE)) => Some(Command::Exit),
+            (CopyStyle::Emacs, key) => match key.is_utf8() {
+                Some('R') => Some(Command::Rectangle),
+                Some('q') => Some(Command::Exit),
+                _ => None,
+            },
+        }
+    }
+
+    /// The method `feed` moves the cursor or changes the selection
+    /// with a pressed key.
+    pub fn feed(&mut self, display: &Display, key: Key) -> CopyFeed {
+        let key: Key = match key {
+            Key::Repeat(code, modifiers) => Key::Code(code, modifiers),
+            Key::Release(_, _) => return CopyFeed::Ignore,
+            key => key,
+        };
+        let command: Command = match CopyMode::command(self.style, self.count, key) {
+            Some(command) => command,
+            None => {
+                self.count = 0;
+                return CopyFeed::Ignore;
+            },
+        };
+        let pending: libc::size_t = self.count;
+
+        self.count = 0;
+        match command {
+            Command::Count(digit) => {
+                self.count = pending.saturating_mul(10).saturating_add(digit);
+                CopyFeed::Ignore
+            },
+            Command::Begin => {
+                self.selection = Some(Selection::new(SelectionMode::Linear, self.cursor));
+                CopyFeed::Redraw
+            },
+            Command::Toggle(mode) => {
+                match self.selection.as_ref().map(|selection| selection.get_mode()) {
+                    None => self.selection = Some(Selection::new(mode, self.cursor)),
+                    Some(current) if current.eq(&mode) => self.selection = None,
+                    Some(_) => if let Some(selection) = self.selection.as_mut() {
+                        selection.set_mode(mode);
+                    },
+                }
+                CopyFeed::Redraw
+            },
+            Command::Rectangle => {
+                if let Some(selection) = self.selection.as_mut() {
+                    let mode: SelectionMode = match selection.get_mode() {
+                        SelectionMode::Block => SelectionMode::Linear,
+                        _ => SelectionMode::Block,
+                    };
+
+                    selection.set_mode(mode);
+                }
+                CopyFeed::Redraw
+            },
+            Command::Clear => {
+                if self.selection.take().is_some() {
+                    CopyFeed::Redraw
+                } else {
+                    CopyFeed::Exit
+                }
+            },
+            Command::Yank => {
+                match self.selection.take() {
+                    Some(selection) => {
+                        self.buffer = selection.get_text(display);
+                        CopyFeed::Yank(self.buffer.clone())
+                    },
+                    None => CopyFeed::Exit,
+                }
+            },
+            Command::Exit => CopyFeed::Exit,
+            motion => {
+                let cursor: Coordinate = (0..cmp::max(pending, 1)).fold(self.cursor, |cursor, _| {
+                    self.motion(display, motion, cursor)
+                });
+
+                self.set_cursor(display, cursor);
+                CopyFeed::Redraw
+            },
+        }
+    }
+
+    /// The method `motion` returns the cell where a motion goes from a cell.
+    fn motion(&self, display: &Display, motion: Command, at: Coordinate) -> Coordinate {
+        let row: libc::size_t = display.get_window_size().get_row();
+        let half: libc::size_t = cmp::max(row / 2, 1);
+
+        match motion {
+            Command::Left => self.clamp(display, at.x.saturating_sub(1), at.y),
+            Command::Right => self.clamp(display, at.x + 1, at.y),
+            Command::Up => self.clamp(display, at.x, at.y.saturating_sub(1)),
+            Command::Down => self.clamp(display, at.x, at.y + 1),
+            Command::LineStart => self.clamp(display, 0, at.y),
+            Command::LineFirst => {
+                let x: libc::size_t = display.get_line(at.y).and_then(|line| {
+                    line.iter().position(|character| !character.get_glyph().is_whitespace())
+                }).unwrap_or_default();
+
+                self.clamp(display, x, at.y)
+            },
+            Command::LineEnd => {
+                let x: libc::size_t = display.get_line(at.y).and_then(|line| {
+                    line.iter().rposition(|character| !character.get_glyph().is_whitespace())
+                }).unwrap_or_default();
+
+                self.clamp(display, x, at.y)
+            },
+            Command::WordNext => self.word(display, at, true, false),
+            Command::WordPrevious => self.word(display, at, false, false),
+            Command::WordEnd => self.word(display, at, true, true),
+            Command::ScreenTop => self.clamp(display, 0, 0),
+            Command::ScreenMiddle => self.clamp(display, 0, row.saturating_sub(1) / 2),
+            Command::ScreenBottom => self.clamp(display, 0, row.saturating_sub(1)),
+            Command::HalfUp => self.clamp(display, at.x, at.y.saturating_sub(half)),
+            Command::HalfDown => self.clamp(display, at.x, at.y + half),
+            Command::PageUp => self.clamp(display, at.x, at.y.saturating_sub(row)),
+            Command::PageDown => self.clamp(display, at.x, at.y + row),
+            Command::Search(direction) => {
+                self.search.as_ref()
+                           .and_then(|search| search.find(display, &at, direction))
+                           .map(|(start, _)| start)
+                           .unwrap_or(at)
+            },
+            _ => at,
+        }
+    }
+
+    /// The method `word` returns the start of the next or of the previous
+    /// word, or the end of the next word, the punctuation is a word.
+    fn word(&self, display: &Display, at: Coordinate, forward: bool, end: bool) -> Coordinate {
+        let col: libc::size_t = cmp::max(display.get_window_size().get_col(), 1);
+        let last: libc::size_t = (display.get_window_size().get_row() * col).saturating_sub(1);
+        let class = |index: libc::size_t| -> Class {
+            let glyph: char = display.get_line(index / col)
+                                     .and_then(|line| line.get(index % col))
+                                     .map(|character| character.get_glyph())
+                                     .unwrap_or(' ');
+
+            if glyph.is_whitespace() || glyph.eq(&'\0') {
+                Class::Space
+            } else if DEFAULT_SEPARATORS.contains(glyph) {
+                Class::Separator
+            } else {
+                Class::Word
+            }
+        };
+        let mut index: libc::size_t = cmp::min(at.y * col + at.x, last);
+
+        match (forward, end) {
+            (true, false) => {
+                let start: Class = class(index);
+
+                while index < last && class(index).eq(&start) && start.ne(&Class::Space) {
+                    index += 1;
+                }
+                while index < last && class(index).eq(&Class::Space) {
+                    index += 1;
+                }
+            },
+            (true, true) => {
+                index = cmp::min(index + 1, last);
+                while index < last && class(index).eq(&Class::Space) {
+                    index += 1;
+                }
+                let start: Class = class(index);
+
+                while index < last && class(index + 1).eq(&start) {
+                    index += 1;
+                }
+            },
+            (false, _) => {
+                index = index.saturating_sub(1);
+                while index > 0 && class(index).eq(&Class::Space) {
+                    index -= 1;
+                }
+                let start: Class = class(index);
+
+                while index > 0 && class(index - 1).eq(&start) {
+                    index -= 1;
+                }
+            },
+        }
+        Coordinate::from((index % col, index / col))
+    }
+
+    /// The method `clamp` returns the nearest cell of the display.
+    fn clamp(&self, display: &Display, x: libc::size_t, y: libc::size_t) -> Coordinate {
+        Coordinate::from((
+            cmp::min(x, display.get_window_size().get_col().saturating_sub(1)),
+            cmp::min(y, display.get_window_size().get_row().saturating_sub(1)),
+        ))
+    }
+
+    /// The method `overlay` returns the sequences to write after the
+    /// display's output: the selected cells are reversed and the host's
+    /// cursor is moved to the virtual cursor.
+    pub fn overlay(&self, display: &Display) -> Vec<libc::c_uchar> {
+        let mut bytes: Vec<libc::c_uchar> = Vec::new();
+
+        if let Some(selection) = self.selection.as_ref() {
+            let (start, end): (Coordinate, Coordinate) = selection.get_range(display);
+            let col: libc::size_t = display.get_window_size().get_col();
+
+            (start.y..end.y + 1).all(|y| {
+                let (from, to): (libc::size_t, libc::size_t) = match selection.get_mode() {
+                    SelectionMode::Block => (start.x, end.x),
+                    _ => (
+                        if y.eq(&start.y) { start.x } else { 0 },
+                        if y.eq(&end.y) { end.x } else { col.saturating_sub(1) },
+                    ),
+                };
+                let glyphs: String = display.get_line(y).map(|line| {
+                    line.iter()
+                        .skip(from)
+                        .take((to + 1).saturating_sub(from))
+                        .map(|character| character.get_glyph())
+                        .collect::<String>()
+                }).unwrap_or_default();
+
+                bytes.extend(format!("\x1B[{};{}H\x1B[7m{}\x1B[27m", y + 1, from + 1, glyphs).into_bytes());
+                true
+            });
+        }
+        bytes.extend(format!("\x1B[{};{}H\x1B[?25h", self.cursor.y + 1, self.cursor.x + 1).into_bytes());
+        bytes
+    }
+}
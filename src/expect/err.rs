@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use ::regex;
+
+use ::shell::ShellError;
+use ::shell::device::reactor::ReactorError;
+
+/// The enum `ExpectError` defines the possible errors from the Expect interface.
+
+#[derive(Debug)]
+pub enum ExpectError {
+    /// The Shell has occured an error.
+    ShellFail(ShellError),
+    /// The Reactor has occured an error.
+    ReactorFail(ReactorError),
+    /// The pattern isn't a valid regular expression.
+    RegexFail(regex::Error),
+    /// Can't read or write the master.
+    Io(io::Error),
+    /// The Shell isn't evented.
+    NotEvented,
+    /// The time limit has elapsed before a match.
+    Timeout,
+    /// The child has hung up before a match.
+    Eof,
+}
+
+impl fmt::Display for ExpectError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", ::errno::errno())
+    }
+}
+
+impl Error for ExpectError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            ExpectError::ShellFail(_) => "The shell has occured an error.",
+            ExpectError::ReactorFail(_) => "The reactor has occured an error.",
+            ExpectError::RegexFail(_) => "The pattern isn't a valid regular expression.",
+            ExpectError::Io(_) => "Can't read or write the master.",
+            ExpectError::NotEvented => "The shell isn't evented.",
+            ExpectError::Timeout => "The time limit has elapsed before a match.",
+            ExpectError::Eof => "The child has hung up before a match.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ExpectError::ShellFail(ref why) => Some(why),
+            ExpectError::ReactorFail(ref why) => Some(why),
+            ExpectError::RegexFail(ref why) => Some(why),
+            ExpectError::Io(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExpectError {
+    fn from(why: io::Error) -> ExpectError {
+        ExpectError::Io(why)
+    }
+}
@@ -0,0 +1,242 @@
+mod err;
+
+use std::io::{self, Write};
+use std::time;
+
+use ::regex;
+
+use ::shell::{Shell, Parent, Winszed};
+use ::shell::display::Display;
+use ::shell::device::reactor::Reactor;
+
+pub use self::err::ExpectError;
+
+/// The token of the shell in the reactor.
+const TOKEN_SHELL: usize = 0;
+
+/// The struct `Match` is a matched pattern with the text before it
+/// and its captures.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The text between the last match and this one.
+    before: String,
+    /// The captures, the first one is the whole match.
+    captures: Vec<Option<String>>,
+}
+
+impl Match {
+
+    /// The accessor method `get_before` returns the text before the match.
+    pub fn get_before(&self) -> &str {
+        self.before.as_str()
+    }
+
+    /// The accessor method `get_matched` returns the whole match.
+    pub fn get_matched(&self) -> &str {
+        self.get(0).unwrap_or_default()
+    }
+
+    /// The accessor method `get` returns a capture by index.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.captures.get(index)
+                     .and_then(|capture| capture.as_ref())
+                     .map(|capture| capture.as_str())
+    }
+
+    /// The accessor method `get_captures` returns all the captures.
+    pub fn get_captures(&self) -> &[Option<String>] {
+        self.captures.as_slice()
+    }
+}
+
+/// The struct `Expect` scripts an evented `Shell`: it sends a text,
+/// then waits until a pattern is matched by the output stream or
+/// by the rendered `Display`.
+
+#[derive(Debug)]
+pub struct Expect {
+    shell: Shell,
+    reactor: Reactor,
+    /// The output not yet consumed by a match.
+    buffer: Vec<u8>,
+    /// The child has hung up.
+    eof: bool,
+}
+
+impl Expect {
+
+    /// The constructor method `spawn` returns a Expect interface
+    /// on a new evented shell.
+    pub fn spawn (
+        command: Option<&str>,
+        windows: Option<Winszed>,
+    ) -> Result<Self, ExpectError> {
+        match Shell::new_evented(None, None, command, windows) {
+            Err(why) => Err(ExpectError::ShellFail(why)),
+            Ok(shell) => Expect::new(shell),
+        }
+    }
+
+    /// The constructor method `new` returns a Expect interface
+    /// on a evented shell.
+    pub fn new(shell: Shell) -> Result<Self, ExpectError> {
+        if shell.is_evented() {
+            let mut reactor: Reactor = Reactor::new();
+
+            reactor.register(&shell, TOKEN_SHELL);
+            Ok(Expect {
+                shell: shell,
+                reactor: reactor,
+                buffer: Vec::new(),
+                eof: false,
+            })
+        } else {
+            Err(ExpectError::NotEvented)
+        }
+    }
+
+    /// The accessor method `get_shell` returns a reference on the shell.
+    pub fn get_shell(&self) -> &Shell {
+        &self.shell
+    }
+
+    /// The accessor method `get_mut_shell` returns a mutable reference on the shell.
+    pub fn get_mut_shell(&mut self) -> &mut Shell {
+        &mut self.shell
+    }
+
+    /// The accessor method `get_buffer` returns the output not yet consumed.
+    pub fn get_buffer(&self) -> &[u8] {
+        self.buffer.as_slice()
+    }
+
+    /// The accessor method `is_eof` returns true when the child has hung up.
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// The method `into_inner` returns the shell.
+    pub fn into_inner(self) -> Shell {
+        self.shell
+    }
+
+    /// The method `send` writes a text to the child.
+    pub fn send(&mut self, text: &str) -> Result<(), ExpectError> {
+        try!(<Shell as Write>::write_all(&mut self.shell, text.as_bytes()));
+        try!(<Shell as Write>::flush(&mut self.shell));
+        Ok(())
+    }
+
+    /// The method `send_line` writes a text and a newline to the child.
+    pub fn send_line(&mut self, text: &str) -> Result<(), ExpectError> {
+        try!(self.send(text));
+        self.send("\n")
+    }
+
+    /// The method `fill` waits for output until the timeout and
+    /// updates the buffer and the display.
+    fn fill(&mut self, timeout: time::Duration) -> Result<(), ExpectError> {
+        try!(self.reactor.poll(Some(timeout)).map_err(|why| ExpectError::ReactorFail(why)));
+        loop {
+            match self.shell.next_evented() {
+                Ok(Some(state)) => {
+                    if let Some(out) = state.is_output_last() {
+                        self.buffer.extend_from_slice(out);
+                    }
+                },
+                Ok(None) => {
+                    self.eof = true;
+                    return Ok(());
+                },
+                Err(ref why) if why.kind().eq(&io::ErrorKind::Interrupted) => continue,
+                Err(ref why) if why.kind().eq(&io::ErrorKind::WouldBlock) => return Ok(()),
+                Err(why) => return Err(ExpectError::Io(why)),
+            }
+        }
+    }
+
+    /// The method `wait` repeats `find` with the new output until it
+    /// returns something, the child hangs up or the timeout elapses.
+    fn wait<T, F: FnMut(&mut Expect) -> Option<T>>(
+        &mut self,
+        timeout: time::Duration,
+        mut find: F,
+    ) -> Result<T, ExpectError> {
+        let deadline: time::Instant = time::Instant::now() + timeout;
+
+        loop {
+            if let Some(found) = find(self) {
+                return Ok(found);
+            } else if self.eof {
+                return Err(ExpectError::Eof);
+            } else {
+                let now: time::Instant = time::Instant::now();
+
+                if now >= deadline {
+                    return Err(ExpectError::Timeout);
+                }
+                try!(self.fill(deadline - now));
+            }
+        }
+    }
+
+    /// The method `expect` waits until the pattern matches the output stream
+    /// and consumes the output up to the end of the match.
+    pub fn expect(&mut self, pattern: &str, timeout: time::Duration) -> Result<Match, ExpectError> {
+        let regex: regex::bytes::Regex = try!(regex::bytes::Regex::new(pattern).map_err(|why| ExpectError::RegexFail(why)));
+
+        self.wait(timeout, |expect| {
+            let found: Option<(usize, Match)> = regex.captures(&expect.buffer).map(|captures| {
+                let (start, end) = captures.get(0).map(|all| (all.start(), all.end())).unwrap_or_default();
+
+                (end, Match {
+                    before: String::from_utf8_lossy(&expect.buffer[..start]).into_owned(),
+                    captures: captures.iter().map(|capture| {
+                        capture.map(|capture| String::from_utf8_lossy(capture.as_bytes()).into_owned())
+                    }).collect::<Vec<Option<String>>>(),
+                })
+            });
+
+            found.map(|(end, found)| {
+                expect.buffer.drain(..end);
+                found
+            })
+        })
+    }
+
+    /// The method `expect_screen` waits until the predicate is true
+    /// for the display.
+    pub fn expect_screen<F: Fn(&Display) -> bool>(
+        &mut self,
+        predicate: F,
+        timeout: time::Duration,
+    ) -> Result<(), ExpectError> {
+        self.wait(timeout, |expect| {
+            if predicate(expect.shell.get_screen()) {
+                Some(())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The method `expect_screen_regex` waits until the pattern matches the
+    /// text of the display (see `Display::get_text`), nothing is consumed.
+    pub fn expect_screen_regex(&mut self, pattern: &str, timeout: time::Duration) -> Result<Match, ExpectError> {
+        let regex: regex::Regex = try!(regex::Regex::new(pattern).map_err(|why| ExpectError::RegexFail(why)));
+
+        self.wait(timeout, |expect| {
+            let text: String = expect.shell.get_screen().get_text();
+
+            regex.captures(&text).map(|captures| {
+                Match {
+                    before: text[..captures.get(0).map(|all| all.start()).unwrap_or_default()].to_string(),
+                    captures: captures.iter().map(|capture| {
+                        capture.map(|capture| capture.as_str().to_string())
+                    }).collect::<Vec<Option<String>>>(),
+                }
+            })
+        })
+    }
+}
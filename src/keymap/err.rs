@@ -0,0 +1,58 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// The enum `KeymapError` defines the possible errors from the Keymap
+/// and its configuration's file (the lines are counted from 1).
+
+#[derive(Debug)]
+pub enum KeymapError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// The command of the line is unknown or misses an argument.
+    Syntax(usize),
+    /// The key of the line can't be parsed.
+    UnknownKey(usize),
+    /// The action of the line is unknown.
+    UnknownAction(usize),
+}
+
+impl fmt::Display for KeymapError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KeymapError::Io(ref why) => write!(f, "{}", why),
+            KeymapError::Syntax(line) |
+            KeymapError::UnknownKey(line) |
+            KeymapError::UnknownAction(line) => write!(f, "{} (line {})", self.description(), line),
+        }
+    }
+}
+
+impl Error for KeymapError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            KeymapError::Io(_) => "The file can't be read.",
+            KeymapError::Syntax(_) => "The command is unknown or misses an argument.",
+            KeymapError::UnknownKey(_) => "The key can't be parsed.",
+            KeymapError::UnknownAction(_) => "The action is unknown.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            KeymapError::Io(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for KeymapError {
+    fn from(why: io::Error) -> KeymapError {
+        KeymapError::Io(why)
+    }
+}
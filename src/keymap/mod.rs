@@ -0,0 +1,303 @@
+mod err;
+
+use std::fs;
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+
+use ::shell::ShellState;
+use ::shell::device::control::operate::key::{Key, KeyCode, Modifiers, NONE};
+
+pub use self::err::KeymapError;
+
+/// The enum `Action` is what the host does for a bound key.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Enters the copy mode up by lines (the display keeps no scrollback).
+    ScrollUp(usize),
+    /// Enters the copy mode down by lines.
+    ScrollDown(usize),
+    /// Enters the copy mode.
+    CopyMode,
+    /// Sends a key to the child as is (the prefix itself for instance).
+    SendKey(Key),
+    /// Runs the callback registered with this name (see `set_callback`).
+    Callback(String),
+}
+
+/// The enum `Feed` is the decision of the Keymap for a key.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Feed {
+    /// The key isn't bound, it's written to the child.
+    Forward,
+    /// The prefix is pressed, the next key is a command.
+    Prefix,
+    /// The key follows the prefix without binding, it's dropped.
+    Ignore,
+    /// The key is bound to an action.
+    Action(Action),
+}
+
+/// The function `parse_key` returns the key of a name as `C-b`, `M-x`,
+/// `S-PageUp`, `F5` or `a` (the modifiers are Ctrl, Alt (M) and Shift).
+pub fn parse_key(name: &str) -> Option<Key> {
+    let mut modifiers: Modifiers = NONE;
+    let mut rest: &str = name;
+
+    while rest.len() > 2 && rest.as_bytes()[1] == b'-' {
+        match rest.as_bytes()[0] {
+            b'C' => modifiers.ctrl = true,
+            b'M' => modifiers.alt = true,
+            b'S' => modifiers.shift = true,
+            _ => return None,
+        }
+        rest = &rest[2..];
+    }
+    let code: KeyCode = match rest {
+        "Enter" => KeyCode::Enter,
+        "Tab" => KeyCode::Tab,
+        "BSpace" | "Backspace" => KeyCode::Backspace,
+        "Escape" => KeyCode::Escape,
+        "Space" => KeyCode::Char(' '),
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "IC" | "Insert" => KeyCode::Insert,
+        "DC" | "Delete" => KeyCode::Delete,
+        "PPage" | "PageUp" => KeyCode::PageUp,
+        "NPage" | "PageDown" => KeyCode::PageDown,
+        _ if rest.chars().count() == 1 => {
+            let glyph: char = rest.chars().next().unwrap_or_default();
+
+            // The control's characters don't have case.
+            if modifiers.ctrl {
+                KeyCode::Char(glyph.to_ascii_lowercase())
+            } else {
+                KeyCode::Char(glyph)
+            }
+        },
+        _ if rest.starts_with('F') => {
+            match rest[1..].parse::<u8>() {
+                Ok(number) if number > 0 && number <= 35 => KeyCode::Function(number),
+                _ => return None,
+            }
+        },
+        _ => return None,
+    };
+    Some(Key::Code(code, modifiers))
+}
+
+/// The function `parse_action` returns the action of a command's words.
+fn parse_action(words: &[&str]) -> Option<Action> {
+    match words {
+        &["scroll-up"] => Some(Action::ScrollUp(1)),
+        &["scroll-down"] => Some(Action::ScrollDown(1)),
+        &["scroll-up", lines] => lines.parse::<usize>().ok().map(|lines| Action::ScrollUp(lines)),
+        &["scroll-down", lines] => lines.parse::<usize>().ok().map(|lines| Action::ScrollDown(lines)),
+        &["copy-mode"] => Some(Action::CopyMode),
+        &["send-key", key] => parse_key(key).map(|key| Action::SendKey(key)),
+        &["callback", name] => Some(Action::Callback(name.to_string())),
+        _ => None,
+    }
+}
+
+/// The struct `Keymap` intercepts the keys before they are written to
+/// the child: a key is bound without prefix, or after a prefix à la tmux,
+/// to a host's action.
+
+pub struct Keymap {
+    prefix: Option<Key>,
+    /// The bindings after the prefix.
+    bindings: Vec<(Key, Action)>,
+    /// The bindings without prefix.
+    roots: Vec<(Key, Action)>,
+    /// The prefix has been pressed.
+    pending: bool,
+    callbacks: Vec<(String, Box<FnMut() + Send>)>,
+}
+
+impl Keymap {
+
+    /// The constructor method `new` returns a Keymap without binding
+    /// nor prefix.
+    pub fn new() -> Self {
+        Keymap {
+            prefix: None,
+            bindings: Vec::new(),
+            roots: Vec::new(),
+            pending: false,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// The constructor method `from_config` returns a Keymap of a
+    /// configuration, one command by line (`#` starts a comment):
+    ///
+    /// ```text
+    /// prefix C-b
+    /// bind C-b send-key C-b
+    /// bind [ copy-mode
+    /// bind -n S-PageUp scroll-up 10
+    /// bind x callback close
+    /// unbind x
+    /// ```
+    pub fn from_config(config: &str) -> Result<Self, KeymapError> {
+        let mut keymap: Keymap = Keymap::new();
+
+        try!(config.lines().enumerate().map(|(index, line)| {
+            let words: Vec<&str> = line.split('#').next().unwrap_or_default().split_whitespace().collect::<Vec<&str>>();
+            let line: usize = index + 1;
+
+            match words.as_slice() {
+                &[] => Ok(()),
+                &["prefix", "None"] => {
+                    keymap.set_prefix(None);
+                    Ok(())
+                },
+                &["prefix", key] => {
+                    let key: Key = try!(parse_key(key).ok_or(KeymapError::UnknownKey(line)));
+
+                    keymap.set_prefix(Some(key));
+                    Ok(())
+                },
+                &["bind", "-n", key, ref action..] |
+                &["bind", key, ref action..] if !action.is_empty() => {
+                    let key: Key = try!(parse_key(key).ok_or(KeymapError::UnknownKey(line)));
+                    let action: Action = try!(parse_action(action).ok_or(KeymapError::UnknownAction(line)));
+
+                    if words[1].eq("-n") {
+                        keymap.bind_root(key, action);
+                    } else {
+                        keymap.bind(key, action);
+                    }
+                    Ok(())
+                },
+                &["unbind", "-n", key] |
+                &["unbind", key] => {
+                    let key: Key = try!(parse_key(key).ok_or(KeymapError::UnknownKey(line)));
+
+                    keymap.unbind(key, words[1].eq("-n"));
+                    Ok(())
+                },
+                _ => Err(KeymapError::Syntax(line)),
+            }
+        }).collect::<Result<Vec<()>, KeymapError>>());
+        Ok(keymap)
+    }
+
+    /// The constructor method `from_file` returns a Keymap of a
+    /// configuration's file (see `from_config`).
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, KeymapError> {
+        let mut config: String = String::new();
+
+        try!(try!(fs::File::open(path)).read_to_string(&mut config));
+        Keymap::from_config(&config)
+    }
+
+    /// The accessor method `get_prefix` returns the prefix.
+    pub fn get_prefix(&self) -> Option<Key> {
+        self.prefix
+    }
+
+    /// The accessor method `is_pending` returns true when the prefix
+    /// waits for its command.
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// The mutator method `set_prefix` changes the prefix.
+    pub fn set_prefix(&mut self, prefix: Option<Key>) {
+        self.prefix = prefix;
+        self.pending = false;
+    }
+
+    /// The mutator method `set_callback` registers the callback of
+    /// the `Action::Callback` with this name.
+    pub fn set_callback(&mut self, name: &str, callback: Box<FnMut() + Send>) {
+        self.callbacks.retain(|&(ref current, _)| current.ne(name));
+        self.callbacks.push((name.to_string(), callback));
+    }
+
+    /// The method `bind` binds a key after the prefix.
+    pub fn bind(&mut self, key: Key, action: Action) {
+        self.unbind(key, false);
+        self.bindings.push((key, action));
+    }
+
+    /// The method `bind_root` binds a key without prefix.
+    pub fn bind_root(&mut self, key: Key, action: Action) {
+        self.unbind(key, true);
+        self.roots.push((key, action));
+    }
+
+    /// The method `unbind` forgets the binding of a key without prefix
+    /// (root) or after it.
+    pub fn unbind(&mut self, key: Key, root: bool) {
+        if root {
+            self.roots.retain(|&(current, _)| current.ne(&key));
+        } else {
+            self.bindings.retain(|&(current, _)| current.ne(&key));
+        }
+    }
+
+    /// The method `feed` returns the decision for a pressed key, the
+    /// callback of a bound `Action::Callback` is run.
+    pub fn feed(&mut self, key: Key) -> Feed {
+        let key: Key = match key {
+            Key::Repeat(code, modifiers) => Key::Code(code, modifiers),
+            // The releases after the prefix are dropped.
+            Key::Release(_, _) if self.pending => return Feed::Ignore,
+            key => key,
+        };
+        let prefixed: bool = self.pending;
+
+        if prefixed {
+            self.pending = false;
+        } else if self.prefix.eq(&Some(key)) {
+            self.pending = true;
+            return Feed::Prefix;
+        }
+        let action: Option<Action> = if prefixed {
+            self.bindings.as_slice()
+        } else {
+            self.roots.as_slice()
+        }.iter().find(|&&(current, _)| current.eq(&key)).map(|&(_, ref action)| action.clone());
+
+        match action {
+            Some(Action::Callback(name)) => {
+                if let Some(&mut (_, ref mut callback)) = self.callbacks.iter_mut().find(|&&mut (ref current, _)| current.eq(&name)) {
+                    callback();
+                }
+                Feed::Action(Action::Callback(name))
+            },
+            Some(action) => Feed::Action(action),
+            None if prefixed => Feed::Ignore,
+            None => Feed::Forward,
+        }
+    }
+
+    /// The method `feed_state` returns the decision for the pressed key
+    /// of a state, or nothing when there isn't key.
+    pub fn feed_state(&mut self, state: &ShellState) -> Option<Feed> {
+        state.is_input_keydown().map(|key| self.feed(key))
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap::new()
+    }
+}
+
+impl fmt::Debug for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Keymap {{ prefix: {:?}, bindings: {:?}, roots: {:?}, pending: {}, callbacks: {:?} }}",
+               self.prefix, self.bindings, self.roots, self.pending,
+               self.callbacks.iter().map(|&(ref name, _)| name).collect::<Vec<&String>>())
+    }
+}
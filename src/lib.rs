@@ -20,9 +20,16 @@ extern crate pty;
 extern crate libc;
 extern crate time;
 extern crate errno;
+extern crate regex;
 
 #[macro_use] mod macros;
 mod child;
 pub mod parent;
 pub mod shell;
+pub mod session;
+pub mod compositor;
+pub mod server;
+pub mod expect;
+pub mod keymap;
+pub mod copy;
 pub mod prelude;
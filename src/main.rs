@@ -1,11 +1,65 @@
 extern crate pty_proc;
 extern crate libc;
 
-use std::io::Write;
+use std::io::{self, Write};
+use std::process;
+use std::env;
 
 use pty_proc::prelude as shell;
 
-fn main() {
+/// The function `serve` forks a background server which holds
+/// the sessions on the socket's path.
+fn serve(path: &str, command: Option<&str>) {
+    let mut server: shell::Server = shell::Server::bind(path, command).unwrap();
+
+    unsafe {
+        match libc::fork() {
+            -1 => panic!("{}", path),
+            0 => {
+                let null: libc::c_int = libc::open(b"/dev/null\0".as_ptr() as *const libc::c_char, libc::O_RDWR);
+
+                libc::setsid();
+                libc::dup2(null, libc::STDIN_FILENO);
+                libc::dup2(null, libc::STDOUT_FILENO);
+                libc::dup2(null, libc::STDERR_FILENO);
+                let _ = server.run();
+            },
+            // The socket belongs to the child.
+            _ => process::exit(0),
+        }
+    }
+}
+
+/// The function `draw` prints the shell's screen, with the overlay
+/// of the copy mode.
+fn draw(shell: &shell::Shell, copy: Option<&shell::CopyMode>) {
+    print!("\x1B[H{}", shell);
+    match copy {
+        Some(copy) => {
+            let screen: &shell::Display = <shell::Shell as shell::Parent>::get_screen(shell);
+
+            print!("{}", String::from_utf8_lossy(&copy.overlay(screen)));
+        },
+        None => print!("\x1B[?25l"),
+    }
+    let _ = io::stdout().flush();
+}
+
+/// The function `copy_mode` returns a copy mode whose cursor is moved
+/// by lines, the display keeps no scrollback to scroll.
+fn copy_mode(shell: &shell::Shell, up: usize, down: usize) -> shell::CopyMode {
+    let screen: &shell::Display = <shell::Shell as shell::Parent>::get_screen(shell);
+    let mut copy: shell::CopyMode = shell::CopyMode::new(screen, shell::CopyStyle::default());
+    let at: shell::Coordinate = *copy.get_cursor();
+
+    copy.set_cursor(screen, shell::Coordinate::from((at.x, at.y.saturating_sub(up) + down)));
+    copy
+}
+
+/// The function `run` prints a shell on the terminal, each key is
+/// filtered by the keymap, or by the copy mode when it's entered.
+/// A yank is given to the host's clipboard (OSC 52).
+fn run(mut keymap: shell::Keymap) {
     print!("\x1B[?25l\x1B[H\x1B[2J");
 
     let mut shell: shell::Shell = shell::Shell::new(
@@ -14,14 +68,76 @@ fn main() {
         None,
         None,
     ).unwrap();
+    let mut copy: Option<shell::CopyMode> = None;
+
     while let Some(event) = <shell::Shell as Iterator>::next(&mut shell) {
-        if let Some(ref text) = event.is_input_slice() {
-            shell.write(text).unwrap();
-            shell.flush().unwrap();
+        match (event.is_input_keydown(), event.is_input_slice()) {
+            (Some(_), Some(text)) => {
+                for (key, bytes) in shell::Key::split(text) {
+                    if let Some(mut mode) = copy.take() {
+                        let feed: shell::CopyFeed = mode.feed(<shell::Shell as shell::Parent>::get_screen(&shell), key);
+
+                        match feed {
+                            shell::CopyFeed::Yank(yanked) => {
+                                let osc: Vec<u8> = shell::Clipboard::new(b"c", false).answer(yanked.as_bytes());
+
+                                print!("{}", String::from_utf8_lossy(&osc));
+                            },
+                            shell::CopyFeed::Exit => {},
+                            _ => copy = Some(mode),
+                        }
+                        draw(&shell, copy.as_ref());
+                        continue ;
+                    }
+                    match keymap.feed(key) {
+                        shell::Feed::Forward => {
+                            shell.write(bytes).unwrap();
+                            shell.flush().unwrap();
+                        },
+                        shell::Feed::Action(shell::Action::SendKey(key)) => {
+                            shell.send_key(key).unwrap();
+                        },
+                        shell::Feed::Action(shell::Action::CopyMode) => {
+                            copy = Some(copy_mode(&shell, 0, 0));
+                            draw(&shell, copy.as_ref());
+                        },
+                        shell::Feed::Action(shell::Action::ScrollUp(lines)) => {
+                            copy = Some(copy_mode(&shell, lines, 0));
+                            draw(&shell, copy.as_ref());
+                        },
+                        shell::Feed::Action(shell::Action::ScrollDown(lines)) => {
+                            copy = Some(copy_mode(&shell, 0, lines));
+                            draw(&shell, copy.as_ref());
+                        },
+                        // The callbacks are run by the keymap.
+                        shell::Feed::Action(shell::Action::Callback(_)) |
+                        shell::Feed::Prefix | shell::Feed::Ignore => {},
+                    }
+                }
+            },
+            // The pastes, the mouse and the focus aren't keys.
+            (None, Some(text)) if copy.is_none() => {
+                shell.write(text).unwrap();
+                shell.flush().unwrap();
+            },
+            _ => {},
         }
         if let Some(()) = event.is_output_screen() {
-            print!("\x1B[H{}", shell);
-          //  print!("{}", shell);
+            draw(&shell, copy.as_ref());
         }
     }
 }
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    match (args.get(1).map(|arg| arg.as_str()), args.get(2)) {
+        (Some("--server"), Some(path)) => serve(path, args.get(3).map(|command| command.as_str())),
+        (Some("--attach"), Some(path)) => {
+            print!("\x1B[?25l\x1B[H\x1B[2J");
+            shell::attach(path, args.get(3).and_then(|id| id.parse::<shell::SessionId>().ok())).unwrap();
+        },
+        (Some("--keymap"), Some(path)) => run(shell::Keymap::from_file(path).unwrap()),
+        _ => run(shell::Keymap::default()),
+    }
+}
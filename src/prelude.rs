@@ -1,19 +1,31 @@
 #[cfg(feature = "task")]
-pub use ::shell::device::task::{Proc, ProcError, BufProc};
+pub use ::shell::device::task::{Proc, ProcError, ProcessInfo, ProcessTree, ProcEvent, Monitor, Usage, Limit, Limits, BufProc};
 pub use ::shell::{Shell, ShellError, ShellState};
-pub use ::shell::state::DeviceState;
-pub use ::shell::device::control::operate::key::Key;
-pub use ::shell::device::control::operate::mouse::Mouse;
+pub use ::shell::state::{DeviceState, Gesture};
+pub use ::shell::device::control::operate::key::{Key, KeyCode, KeyModes, Modifiers};
+pub use ::shell::device::control::operate::mouse::{Mouse, MouseMode, MouseEncoding};
 pub use ::shell::device::control::operate::mouse::code::Code;
 pub use ::shell::device::control::operate::Operate;
 pub use ::shell::device::control::Control;
 pub use ::shell::device::{In, Out};
+pub use ::shell::device::reactor::{Reactor, ReactorError, Token};
 pub use ::shell::display::Display;
-pub use ::shell::display::Newline;
+pub use ::shell::display::{Newline, Coordinate, Selection, SelectionMode};
+pub use ::shell::display::{Clipboard, ClipboardTarget, ClipboardPolicy, ClipboardProvider};
+pub use ::shell::display::{Mark, MarkKind, Command};
+pub use ::shell::display::{Notification, NotificationHandler};
+pub use ::shell::display::{Search, SearchCase, SearchDirection};
 pub use ::shell::display::winsz::Winszed;
 pub use ::shell::display::character::Character;
 pub use ::shell::termios::Termios;
 pub use ::shell::state::DEFAULT_REPEAT as REPEAT;
 pub use ::shell::state::DEFAULT_INTERVAL as INTERVAL;
+pub use ::shell::state::DEFAULT_CLICK as CLICK;
 pub use ::pty::prelude::Master;
 pub use ::parent::Parent;
+pub use ::session::{SessionManager, SessionError, SessionEvent, SessionId};
+pub use ::compositor::{Compositor, CompositorError, Layout, Rect, Split};
+pub use ::server::{Server, ServerError, Frame, attach};
+pub use ::expect::{Expect, ExpectError, Match};
+pub use ::keymap::{Keymap, KeymapError, Action, Feed};
+pub use ::copy::{CopyMode, CopyStyle, CopyFeed};
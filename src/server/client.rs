@@ -0,0 +1,89 @@
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::io::{self, Read, Write};
+use std::time;
+
+use ::libc;
+
+use ::session::SessionId;
+use ::shell::device::In;
+use ::shell::device::reactor::{Reactor, Token};
+use ::shell::display::winsz::Winszed;
+use ::shell::termios::Termios;
+
+use super::err::ServerError;
+use super::frame::Frame;
+
+/// The key which detaches the client (Ctrl-\).
+const DETACH: u8 = b'\x1C';
+
+/// The delay to check the size of terminal (milliseconds).
+const TM_CLIENT: u64 = 100;
+
+const TOKEN_STDIN: Token = 0;
+const TOKEN_STREAM: Token = 1;
+
+/// The function `attach` connects the terminal to a session of the server
+/// (a new one without identifier) until the detach key (Ctrl-\) is pressed
+/// or the session hangs up.
+pub fn attach<P: AsRef<Path>>(path: P, id: Option<SessionId>) -> Result<(), ServerError> {
+    let mut stream: UnixStream = try!(UnixStream::connect(path));
+    let _config: Termios = try!(Termios::new(libc::STDOUT_FILENO).map_err(|_| ServerError::TermiosFail));
+    let mut size: Winszed = Winszed::new(libc::STDIN_FILENO).unwrap_or_default();
+    let mut reactor: Reactor = Reactor::new();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    try!(stream.write_all(&Frame::Attach(id, size).encode()));
+    reactor.register_fd(libc::STDIN_FILENO, TOKEN_STDIN);
+    reactor.register(&stream, TOKEN_STREAM);
+    loop {
+        let tokens: Vec<Token> = try!(reactor.poll(Some(time::Duration::from_millis(TM_CLIENT)))
+                                             .map_err(|why| ServerError::ReactorFail(why)));
+
+        match Winszed::new(libc::STDIN_FILENO) {
+            Ok(current) if current.ne(&size) => {
+                size = current;
+                try!(stream.write_all(&Frame::Resize(size).encode()));
+            },
+            _ => {},
+        }
+        for token in tokens {
+            if token.eq(&TOKEN_STDIN) {
+                let mut bytes: In = In::default();
+
+                match try!(io::stdin().read(&mut bytes)) {
+                    0 => return stream.write_all(&Frame::Detach.encode()).map_err(|why| ServerError::Io(why)),
+                    len => match bytes[..len].iter().position(|&byte| byte.eq(&DETACH)) {
+                        Some(index) => {
+                            if index > 0 {
+                                try!(stream.write_all(&Frame::Input(bytes[..index].to_vec()).encode()));
+                            }
+                            return stream.write_all(&Frame::Detach.encode()).map_err(|why| ServerError::Io(why));
+                        },
+                        None => try!(stream.write_all(&Frame::Input(bytes[..len].to_vec()).encode())),
+                    },
+                }
+            } else {
+                let mut bytes: [u8; 4096] = [0; 4096];
+
+                match try!(stream.read(&mut bytes)) {
+                    0 => return Ok(()),
+                    len => buffer.extend_from_slice(&bytes[..len]),
+                }
+                while let Some((frame, len)) = try!(Frame::decode(&buffer)) {
+                    buffer.drain(..len);
+                    match frame {
+                        Frame::Output(output) => {
+                            let mut stdout = io::stdout();
+
+                            try!(stdout.write_all(&output));
+                            try!(stdout.flush());
+                        },
+                        Frame::Closed => return Ok(()),
+                        _ => {},
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use ::session::SessionError;
+use ::shell::device::reactor::ReactorError;
+
+/// The enum `ServerError` defines the possible errors from the Server
+/// and its clients.
+
+#[derive(Debug)]
+pub enum ServerError {
+    /// The socket has occured an error.
+    Io(io::Error),
+    /// The SessionManager has occured an error.
+    SessionFail(SessionError),
+    /// The Reactor has occured an error.
+    ReactorFail(ReactorError),
+    /// The Termios has occured an error.
+    TermiosFail,
+    /// The frame's kind is unknown.
+    FrameUnknown(u8),
+    /// The frame's payload is too long or hasn't the length of its kind.
+    FrameMalformed,
+}
+
+impl fmt::Display for ServerError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl Error for ServerError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            ServerError::Io(_) => "The socket has occured an error.",
+            ServerError::SessionFail(_) => "The session manager has occured an error.",
+            ServerError::ReactorFail(_) => "The reactor has occured an error.",
+            ServerError::TermiosFail => "The termios has occured an error.",
+            ServerError::FrameUnknown(_) => "The frame's kind is unknown.",
+            ServerError::FrameMalformed => "The frame's payload is too long or hasn't the length of its kind.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ServerError::Io(ref why) => Some(why),
+            ServerError::SessionFail(ref why) => Some(why),
+            ServerError::ReactorFail(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ServerError {
+    fn from(why: io::Error) -> ServerError {
+        ServerError::Io(why)
+    }
+}
@@ -0,0 +1,148 @@
+use ::libc;
+
+use ::session::SessionId;
+use ::shell::display::winsz::Winszed;
+
+use super::err::ServerError;
+
+/// The length of the frame's header: the kind and the length of payload.
+pub const HEADER_LEN: libc::size_t = 5;
+/// The length of the largest payload, a longer frame is malformed.
+pub const LIMIT_FRAME: libc::size_t = 1 << 20;
+
+/// The identifier sent to attach a new session.
+const NEW_SESSION: u64 = ::std::u64::MAX;
+
+const KIND_ATTACH: u8 = 1;
+const KIND_INPUT: u8 = 2;
+const KIND_RESIZE: u8 = 3;
+const KIND_DETACH: u8 = 4;
+const KIND_ATTACHED: u8 = 5;
+const KIND_OUTPUT: u8 = 6;
+const KIND_CLOSED: u8 = 7;
+
+/// The enum `Frame` is a message exchanged on the socket, encoded
+/// as a kind byte, a big-endian length of four bytes and the payload.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Frame {
+    /// The client attaches a session (a new one without identifier)
+    /// with the size of its terminal.
+    Attach(Option<SessionId>, Winszed),
+    /// The client sends a input to its session.
+    Input(Vec<u8>),
+    /// The client's terminal was redimensioned.
+    Resize(Winszed),
+    /// The client detaches without killing the session.
+    Detach,
+    /// The server confirms the attached session.
+    Attached(SessionId),
+    /// The server sends a output to print.
+    Output(Vec<u8>),
+    /// The session's child has hung up.
+    Closed,
+}
+
+fn encode_u16(value: libc::c_ushort) -> [u8; 2] {
+    [(value >> 8) as u8, value as u8]
+}
+
+fn decode_u16(buf: &[u8]) -> libc::c_ushort {
+    (buf[0] as libc::c_ushort) << 8 | buf[1] as libc::c_ushort
+}
+
+fn encode_u64(value: u64) -> [u8; 8] {
+    let mut buf: [u8; 8] = [0; 8];
+
+    buf.iter_mut().enumerate().all(|(index, byte)| {
+        *byte = (value >> ((7 - index) * 8)) as u8;
+        true
+    });
+    buf
+}
+
+fn decode_u64(buf: &[u8]) -> u64 {
+    buf.iter().take(8).fold(0, |acc, &byte| acc << 8 | byte as u64)
+}
+
+fn encode_winszed(size: &Winszed) -> [u8; 4] {
+    let (row, col) = (encode_u16(size.ws_row), encode_u16(size.ws_col));
+
+    [row[0], row[1], col[0], col[1]]
+}
+
+fn decode_winszed(buf: &[u8]) -> Winszed {
+    Winszed {
+        ws_row: decode_u16(&buf[..2]),
+        ws_col: decode_u16(&buf[2..4]),
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
+
+impl Frame {
+
+    /// The method `encode` returns the bytes of the frame.
+    pub fn encode(&self) -> Vec<u8> {
+        let (kind, payload): (u8, Vec<u8>) = match *self {
+            Frame::Attach(id, ref size) => {
+                let mut payload: Vec<u8> = encode_u64(id.map(|id| id as u64).unwrap_or(NEW_SESSION)).to_vec();
+
+                payload.extend_from_slice(&encode_winszed(size));
+                (KIND_ATTACH, payload)
+            },
+            Frame::Input(ref buf) => (KIND_INPUT, buf.clone()),
+            Frame::Resize(ref size) => (KIND_RESIZE, encode_winszed(size).to_vec()),
+            Frame::Detach => (KIND_DETACH, Vec::new()),
+            Frame::Attached(id) => (KIND_ATTACHED, encode_u64(id as u64).to_vec()),
+            Frame::Output(ref buf) => (KIND_OUTPUT, buf.clone()),
+            Frame::Closed => (KIND_CLOSED, Vec::new()),
+        };
+        let len: u32 = payload.len() as u32;
+        let mut buf: Vec<u8> = Vec::with_capacity(HEADER_LEN + payload.len());
+
+        buf.push(kind);
+        buf.extend_from_slice(&[(len >> 24) as u8, (len >> 16) as u8, (len >> 8) as u8, len as u8]);
+        buf.extend_from_slice(&payload);
+        buf
+    }
+
+    /// The constructor method `decode` returns the first frame of the buffer
+    /// with its length, or `None` when the frame isn't entirely received.
+    /// The length is checked with the header, before the payload is received.
+    pub fn decode(buf: &[u8]) -> Result<Option<(Frame, libc::size_t)>, ServerError> {
+        if buf.len() < HEADER_LEN {
+            return Ok(None);
+        }
+        let len: libc::size_t = buf[1..HEADER_LEN].iter().fold(0, |acc, &byte| acc << 8 | byte as libc::size_t);
+
+        if len > LIMIT_FRAME {
+            return Err(ServerError::FrameMalformed);
+        }
+        if buf.len() < HEADER_LEN + len {
+            return Ok(None);
+        }
+        let payload: &[u8] = &buf[HEADER_LEN..HEADER_LEN + len];
+        let frame: Frame = match (buf[0], len) {
+            (KIND_ATTACH, 12) => {
+                let id: u64 = decode_u64(&payload[..8]);
+
+                Frame::Attach(if id.eq(&NEW_SESSION) {
+                    None
+                } else {
+                    Some(id as SessionId)
+                }, decode_winszed(&payload[8..]))
+            },
+            (KIND_INPUT, _) => Frame::Input(payload.to_vec()),
+            (KIND_RESIZE, 4) => Frame::Resize(decode_winszed(payload)),
+            (KIND_DETACH, 0) => Frame::Detach,
+            (KIND_ATTACHED, 8) => Frame::Attached(decode_u64(payload) as SessionId),
+            (KIND_OUTPUT, _) => Frame::Output(payload.to_vec()),
+            (KIND_CLOSED, 0) => Frame::Closed,
+            (KIND_ATTACH, _) | (KIND_RESIZE, _) | (KIND_DETACH, _) |
+            (KIND_ATTACHED, _) | (KIND_CLOSED, _) => return Err(ServerError::FrameMalformed),
+            (kind, _) => return Err(ServerError::FrameUnknown(kind)),
+        };
+        Ok(Some((frame, HEADER_LEN + len)))
+    }
+}
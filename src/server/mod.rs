@@ -0,0 +1,328 @@
+mod err;
+mod frame;
+mod client;
+
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+use std::io::{self, Read, Write};
+use std::fs;
+
+use ::libc;
+
+use ::parent::Parent;
+use ::session::{SessionId, SessionManager, SessionEvent};
+use ::shell::device::reactor::Token;
+
+pub use self::err::ServerError;
+pub use self::frame::Frame;
+pub use self::client::attach;
+
+/// The token of the listener.
+const TOKEN_LISTENER: Token = ::std::usize::MAX - 1;
+
+/// The size of the buffer to read a socket.
+const SPEC_CAPACITY_READ: libc::size_t = 4096;
+
+/// The largest output waiting for a client, a slower client is dropped.
+const LIMIT_OUTPUT: libc::size_t = 1 << 22;
+
+/// The struct `Client` is a connection attached (or not yet)
+/// to a session.
+
+#[derive(Debug)]
+struct Client {
+    token: Token,
+    stream: UnixStream,
+    /// The received bytes waiting for a complete frame.
+    buffer: Vec<u8>,
+    /// The frames waiting for the socket to be writable.
+    output: Vec<u8>,
+    session: Option<SessionId>,
+}
+
+/// The struct `Server` holds `Shell` sessions which survive their clients:
+/// a client attaches over a Unix domain socket, receives a redraw of the
+/// `Display`, streams the output, sends its input and size, then detaches
+/// without killing the child.
+
+#[derive(Debug)]
+pub struct Server {
+    path: PathBuf,
+    listener: UnixListener,
+    manager: SessionManager,
+    clients: Vec<Client>,
+    /// The next free token of client.
+    next_token: Token,
+    /// The command of new sessions.
+    command: Option<String>,
+}
+
+impl Server {
+
+    /// The constructor method `bind` listens on the socket's path.
+    pub fn bind<P: AsRef<Path>>(path: P, command: Option<&str>) -> Result<Self, ServerError> {
+        let listener: UnixListener = try!(UnixListener::bind(path.as_ref()));
+        let mut manager: SessionManager = SessionManager::headless(None, None);
+
+        try!(listener.set_nonblocking(true));
+        manager.register_fd(listener.as_raw_fd(), TOKEN_LISTENER);
+        Ok(Server {
+            path: path.as_ref().to_path_buf(),
+            listener: listener,
+            manager: manager,
+            clients: Vec::new(),
+            next_token: TOKEN_LISTENER - 1,
+            command: command.map(|command| command.to_string()),
+        })
+    }
+
+    /// The accessor method `get_manager` returns the sessions.
+    pub fn get_manager(&self) -> &SessionManager {
+        &self.manager
+    }
+
+    /// The method `run` serves the clients until the last session
+    /// has hung up.
+    pub fn run(&mut self) -> Result<(), ServerError> {
+        loop {
+            let tokens: Vec<Token> = try!(self.manager.poll(None).map_err(|why| ServerError::SessionFail(why)));
+
+            try!(tokens.into_iter().map(|token| {
+                if token.eq(&TOKEN_LISTENER) {
+                    self.accept()
+                } else {
+                    self.flush(token);
+                    self.read_client(token)
+                }
+            }).collect::<Result<Vec<()>, ServerError>>());
+            while let Some((id, event)) = self.manager.pop_event() {
+                self.dispatch(id, event);
+            }
+            if self.manager.is_empty() && self.clients.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The method `accept` registers the waiting connections.
+    fn accept(&mut self) -> Result<(), ServerError> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    let token: Token = self.next_token;
+
+                    try!(stream.set_nonblocking(true));
+                    self.next_token -= 1;
+                    self.manager.register_fd(stream.as_raw_fd(), token);
+                    self.clients.push(Client {
+                        token: token,
+                        stream: stream,
+                        buffer: Vec::new(),
+                        output: Vec::new(),
+                        session: None,
+                    });
+                },
+                Err(ref why) if why.kind().eq(&io::ErrorKind::WouldBlock) => return Ok(()),
+                Err(why) => return Err(ServerError::Io(why)),
+            }
+        }
+    }
+
+    /// The method `drop_client` closes a connection, its session lives on.
+    fn drop_client(&mut self, token: Token) {
+        if let Some(index) = self.clients.iter().position(|client| client.token.eq(&token)) {
+            let client: Client = self.clients.remove(index);
+
+            let _ = self.manager.deregister_fd(client.stream.as_raw_fd());
+        }
+    }
+
+    /// The method `read_client` reads a connection and handles its frames.
+    fn read_client(&mut self, token: Token) -> Result<(), ServerError> {
+        let mut bytes: [u8; SPEC_CAPACITY_READ] = [0; SPEC_CAPACITY_READ];
+        let read: io::Result<libc::size_t> = match self.clients.iter_mut().find(|client| client.token.eq(&token)) {
+            Some(client) => client.stream.read(&mut bytes),
+            None => return Ok(()),
+        };
+
+        match read {
+            Ok(0) => {
+                self.drop_client(token);
+                Ok(())
+            },
+            Ok(len) => {
+                let frames: Result<Vec<Frame>, ServerError> = self.clients.iter_mut()
+                    .find(|client| client.token.eq(&token))
+                    .map(|client| {
+                        let mut frames: Vec<Frame> = Vec::new();
+
+                        client.buffer.extend_from_slice(&bytes[..len]);
+                        while let Some((frame, len)) = try!(Frame::decode(&client.buffer)) {
+                            client.buffer.drain(..len);
+                            frames.push(frame);
+                        }
+                        Ok(frames)
+                    }).unwrap_or(Ok(Vec::new()));
+
+                match frames {
+                    Ok(frames) => {
+                        frames.into_iter().all(|frame| {
+                            self.handle(token, frame);
+                            true
+                        });
+                        Ok(())
+                    },
+                    Err(_) => {
+                        self.drop_client(token);
+                        Ok(())
+                    },
+                }
+            },
+            Err(ref why) if why.kind().eq(&io::ErrorKind::WouldBlock) => Ok(()),
+            Err(_) => {
+                self.drop_client(token);
+                Ok(())
+            },
+        }
+    }
+
+    /// The method `send` queues a frame to a client and writes what its
+    /// socket accepts without blocking.
+    fn send(&mut self, token: Token, frame: &Frame) {
+        if let Some(client) = self.clients.iter_mut().find(|client| client.token.eq(&token)) {
+            client.output.extend(frame.encode());
+        }
+        self.flush(token);
+    }
+
+    /// The method `flush` writes the queued output of a client until its
+    /// socket would block, the rest waits the socket's writability; the client
+    /// is dropped on a error or when its queue exceeds `LIMIT_OUTPUT`.
+    fn flush(&mut self, token: Token) {
+        let flushed: Option<Option<(RawFd, bool)>> = self.clients.iter_mut()
+            .find(|client| client.token.eq(&token))
+            .map(|client| {
+                let fd: RawFd = client.stream.as_raw_fd();
+
+                while !client.output.is_empty() {
+                    match client.stream.write(&client.output) {
+                        Ok(0) => return None,
+                        Ok(len) => {
+                            client.output.drain(..len);
+                        },
+                        Err(ref why) if why.kind().eq(&io::ErrorKind::Interrupted) => {},
+                        Err(ref why) if why.kind().eq(&io::ErrorKind::WouldBlock) => {
+                            return if client.output.len() > LIMIT_OUTPUT {
+                                None
+                            } else {
+                                Some((fd, true))
+                            };
+                        },
+                        Err(_) => return None,
+                    }
+                }
+                Some((fd, false))
+            });
+
+        match flushed {
+            Some(Some((fd, pending))) => {
+                let _ = self.manager.set_writable(fd, pending);
+            },
+            Some(None) => self.drop_client(token),
+            None => {},
+        }
+    }
+
+    /// The method `redraw` sends the whole screen of a session.
+    fn redraw(&mut self, token: Token, id: SessionId) {
+        if let Some(screen) = self.manager.get(id).map(|shell| format!("\x1B[H{}", shell)) {
+            self.send(token, &Frame::Output(screen.into_bytes()));
+        }
+    }
+
+    /// The method `handle` answers a frame of client.
+    fn handle(&mut self, token: Token, frame: Frame) {
+        let session: Option<SessionId> = self.clients.iter()
+                                                     .find(|client| client.token.eq(&token))
+                                                     .and_then(|client| client.session);
+
+        match (frame, session) {
+            (Frame::Attach(id, size), _) => {
+                let id: Option<SessionId> = match id {
+                    Some(id) if self.manager.get(id).is_some() => Some(id),
+                    Some(_) => None,
+                    None => {
+                        let command: Option<String> = self.command.clone();
+
+                        self.manager.create(command.as_ref().map(|command| command.as_str()), Some(size)).ok()
+                    },
+                };
+                match id {
+                    Some(id) => {
+                        if let Some(shell) = self.manager.get_mut(id) {
+                            shell.set_window_size_with(&size);
+                        }
+                        if let Some(client) = self.clients.iter_mut().find(|client| client.token.eq(&token)) {
+                            client.session = Some(id);
+                        }
+                        self.send(token, &Frame::Attached(id));
+                        self.redraw(token, id);
+                    },
+                    None => self.send(token, &Frame::Closed),
+                }
+            },
+            (Frame::Input(buf), Some(id)) => {
+                self.manager.input_to(id, &buf);
+            },
+            (Frame::Resize(size), Some(id)) => {
+                if let Some(shell) = self.manager.get_mut(id) {
+                    shell.set_window_size_with(&size);
+                }
+                self.redraw(token, id);
+            },
+            (Frame::Detach, _) => self.drop_client(token),
+            _ => {},
+        }
+    }
+
+    /// The method `dispatch` forwards the event of a session
+    /// to its clients.
+    fn dispatch(&mut self, id: SessionId, event: SessionEvent) {
+        let tokens: Vec<Token> = self.clients.iter()
+                                             .filter(|client| client.session.eq(&Some(id)))
+                                             .map(|client| client.token)
+                                             .collect::<Vec<Token>>();
+
+        match event {
+            SessionEvent::State(state) => {
+                if let Some(input) = state.is_input_slice() {
+                    if let Some(shell) = self.manager.get_mut(id) {
+                        let _ = <::shell::Shell as Write>::write(shell, input);
+                    }
+                }
+                if state.is_output_screen().is_some() {
+                    tokens.into_iter().all(|token| {
+                        self.redraw(token, id);
+                        true
+                    });
+                }
+            },
+            SessionEvent::Closed => {
+                tokens.into_iter().all(|token| {
+                    self.send(token, &Frame::Closed);
+                    if let Some(client) = self.clients.iter_mut().find(|client| client.token.eq(&token)) {
+                        client.session = None;
+                    }
+                    true
+                });
+            },
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
@@ -0,0 +1,46 @@
+use std::error::Error;
+use std::fmt;
+
+use ::shell::ShellError;
+use ::shell::device::reactor::ReactorError;
+
+/// The enum `SessionError` defines the possible errors from the SessionManager.
+
+#[derive(Debug)]
+pub enum SessionError {
+    /// The Shell has occured an error.
+    ShellFail(ShellError),
+    /// The Reactor has occured an error.
+    ReactorFail(ReactorError),
+    /// There isn't session with this identifier.
+    NotFound,
+}
+
+impl fmt::Display for SessionError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", ::errno::errno())
+    }
+}
+
+impl Error for SessionError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            SessionError::ShellFail(_) => "The shell has occured an error.",
+            SessionError::ReactorFail(_) => "The reactor has occured an error.",
+            SessionError::NotFound => "There isn't session with this identifier.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            SessionError::ShellFail(ref why) => Some(why),
+            SessionError::ReactorFail(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use ::shell::ShellState;
+
+#[derive(Copy, Clone, Debug)]
+pub enum SessionEvent {
+    /// The new state of a session.
+    State(ShellState),
+    /// The child of a session has hung up.
+    Closed,
+}
+
+impl SessionEvent {
+
+    /// The accessor method `is_state` returns a Option for the State's event.
+    pub fn is_state(&self) -> Option<&ShellState> {
+        match *self {
+            SessionEvent::State(ref state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// The accessor method `is_closed` returns a Option for the Closed's event.
+    pub fn is_closed(&self) -> Option<()> {
+        match *self {
+            SessionEvent::Closed => Some(()),
+            _ => None,
+        }
+    }
+}
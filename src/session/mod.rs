@@ -0,0 +1,320 @@
+mod err;
+mod event;
+
+use std::collections::VecDeque;
+use std::os::unix::io::RawFd;
+use std::io;
+use std::time;
+
+use ::libc;
+
+use ::shell::{Shell, Parent, Winszed};
+use ::shell::device::In;
+use ::shell::device::reactor::{Reactor, Token};
+use ::shell::termios::Termios;
+
+pub use self::err::SessionError;
+pub use self::event::SessionEvent;
+
+/// The type `SessionId` is the identifier of a session.
+pub type SessionId = Token;
+
+/// The token reserved to the standard input.
+const TOKEN_STDIN: Token = ::std::usize::MAX;
+
+/// The default delay of the reactor (milliseconds).
+const TM_SESSION: u64 = 10;
+
+/// The struct `SessionManager` owns many evented `Shell`s, routes the
+/// standard input to the focused one and tags their events with the
+/// session's identifier.
+
+#[derive(Debug)]
+pub struct SessionManager {
+    /// The time limit required for a repetition.
+    repeat: Option<libc::c_long>,
+    /// The time limit required for a interval.
+    interval: Option<libc::c_long>,
+    #[allow(dead_code)]
+    config: Option<Termios>,
+    reactor: Reactor,
+    /// List of sessions by identifier.
+    sessions: Vec<(SessionId, Shell)>,
+    /// The session which receives the input.
+    focus: Option<SessionId>,
+    /// The next free identifier.
+    next_id: SessionId,
+    /// Events waiting to be iterated.
+    events: VecDeque<(SessionId, SessionEvent)>,
+    delay: time::Duration,
+}
+
+impl SessionManager {
+
+    /// The constructor method `new` returns a manager without session
+    /// which owns the standard input.
+    pub fn new (
+        repeat: Option<libc::c_long>,
+        interval: Option<libc::c_long>,
+    ) -> Self {
+        SessionManager::from_host(repeat, interval, true)
+    }
+
+    /// The constructor method `headless` returns a manager without session
+    /// which neither reads the standard input nor configures the terminal
+    /// (the input is given with `input` or `input_to`).
+    pub fn headless (
+        repeat: Option<libc::c_long>,
+        interval: Option<libc::c_long>,
+    ) -> Self {
+        SessionManager::from_host(repeat, interval, false)
+    }
+
+    fn from_host (
+        repeat: Option<libc::c_long>,
+        interval: Option<libc::c_long>,
+        host: bool,
+    ) -> Self {
+        let mut reactor: Reactor = Reactor::new();
+
+        if host {
+            reactor.register_fd(libc::STDIN_FILENO, TOKEN_STDIN);
+        }
+        SessionManager {
+            repeat: repeat,
+            interval: interval,
+            config: if host {
+                Termios::new(libc::STDOUT_FILENO).ok()
+            } else {
+                None
+            },
+            reactor: reactor,
+            sessions: Vec::new(),
+            focus: None,
+            next_id: 0,
+            events: VecDeque::new(),
+            delay: time::Duration::from_millis(TM_SESSION),
+        }
+    }
+
+    /// The method `create` spawns a new session and focuses it when
+    /// it's the only one.
+    pub fn create (
+        &mut self,
+        command: Option<&str>,
+        windows: Option<Winszed>,
+    ) -> Result<SessionId, SessionError> {
+        match Shell::new_evented(self.repeat, self.interval, command, windows) {
+            Err(why) => Err(SessionError::ShellFail(why)),
+            Ok(shell) => {
+                let id: SessionId = self.next_id;
+
+                self.next_id += 1;
+                self.reactor.register(&shell, id);
+                self.sessions.push((id, shell));
+                if self.focus.is_none() {
+                    self.focus = Some(id);
+                }
+                Ok(id)
+            },
+        }
+    }
+
+    /// The method `close` kills the session's child and forgets it,
+    /// the focus goes to the first session left.
+    pub fn close(&mut self, id: SessionId) -> Result<(), SessionError> {
+        match self.sessions.iter().position(|&(current, _)| current.eq(&id)) {
+            None => Err(SessionError::NotFound),
+            Some(index) => {
+                let (_, shell): (SessionId, Shell) = self.sessions.remove(index);
+
+                let _ = self.reactor.deregister(&shell);
+                if self.focus.eq(&Some(id)) {
+                    self.focus = self.sessions.first().map(|&(first, _)| first);
+                }
+                Ok(())
+            },
+        }
+    }
+
+    /// The mutator method `set_focus` changes the session which
+    /// receives the input, the sessions which listen the focus
+    /// are told of the change.
+    pub fn set_focus(&mut self, id: SessionId) -> Result<(), SessionError> {
+        if self.get(id).is_some() {
+            if self.focus.ne(&Some(id)) {
+                if let Some(before) = self.focus.and_then(|before| self.get_mut(before)) {
+                    let _ = before.send_focus(false);
+                }
+                if let Some(after) = self.get_mut(id) {
+                    let _ = after.send_focus(true);
+                }
+            }
+            self.focus = Some(id);
+            Ok(())
+        } else {
+            Err(SessionError::NotFound)
+        }
+    }
+
+    /// The accessor method `get_focus` returns the focused session.
+    pub fn get_focus(&self) -> Option<SessionId> {
+        self.focus
+    }
+
+    /// The accessor method `get` returns a reference on a session.
+    pub fn get(&self, id: SessionId) -> Option<&Shell> {
+        self.sessions.iter()
+                     .find(|&&(current, _)| current.eq(&id))
+                     .map(|&(_, ref shell)| shell)
+    }
+
+    /// The accessor method `get_mut` returns a mutable reference on a session.
+    pub fn get_mut(&mut self, id: SessionId) -> Option<&mut Shell> {
+        self.sessions.iter_mut()
+                     .find(|&&mut (current, _)| current.eq(&id))
+                     .map(|&mut (_, ref mut shell)| shell)
+    }
+
+    /// The accessor method `get_ids` returns the identifiers of sessions
+    /// by creation order.
+    pub fn get_ids(&self) -> Vec<SessionId> {
+        self.sessions.iter().map(|&(id, _)| id).collect::<Vec<SessionId>>()
+    }
+
+    /// The accessor method `len` returns the number of sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// The accessor method `is_empty` returns true when there isn't session.
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// The mutator method `set_window_size_with` redimentionnes all
+    /// the sessions with a argument size.
+    pub fn set_window_size_with(&mut self, size: &Winszed) {
+        self.sessions.iter_mut().all(|&mut (_, ref mut shell)| {
+            shell.set_window_size_with(size);
+            true
+        });
+    }
+
+    /// The method `input` routes a input to the focused session.
+    pub fn input(&mut self, buf: &[u8]) {
+        if let Some(id) = self.focus {
+            self.input_to(id, buf);
+        }
+    }
+
+    /// The method `input_to` routes a input to a session.
+    pub fn input_to(&mut self, id: SessionId, buf: &[u8]) {
+        if let Some(state) = self.get_mut(id).map(|shell| shell.next_input(buf)) {
+            self.events.push_back((id, SessionEvent::State(state)));
+        }
+    }
+
+    /// The method `register_fd` watches a foreign descriptor with the sessions,
+    /// its token must be distinct of the identifiers of sessions
+    /// (counted from zero) and of `usize::MAX`.
+    pub fn register_fd(&mut self, fd: RawFd, token: Token) {
+        self.reactor.register_fd(fd, token);
+    }
+
+    /// The mutator method `set_writable` watches or not the writability
+    /// of a foreign descriptor.
+    pub fn set_writable(&mut self, fd: RawFd, writable: bool) -> Result<(), SessionError> {
+        self.reactor.set_writable(fd, writable).map_err(|why| SessionError::ReactorFail(why))
+    }
+
+    /// The method `deregister_fd` stops to watch a foreign descriptor.
+    pub fn deregister_fd(&mut self, fd: RawFd) -> Result<Token, SessionError> {
+        self.reactor.deregister_fd(fd).map_err(|why| SessionError::ReactorFail(why))
+    }
+
+    /// The method `pop_event` returns the next queued event without waiting.
+    pub fn pop_event(&mut self) -> Option<(SessionId, SessionEvent)> {
+        self.events.pop_front()
+    }
+
+    /// The method `read_stdin` reads the standard input and routes it.
+    fn read_stdin(&mut self) {
+        let mut bytes: In = In::default();
+
+        unsafe {
+            match libc::read(libc::STDIN_FILENO,
+                             bytes.as_mut_ptr() as *mut libc::c_void,
+                             bytes.len()) {
+                -1 => {},
+                0 => {
+                    let _ = self.reactor.deregister_fd(libc::STDIN_FILENO);
+                },
+                len => self.input(&bytes[..len as libc::size_t]),
+            }
+        }
+    }
+
+    /// The method `read_session` reads all the available output of a session,
+    /// the session is closed on the end of file, the hangup (EIO) or
+    /// a error other than a interruption or a empty descriptor.
+    fn read_session(&mut self, id: SessionId) {
+        loop {
+            match self.get_mut(id).map(|shell| shell.next_evented()) {
+                Some(Ok(Some(state))) => {
+                    self.events.push_back((id, SessionEvent::State(state)));
+                },
+                Some(Err(ref why)) if why.kind().eq(&io::ErrorKind::Interrupted) => continue ,
+                Some(Err(ref why)) if why.kind().eq(&io::ErrorKind::WouldBlock) => break ,
+                Some(Ok(None)) | Some(Err(_)) => {
+                    let _ = self.close(id);
+                    self.events.push_back((id, SessionEvent::Closed));
+                    break ;
+                },
+                None => break ,
+            }
+        }
+    }
+
+    /// The method `poll` waits for the readiness of the standard input, of
+    /// a session or of a foreign descriptor until the timeout, queues the
+    /// events of sessions and returns the tokens of foreign descriptors.
+    pub fn poll(&mut self, timeout: Option<time::Duration>) -> Result<Vec<Token>, SessionError> {
+        match self.reactor.poll(timeout) {
+            Err(why) => Err(SessionError::ReactorFail(why)),
+            Ok(tokens) => {
+                Ok(tokens.into_iter().filter(|&token| {
+                    if token.eq(&TOKEN_STDIN) {
+                        self.read_stdin();
+                        false
+                    } else if self.get(token).is_some() {
+                        self.read_session(token);
+                        false
+                    } else {
+                        true
+                    }
+                }).collect::<Vec<Token>>())
+            },
+        }
+    }
+}
+
+impl Iterator for SessionManager {
+    type Item = (SessionId, SessionEvent);
+
+    fn next(&mut self) -> Option<(SessionId, SessionEvent)> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(event);
+            } else if self.sessions.is_empty() {
+                return None;
+            } else {
+                let delay: time::Duration = self.delay;
+
+                if self.poll(Some(delay)).is_err() {
+                    return None;
+                }
+            }
+        }
+    }
+}
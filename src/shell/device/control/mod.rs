@@ -30,6 +30,30 @@ impl Control {
         }
     }
 
+    /// The constructor method `from_paste` returns a Control's event of a
+    /// pasted text with the bytes to send to the child.
+    pub fn from_paste(text: &[libc::c_uchar], bytes: &[libc::c_uchar]) -> Self {
+        let (input, paste): (In, In) = (In::from(bytes), In::from(text));
+
+        Control {
+            input: (input, bytes.len().min(input.len())),
+            time: time::now(),
+            operate: Operate::Paste(paste, text.len().min(paste.len())),
+        }
+    }
+
+    /// The constructor method `from_mouse` returns a Control's event of a
+    /// mouse with the bytes to send to the child.
+    pub fn from_mouse(mouse: Mouse, bytes: &[libc::c_uchar]) -> Self {
+        let input: In = In::from(bytes);
+
+        Control {
+            input: (input, bytes.len().min(input.len())),
+            time: time::now(),
+            operate: Operate::Mouse(mouse),
+        }
+    }
+
     pub fn ss_mod(&mut self, ss: libc::c_uchar) {
         let (ref mut buf, ref mut len) = self.input;
         buf[0] = b'\x1B';
@@ -69,6 +93,18 @@ impl Control {
     pub fn is_mouse(&self) -> Option<Mouse> {
         self.operate.is_mouse()
     }
+
+    /// The accessor method `is_focus` returns an Option for
+    /// the focus (true when gained).
+    pub fn is_focus(&self) -> Option<bool> {
+        self.operate.is_focus()
+    }
+
+    /// The accessor method `is_paste` returns an Option for
+    /// the pasted text.
+    pub fn is_paste(&self) -> Option<&[libc::c_uchar]> {
+        self.operate.is_paste()
+    }
 }
 
 impl fmt::Debug for Control {
@@ -0,0 +1,166 @@
+/// The enum `KeyCode` is the key pressed without its modifiers.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    /// Unicode character.
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    Right,
+    Left,
+    Home,
+    End,
+    Insert,
+    Delete,
+    PageUp,
+    PageDown,
+    /// Function key from 1.
+    Function(u8),
+    /// Keypad's key by its glyph (`'\r'` for the keypad's Enter).
+    Keypad(char),
+}
+
+/// The keypad's glyphs by kitty's code (from 57399) and the final bytes
+/// of their `SS3` sequence in application mode.
+const KEYPAD: [(char, u8); 17] = [
+    ('0', b'p'), ('1', b'q'), ('2', b'r'), ('3', b's'), ('4', b't'),
+    ('5', b'u'), ('6', b'v'), ('7', b'w'), ('8', b'x'), ('9', b'y'),
+    ('.', b'n'), ('/', b'o'), ('*', b'j'), ('-', b'm'), ('+', b'k'),
+    ('\r', b'M'), ('=', b'X'),
+];
+
+impl KeyCode {
+
+    /// The constructor method `from_tilde` returns the key of a
+    /// `CSI n ~` sequence.
+    pub fn from_tilde(number: u32) -> Option<Self> {
+        match number {
+            1 | 7 => Some(KeyCode::Home),
+            2 => Some(KeyCode::Insert),
+            3 => Some(KeyCode::Delete),
+            4 | 8 => Some(KeyCode::End),
+            5 => Some(KeyCode::PageUp),
+            6 => Some(KeyCode::PageDown),
+            11...15 => Some(KeyCode::Function((number - 10) as u8)),
+            17...21 => Some(KeyCode::Function((number - 11) as u8)),
+            23...26 => Some(KeyCode::Function((number - 12) as u8)),
+            28 | 29 => Some(KeyCode::Function((number - 13) as u8)),
+            31...34 => Some(KeyCode::Function((number - 14) as u8)),
+            _ => None,
+        }
+    }
+
+    /// The constructor method `from_final` returns the key of a
+    /// `CSI 1 ; m X` or `SS3 X` sequence.
+    pub fn from_final(end: u8) -> Option<Self> {
+        match end {
+            b'A' => Some(KeyCode::Up),
+            b'B' => Some(KeyCode::Down),
+            b'C' => Some(KeyCode::Right),
+            b'D' => Some(KeyCode::Left),
+            b'H' => Some(KeyCode::Home),
+            b'F' => Some(KeyCode::End),
+            b'P' => Some(KeyCode::Function(1)),
+            b'Q' => Some(KeyCode::Function(2)),
+            b'R' => Some(KeyCode::Function(3)),
+            b'S' => Some(KeyCode::Function(4)),
+            _ => None,
+        }
+    }
+
+    /// The constructor method `from_kitty` returns the key of a kitty's
+    /// `CSI code ; m u` sequence.
+    pub fn from_kitty(code: u32) -> Option<Self> {
+        match code {
+            9 => Some(KeyCode::Tab),
+            13 => Some(KeyCode::Enter),
+            27 => Some(KeyCode::Escape),
+            127 => Some(KeyCode::Backspace),
+            // Function 13 to 35.
+            57376...57398 => Some(KeyCode::Function((code - 57363) as u8)),
+            // Keypad.
+            57399...57415 => Some(KeyCode::Keypad(KEYPAD[(code - 57399) as usize].0)),
+            57344...63743 => None,
+            code => ::std::char::from_u32(code).map(|glyph| KeyCode::Char(glyph)),
+        }
+    }
+
+    /// The accessor method `as_kitty` returns the code of the kitty's
+    /// `CSI code ; m u` sequence of the key.
+    pub fn as_kitty(&self) -> Option<u32> {
+        match *self {
+            KeyCode::Char(glyph) => Some(glyph as u32),
+            KeyCode::Tab => Some(9),
+            KeyCode::Enter => Some(13),
+            KeyCode::Escape => Some(27),
+            KeyCode::Backspace => Some(127),
+            KeyCode::Function(number @ 13...35) => Some(number as u32 + 57363),
+            KeyCode::Keypad(glyph) => {
+                KEYPAD.iter()
+                      .position(|&(keypad, _)| keypad.eq(&glyph))
+                      .map(|index| index as u32 + 57399)
+            },
+            _ => None,
+        }
+    }
+
+    /// The constructor method `from_keypad` returns the keypad's key of a
+    /// `SS3 X` sequence (application mode).
+    pub fn from_keypad(end: u8) -> Option<Self> {
+        KEYPAD.iter()
+              .find(|&&(_, keypad)| keypad.eq(&end))
+              .map(|&(glyph, _)| KeyCode::Keypad(glyph))
+    }
+
+    /// The accessor method `as_keypad` returns the final byte of the
+    /// `SS3 X` sequence of a keypad's key (application mode).
+    pub fn as_keypad(&self) -> Option<u8> {
+        match *self {
+            KeyCode::Keypad(glyph) => {
+                KEYPAD.iter()
+                      .find(|&&(keypad, _)| keypad.eq(&glyph))
+                      .map(|&(_, end)| end)
+            },
+            _ => None,
+        }
+    }
+
+    /// The accessor method `as_tilde` returns the number of the
+    /// `CSI n ~` sequence of the key.
+    pub fn as_tilde(&self) -> Option<u32> {
+        match *self {
+            KeyCode::Insert => Some(2),
+            KeyCode::Delete => Some(3),
+            KeyCode::PageUp => Some(5),
+            KeyCode::PageDown => Some(6),
+            KeyCode::Function(5) => Some(15),
+            KeyCode::Function(number @ 6...10) => Some(number as u32 + 11),
+            KeyCode::Function(number @ 11...14) => Some(number as u32 + 12),
+            KeyCode::Function(number @ 15...16) => Some(number as u32 + 13),
+            KeyCode::Function(number @ 17...20) => Some(number as u32 + 14),
+            _ => None,
+        }
+    }
+
+    /// The accessor method `as_final` returns the final byte of the
+    /// `CSI 1 ; m X` sequence of the key.
+    pub fn as_final(&self) -> Option<u8> {
+        match *self {
+            KeyCode::Up => Some(b'A'),
+            KeyCode::Down => Some(b'B'),
+            KeyCode::Right => Some(b'C'),
+            KeyCode::Left => Some(b'D'),
+            KeyCode::Home => Some(b'H'),
+            KeyCode::End => Some(b'F'),
+            KeyCode::Function(1) => Some(b'P'),
+            KeyCode::Function(2) => Some(b'Q'),
+            KeyCode::Function(3) => Some(b'R'),
+            KeyCode::Function(4) => Some(b'S'),
+            _ => None,
+        }
+    }
+}
@@ -1,362 +1,772 @@
-use std::ops::Shr;
-use std::mem;
+mod code;
+mod modifiers;
+mod modes;
+
 use std::char;
+use std::str;
 use std::io::Write;
 
 use ::libc;
 
 pub use super::In;
-
-// TODO: Add this key event.
-//
-//            Key::Insert,
-//            Key::F()
-//           &[b'\x1B', b'[', b'1', b';', b'2', b'A', ..] => Key::ShiftUp,
-//           &[b'\x1B', b'[', b'1', b';', b'2', b'B', ..] => Key::ShiftDown,
-//           &[b'\x1B', b'[', b'1', b';', b'2', b'C', ..] => Key::ShiftRight,
-//           &[b'\x1B', b'[', b'1', b';', b'2', b'D', ..] => Key::ShiftLeft,
-//           &[b'\x1B', b'[', b'1', b';', b'5', b'A', ..] => Key::CtrlUp,
-//           &[b'\x1B', b'[', b'1', b';', b'5', b'B', ..] => Key::CtrlDown,
-//           &[b'\x1B', b'[', b'1', b';', b'5', b'C', ..] => Key::CtrlRight,
-//           &[b'\x1B', b'[', b'1', b';', b'5', b'D', ..] => Key::CtrlLeft,
-//           &[b'\x1B', b'[', b'1', b';', b'6', b'A', ..] => Key::CtrlShiftUp,
-//           &[b'\x1B', b'[', b'1', b';', b'6', b'B', ..] => Key::CtrlShiftDown,
-//           &[b'\x1B', b'[', b'1', b';', b'6', b'C', ..] => Key::CtrlShiftRight,
-//           &[b'\x1B', b'[', b'1', b';', b'6', b'D', ..] => Key::CtrlShiftLeft,
-//           &[b'\x1B', b'[', b'1', b';', b'1', b'0', b'A', ..] => Key::AltShiftUp,
-//           &[b'\x1B', b'[', b'1', b';', b'1', b'0', b'B', ..] => Key::AltShiftDown,
-//           &[b'\x1B', b'[', b'1', b';', b'1', b'0', b'C', ..] => Key::AltShiftRight,
-//           &[b'\x1B', b'[', b'1', b';', b'1', b'0', b'D', ..] => Key::AltShiftLeft,
-
-pub const ENTER: u64 = 0xa;
-pub const TABULATION: u64 = 0x9;
-pub const BACKSPACE: u64 = 0x7f;
-pub const ESCAPE: u64 = 0x1b;
-pub const UP: u64 = 0x415b1b;
-pub const DOWN: u64 = 0x425b1b;
-pub const RIGHT: u64 = 0x435b1b;
-pub const LEFT: u64 = 0x445b1b;
-pub const END: u64 = 0x465b1b;
-pub const HOME: u64 = 0x7e315b1b;
-pub const DELETE: u64 = 0x7e335b1b;
-pub const PAGE_UP: u64 = 0x7e355b1b;
-pub const PAGE_DOWN: u64 = 0x7e365b1b;
-pub const ALT_UP: u64 = 0x41393b311b;
-pub const ALT_DOWN: u64 = 0x42393b311b;
-pub const ALT_RIGHT: u64 = 0x43393b311b;
-pub const ALT_LEFT: u64 = 0x44393b311b;
-pub const FUNCTION_1: u64 = 0x504f5b1b;
-pub const FUNCTION_2: u64 = 0x514f5b1b;
-pub const FUNCTION_3: u64 = 0x524f5b1b;
-pub const FUNCTION_4: u64 = 0x534f5b1b;
-pub const FUNCTION_5: u64 = 0x7e35315b1b;
-pub const FUNCTION_6: u64 = 0x7e37315b1b;
-pub const FUNCTION_7: u64 = 0x7e38315b1b;
-pub const FUNCTION_8: u64 = 0x7e39315b1b;
-pub const FUNCTION_9: u64 = 0x7e30325b1b;
-pub const FUNCTION_10: u64 = 0x7e31325b1b;
-pub const FUNCTION_11: u64 = 0x7e33325b1b;
-pub const FUNCTION_12: u64 = 0x7e34325b1b;
-pub const FUNCTION_13: u64 = 0x50323b315b1b;
-pub const FUNCTION_14: u64 = 0x51323b315b1b;
-pub const FUNCTION_15: u64 = 0x52323b315b1b;
-pub const FUNCTION_16: u64 = 0x53323b315b1b;
-pub const FUNCTION_17: u64 = 0x7e31335b1b;
-pub const FUNCTION_18: u64 = 0x7e32335b1b;
-pub const FUNCTION_19: u64 = 0x7e33335b1b;
+pub use self::code::KeyCode;
+pub use self::modifiers::{Modifiers, NONE, SHIFT, CTRL, ALT, META};
+pub use self::modes::{KeyModes, PASTE_START, PASTE_END};
+
+/// The kitty's flag to disambiguate the escape codes.
+pub const KITTY_DISAMBIGUATE: u32 = 1;
+/// The kitty's flag to report the repeat and release events.
+pub const KITTY_EVENT: u32 = 2;
+/// The kitty's flag to report the alternate keys.
+pub const KITTY_ALTERNATE: u32 = 4;
+/// The kitty's flag to report all the keys as escape codes.
+pub const KITTY_ALL: u32 = 8;
+/// The kitty's flag to report the associated text.
+pub const KITTY_TEXT: u32 = 16;
+
+/// The enum `Key` is a decoded key event.
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Key {
-    /// Unicode characters.
-    Char(u64),
+    /// Key pressed with its modifiers.
+    Code(KeyCode, Modifiers),
+    /// Key repeated with its modifiers (kitty's event type 2).
+    Repeat(KeyCode, Modifiers),
+    /// Key released with its modifiers (kitty's event type 3).
+    Release(KeyCode, Modifiers),
     /// Unicode strings.
     Str(In),
 }
 
+/// The function `catch_params` returns the numeric parameters (each one
+/// with its colon separated sub-parameters) and the final byte of a control
+/// sequence (after its introducer).
+fn catch_params(next: &[libc::c_uchar]) -> Option<(Vec<Vec<u32>>, libc::c_uchar)> {
+    let mut params: Vec<Vec<u32>> = vec![vec![0]];
+
+    for (index, &byte) in next.iter().enumerate() {
+        match byte {
+            b'0'...b'9' => {
+                if let Some(param) = params.last_mut().and_then(|param| param.last_mut()) {
+                    *param = param.saturating_mul(10).saturating_add((byte - b'0') as u32);
+                }
+            },
+            b':' => {
+                if let Some(param) = params.last_mut() {
+                    param.push(0);
+                }
+            },
+            b';' => params.push(vec![0]),
+            b'\x40'...b'\x7E' if index + 1 == next.len() => return Some((params, byte)),
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// The function `sequence_len` returns the length of the first key's
+/// sequence of a input: a control sequence, a SS3, Alt with a character
+/// or a character.
+fn sequence_len(buf: &[libc::c_uchar]) -> libc::size_t {
+    let utf8 = |byte: libc::c_uchar| {
+        if byte >= 0xF0 { 4 } else if byte >= 0xE0 { 3 } else if byte >= 0xC0 { 2 } else { 1 }
+    };
+    let len: libc::size_t = match buf {
+        // The X10's mouse sends three raw bytes after its final.
+        &[b'\x1B', b'[', b'M', ..] => 6,
+        &[b'\x1B', b'[', ref next..] => {
+            next.iter().position(|&byte| byte >= 0x40 && byte <= 0x7E)
+                       .map(|index| index + 3)
+                       .unwrap_or(buf.len())
+        },
+        &[b'\x1B', b'O', _, ..] => 3,
+        &[b'\x1B', byte, ..] => 1 + utf8(byte),
+        &[byte, ..] => utf8(byte),
+        &[] => 0,
+    };
+
+    len.min(buf.len())
+}
+
+/// The function `ctrl_byte` returns the C0 byte of Ctrl with a character.
+fn ctrl_byte(glyph: char) -> Option<libc::c_uchar> {
+    match glyph {
+        ' ' | '@' => Some(0),
+        'a'...'z' => Some(glyph as libc::c_uchar - b'a' + 1),
+        'A'...'Z' => Some(glyph as libc::c_uchar - b'A' + 1),
+        '[' | '\\' | ']' | '^' | '_' => Some(glyph as libc::c_uchar - b'@'),
+        _ => None,
+    }
+}
+
 impl Key {
 
+    /// The constructor method `from_c0` returns the Key of a single byte.
+    pub fn from_c0(byte: libc::c_uchar) -> Self {
+        match byte {
+            b'\t' => Key::Code(KeyCode::Tab, NONE),
+            b'\n' | b'\r' => Key::Code(KeyCode::Enter, NONE),
+            b'\x1B' => Key::Code(KeyCode::Escape, NONE),
+            b'\x7F' => Key::Code(KeyCode::Backspace, NONE),
+            0 => Key::Code(KeyCode::Char(' '), CTRL),
+            1...26 => Key::Code(KeyCode::Char((b'a' + byte - 1) as char), CTRL),
+            28...31 => Key::Code(KeyCode::Char((byte + b'@') as char), CTRL),
+            _ => Key::Code(KeyCode::Char(byte as char), NONE),
+        }
+    }
+
     /// The constructor method `from_utf8` returns a UTF-8 parsed Key.
     pub fn from_utf8(buf: [libc::c_uchar; 4]) -> Self {
-        unsafe {
-            let i: libc::c_uint = mem::transmute::<[libc::c_uchar; 4], u32>(buf);
+        let len: libc::size_t = buf.iter().position(|c| c.eq(&b'\0')).unwrap_or(buf.len());
+
+        Key::from_slice(&buf[..len]).unwrap_or_default()
+    }
+
+    /// The function `split` returns the keys of a input with their bytes,
+    /// a read of the host can hold many keys.
+    pub fn split(buf: &[libc::c_uchar]) -> Vec<(Key, &[libc::c_uchar])> {
+        let mut keys: Vec<(Key, &[libc::c_uchar])> = Vec::new();
+        let mut rest: &[libc::c_uchar] = buf;
+
+        while !rest.is_empty() {
+            let (bytes, next): (&[libc::c_uchar], &[libc::c_uchar]) = rest.split_at(sequence_len(rest));
+            let input: In = In::from(bytes);
+
+            keys.push((Key::from((input, bytes.len().min(input.len()))), bytes));
+            rest = next;
+        }
+        keys
+    }
+
+    /// The function `from_slice` returns the Key of a single character.
+    fn from_slice(next: &[libc::c_uchar]) -> Option<Self> {
+        match next {
+            &[byte] => Some(Key::from_c0(byte)),
+            next => {
+                str::from_utf8(next).ok().and_then(|text| {
+                    let mut glyphs = text.chars();
+
+                    match (glyphs.next(), glyphs.next()) {
+                        (Some(glyph), None) => Some(Key::Code(KeyCode::Char(glyph), NONE)),
+                        _ => None,
+                    }
+                })
+            },
+        }
+    }
+
+    /// The function `from_csi` returns the Key of a `CSI` sequence.
+    fn from_csi(next: &[libc::c_uchar]) -> Option<Self> {
+        catch_params(next).and_then(|(fields, end)| {
+            let params: Vec<u32> = fields.iter().map(|field| field[0]).collect::<Vec<u32>>();
+            let modifiers: Modifiers = params.get(1)
+                                             .map(|&param| Modifiers::from_param(param))
+                                             .unwrap_or_default();
+            // The kitty's event type is the sub-parameter of the modifiers.
+            let event: u32 = fields.get(1)
+                                   .and_then(|field| field.get(1))
+                                   .cloned()
+                                   .unwrap_or(1);
+
+            match (end, params.as_slice()) {
+                // kitty's keyboard protocol: `CSI code ; m : event u`.
+                (b'u', _) => KeyCode::from_kitty(params[0]).map(|code| Key::Code(code, modifiers)),
+                // xterm's modifyOtherKeys: `CSI 27 ; m ; code ~`.
+                (b'~', &[27, param, code]) => {
+                    Key::from_code(code).map(|key| key.with_modifiers(Modifiers::from_param(param)))
+                },
+                (b'~', _) => KeyCode::from_tilde(params[0]).map(|code| Key::Code(code, modifiers)),
+                (b'Z', &[0]) => Some(Key::Code(KeyCode::Tab, SHIFT)),
+                (end, _) if params[0] <= 1 => {
+                    KeyCode::from_final(end).map(|code| Key::Code(code, modifiers))
+                },
+                _ => None,
+            }.map(|key| key.with_event(event))
+        })
+    }
+
+    /// The function `from_ss3` returns the Key of a `SS3` sequence.
+    fn from_ss3(next: &[libc::c_uchar]) -> Option<Self> {
+        catch_params(next).and_then(|(params, end)| {
+            KeyCode::from_final(end).or_else(|| KeyCode::from_keypad(end)).map(|code| {
+                Key::Code(code, Modifiers::from_param(params[0][0]))
+            })
+        })
+    }
+
+    /// The function `from_code` returns the Key of a Unicode code point.
+    fn from_code(code: u32) -> Option<Self> {
+        match code {
+            0...127 => Some(Key::from_c0(code as libc::c_uchar)),
+            code => char::from_u32(code).map(|glyph| Key::Code(KeyCode::Char(glyph), NONE)),
+        }
+    }
+
+    /// The method `with_modifiers` returns the Key with more modifiers.
+    pub fn with_modifiers(self, more: Modifiers) -> Self {
+        match self {
+            Key::Code(code, modifiers) => Key::Code(code, modifiers | more),
+            Key::Repeat(code, modifiers) => Key::Repeat(code, modifiers | more),
+            Key::Release(code, modifiers) => Key::Release(code, modifiers | more),
+            key => key,
+        }
+    }
+
+    /// The method `with_event` returns the Key with the kitty's event type
+    /// (1 pressed, 2 repeated, 3 released).
+    pub fn with_event(self, event: u32) -> Self {
+        match (self.is_code(), event) {
+            (Some(code), 2) => Key::Repeat(code, self.get_modifiers()),
+            (Some(code), 3) => Key::Release(code, self.get_modifiers()),
+            (Some(code), _) => Key::Code(code, self.get_modifiers()),
+            (None, _) => self,
+        }
+    }
 
-            Key::Char(i as u64)
+    /// The accessor method `is_code` returns the key without its modifiers.
+    pub fn is_code(&self) -> Option<KeyCode> {
+        match *self {
+            Key::Code(code, _) |
+            Key::Repeat(code, _) |
+            Key::Release(code, _) => Some(code),
+            Key::Str(_) => None,
         }
     }
 
+    /// The accessor method `get_modifiers` returns the modifiers.
+    pub fn get_modifiers(&self) -> Modifiers {
+        match *self {
+            Key::Code(_, modifiers) |
+            Key::Repeat(_, modifiers) |
+            Key::Release(_, modifiers) => modifiers,
+            Key::Str(_) => NONE,
+        }
+    }
+
+    /// The accessor method `is_repeat` returns true when the key
+    /// is reported repeated.
+    pub fn is_repeat(&self) -> bool {
+        match *self {
+            Key::Repeat(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// The accessor method `is_release` returns true when the key
+    /// is reported released.
+    pub fn is_release(&self) -> bool {
+        match *self {
+            Key::Release(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// The accessor method `is_utf8` returns the character typed
+    /// without Ctrl, Alt nor Meta.
     pub fn is_utf8(&self) -> Option<char> {
         match *self {
-            Key::Char(e) if e.shr(&32u64).eq(&0) => unsafe {
-                Some(char::from_u32_unchecked(e as u32))
-            },
+            Key::Code(KeyCode::Char(glyph), Modifiers { ctrl: false, alt: false, meta: false, .. }) => Some(glyph),
             _ => None,
         }
     }
 
+    /// The accessor method `as_c0` returns the C0 byte of the key.
+    pub fn as_c0(&self) -> Option<libc::c_uchar> {
+        match *self {
+            Key::Code(KeyCode::Enter, NONE) => Some(b'\n'),
+            Key::Code(KeyCode::Tab, NONE) => Some(b'\t'),
+            Key::Code(KeyCode::Escape, NONE) => Some(b'\x1B'),
+            Key::Code(KeyCode::Char(glyph), CTRL) => ctrl_byte(glyph),
+            _ => None,
+        }
+    }
+
+    fn is(&self, code: KeyCode) -> bool {
+        self.eq(&Key::Code(code, NONE))
+    }
+
     /// The accessor method `is_up` returns an Option for the Up Key.
     pub fn is_up(&self) -> bool {
-        self.eq(&Key::Char(UP))
+        self.is(KeyCode::Up)
     }
 
     /// The accessor method `is_down` returns an Option for the down Key.
     pub fn is_down(&self) -> bool {
-        self.eq(&Key::Char(DOWN))
+        self.is(KeyCode::Down)
     }
 
     /// The accessor method `is_right` returns an Option for the right Key.
     pub fn is_right(&self) -> bool {
-        self.eq(&Key::Char(RIGHT))
+        self.is(KeyCode::Right)
     }
 
     /// The accessor method `is_left` returns an Option for the left Key.
     pub fn is_left(&self) -> bool {
-        self.eq(&Key::Char(LEFT))
+        self.is(KeyCode::Left)
     }
 
     /// The accessor method `is_delete` returns an Option for the delete Key.
     pub fn is_delete(&self) -> bool {
-        self.eq(&Key::Char(DELETE))
+        self.is(KeyCode::Delete)
     }
 
     /// The accessor method `is_backspace` returns an Option for the backspace Key.
     pub fn is_backspace(&self) -> bool {
-        self.eq(&Key::Char(BACKSPACE))
+        self.is(KeyCode::Backspace)
     }
 
     /// The accessor method `is_escape` returns an Option for the delete Key.
     pub fn is_escape(&self) -> bool {
-        self.eq(&Key::Char(ESCAPE))
+        self.is(KeyCode::Escape)
     }
 
     /// The accessor method `is_end` returns an Option for the delete Key.
     pub fn is_end(&self) -> bool {
-        self.eq(&Key::Char(END))
+        self.is(KeyCode::End)
     }
 
     /// The accessor method `is_home` returns an Option for the delete Key.
     pub fn is_home(&self) -> bool {
-        self.eq(&Key::Char(HOME))
+        self.is(KeyCode::Home)
     }
 
     /// The accessor method `is_pageup` returns an Option for the Page Up Key.
     pub fn is_pageup(&self) -> bool {
-        self.eq(&Key::Char(PAGE_UP))
+        self.is(KeyCode::PageUp)
     }
 
     /// The accessor method `is_pagedown` returns an Option for the Page Down Key.
     pub fn is_pagedown(&self) -> bool {
-        self.eq(&Key::Char(PAGE_DOWN))
+        self.is(KeyCode::PageDown)
     }
 
     pub fn is_c0(&self) -> bool {
-        match *self {
-            Key::Char(e @ 0...31) => e.eq(&e),
-            _ => false,
-        }
+        self.as_c0().is_some()
     }
 
     pub fn is_null(&self) -> bool {
-        self.eq(&Key::Char(0))
+        self.as_c0().eq(&Some(0))
     }
 
     pub fn is_start_heading(&self) -> bool {
-        self.eq(&Key::Char(1))
+        self.as_c0().eq(&Some(1))
     }
 
     pub fn is_start_text(&self) -> bool {
-        self.eq(&Key::Char(178))
+        self.as_c0().eq(&Some(2))
     }
 
     pub fn is_end_text(&self) -> bool {
-        self.eq(&Key::Char(3))
+        self.as_c0().eq(&Some(3))
     }
 
     pub fn is_end_transmission(&self) -> bool {
-        self.eq(&Key::Char(4))
+        self.as_c0().eq(&Some(4))
     }
 
     pub fn is_enquiry(&self) -> bool {
-        self.eq(&Key::Char(5))
+        self.as_c0().eq(&Some(5))
     }
 
     pub fn is_acknowledge(&self) -> bool {
-        self.eq(&Key::Char(6))
+        self.as_c0().eq(&Some(6))
     }
 
     pub fn is_bell(&self) -> bool {
-        self.eq(&Key::Char(7))
+        self.as_c0().eq(&Some(7))
     }
 
     pub fn is_horizontal_tabulation(&self) -> bool {
-        self.eq(&Key::Char(TABULATION))
+        self.is(KeyCode::Tab)
     }
 
     /// The accessor method `is_enter` returns an Option for the Enter Key.
     pub fn is_enter(&self) -> bool {
-        self.eq(&Key::Char(ENTER))
+        self.is(KeyCode::Enter)
     }
 
     pub fn is_line_feed(&self) -> bool {
-        self.eq(&Key::Char(ENTER))
+        self.is(KeyCode::Enter)
     }
 
     pub fn is_vertical_tabulation(&self) -> bool {
-        self.eq(&Key::Char(11))
+        self.as_c0().eq(&Some(11))
     }
 
     pub fn is_form_feed(&self) -> bool {
-        self.eq(&Key::Char(12))
+        self.as_c0().eq(&Some(12))
     }
 
     pub fn is_carriage_return(&self) -> bool {
-        self.eq(&Key::Char(13))
+        self.as_c0().eq(&Some(13))
     }
 
     pub fn is_shift_out(&self) -> bool {
-        self.eq(&Key::Char(14))
+        self.as_c0().eq(&Some(14))
     }
 
     pub fn is_shift_in(&self) -> bool {
-        self.eq(&Key::Char(15))
+        self.as_c0().eq(&Some(15))
     }
 
     pub fn is_data_link_escape(&self) -> bool {
-        self.eq(&Key::Char(16))
+        self.as_c0().eq(&Some(16))
     }
 
     pub fn is_device_control_one(&self) -> bool {
-        self.eq(&Key::Char(17))
+        self.as_c0().eq(&Some(17))
     }
 
     pub fn is_device_control_two(&self) -> bool {
-        self.eq(&Key::Char(18))
+        self.as_c0().eq(&Some(18))
     }
 
     pub fn is_device_control_three(&self) -> bool {
-        self.eq(&Key::Char(19))
+        self.as_c0().eq(&Some(19))
     }
 
     pub fn is_device_control_four(&self) -> bool {
-        self.eq(&Key::Char(20))
+        self.as_c0().eq(&Some(20))
     }
 
     pub fn is_negative_acknowledge(&self) -> bool {
-        self.eq(&Key::Char(21))
+        self.as_c0().eq(&Some(21))
     }
 
     pub fn is_synchronous_idle(&self) -> bool {
-        self.eq(&Key::Char(22))
+        self.as_c0().eq(&Some(22))
     }
 
     pub fn is_end_transmission_block(&self) -> bool {
-        self.eq(&Key::Char(23))
+        self.as_c0().eq(&Some(23))
     }
 
     pub fn is_cancel(&self) -> bool {
-        self.eq(&Key::Char(24))
+        self.as_c0().eq(&Some(24))
     }
 
     pub fn is_end_of_medium(&self) -> bool {
-        self.eq(&Key::Char(25))
+        self.as_c0().eq(&Some(25))
     }
 
     pub fn is_substitute(&self) -> bool {
-        self.eq(&Key::Char(26))
+        self.as_c0().eq(&Some(26))
     }
 
     pub fn is_file_separator(&self) -> bool {
-        self.eq(&Key::Char(28))
+        self.as_c0().eq(&Some(28))
     }
 
     pub fn is_group_separator(&self) -> bool {
-        self.eq(&Key::Char(29))
+        self.as_c0().eq(&Some(29))
     }
 
     pub fn is_record_separator(&self) -> bool {
-        self.eq(&Key::Char(30))
+        self.as_c0().eq(&Some(30))
     }
 
     pub fn is_unit_separator(&self) -> bool {
-        self.eq(&Key::Char(31))
+        self.as_c0().eq(&Some(31))
     }
 
     pub fn is_space(&self) -> bool {
-        self.eq(&Key::Char(32))
+        self.is(KeyCode::Char(' '))
     }
 
     pub fn is_c1(&self) -> bool {
         match *self {
-            Key::Char(e @ 128...159) => e.eq(&e),
+            Key::Code(KeyCode::Char('\u{80}'...'\u{9F}'), _) => true,
             _ => false,
         }
     }
 
-    pub fn as_input(&self) -> (In, libc::size_t) {
+    /// The method `as_bytes` returns the xterm's sequence of the key,
+    /// nothing for a released key.
+    pub fn as_bytes(&self) -> Vec<libc::c_uchar> {
         match *self {
-            Key::Char(key) => unsafe {
-                let mut input: In = In::default();
-                let buf: [u8; 8] = mem::transmute::<u64, [u8; 8]>(key);
-                let _ = input.write(&buf[..]);
-
-                (input, input.iter().position(|c| c.eq(&b'\0')).unwrap_or_default())
+            Key::Release(_, _) => Vec::new(),
+            Key::Code(code, modifiers) |
+            Key::Repeat(code, modifiers) => {
+                let mut bytes: Vec<libc::c_uchar> = Vec::new();
+                let alt: bool = modifiers.alt || modifiers.meta;
+                let plain: Modifiers = Modifiers { alt: false, meta: false, ..modifiers };
+
+                match (code, plain) {
+                    (KeyCode::Char(glyph), NONE) | (KeyCode::Char(glyph), SHIFT) => {
+                        let mut utf8: [libc::c_uchar; 4] = [0; 4];
+
+                        if alt {
+                            bytes.push(b'\x1B');
+                        }
+                        bytes.extend_from_slice(glyph.encode_utf8(&mut utf8).as_bytes());
+                    },
+                    (KeyCode::Char(glyph), CTRL) if ctrl_byte(glyph).is_some() => {
+                        if alt {
+                            bytes.push(b'\x1B');
+                        }
+                        bytes.extend(ctrl_byte(glyph));
+                    },
+                    (KeyCode::Enter, NONE) |
+                    (KeyCode::Tab, NONE) |
+                    (KeyCode::Escape, NONE) |
+                    (KeyCode::Backspace, NONE) => {
+                        if alt {
+                            bytes.push(b'\x1B');
+                        }
+                        bytes.push(match code {
+                            KeyCode::Enter => b'\r',
+                            KeyCode::Tab => b'\t',
+                            KeyCode::Escape => b'\x1B',
+                            _ => b'\x7F',
+                        });
+                    },
+                    (KeyCode::Tab, SHIFT) if !alt => bytes.extend_from_slice(b"\x1B[Z"),
+                    (KeyCode::Keypad('\r'), _) => {
+                        return Key::Code(KeyCode::Enter, modifiers).as_bytes();
+                    },
+                    (KeyCode::Keypad(glyph), _) => {
+                        return Key::Code(KeyCode::Char(glyph), modifiers).as_bytes();
+                    },
+                    (KeyCode::Char(glyph), _) => {
+                        let _ = write!(bytes, "\x1B[27;{};{}~", modifiers.as_param(), glyph as u32);
+                    },
+                    (KeyCode::Enter, _) |
+                    (KeyCode::Tab, _) |
+                    (KeyCode::Escape, _) |
+                    (KeyCode::Backspace, _) => {
+                        let _ = write!(bytes, "\x1B[27;{};{}~", modifiers.as_param(), match code {
+                            KeyCode::Enter => 13,
+                            KeyCode::Tab => 9,
+                            KeyCode::Escape => 27,
+                            _ => 127,
+                        });
+                    },
+                    (code, _) => {
+                        match (code.as_final(), code.as_tilde(), modifiers.is_empty()) {
+                            (Some(end), _, true) => {
+                                if let KeyCode::Function(_) = code {
+                                    bytes.extend_from_slice(&[b'\x1B', b'O', end]);
+                                } else {
+                                    bytes.extend_from_slice(&[b'\x1B', b'[', end]);
+                                }
+                            },
+                            (Some(end), _, false) => {
+                                let _ = write!(bytes, "\x1B[1;{}{}", modifiers.as_param(), end as char);
+                            },
+                            (None, Some(number), true) => {
+                                let _ = write!(bytes, "\x1B[{}~", number);
+                            },
+                            (None, Some(number), false) => {
+                                let _ = write!(bytes, "\x1B[{};{}~", number, modifiers.as_param());
+                            },
+                            (None, None, _) => {},
+                        }
+                    },
+                }
+                bytes
             },
             Key::Str(input) => {
-                (input, input.iter().position(|c| c.eq(&b'\0')).unwrap_or_default())
+                input[..input.iter().position(|c| c.eq(&b'\0')).unwrap_or(input.len())].to_vec()
             },
         }
     }
+
+    /// The method `encode` returns the sequence of the key that a terminal
+    /// sends according to the child's modes.
+    pub fn encode(&self, modes: &KeyModes) -> Vec<libc::c_uchar> {
+        let (code, modifiers): (KeyCode, Modifiers) = match *self {
+            _ if modes.keyboard > 0 => return self.as_kitty(modes.keyboard),
+            Key::Code(code, modifiers) |
+            Key::Repeat(code, modifiers) => (code, modifiers),
+            Key::Release(_, _) |
+            Key::Str(_) => return self.as_bytes(),
+        };
+        let alt: bool = modifiers.alt || modifiers.meta;
+        let plain: Modifiers = Modifiers { alt: false, meta: false, ..modifiers };
+        let mut bytes: Vec<libc::c_uchar> = Vec::new();
+
+        if alt {
+            bytes.push(b'\x1B');
+        }
+        match (code, plain) {
+            (KeyCode::Up, NONE) |
+            (KeyCode::Down, NONE) |
+            (KeyCode::Right, NONE) |
+            (KeyCode::Left, NONE) |
+            (KeyCode::Home, NONE) |
+            (KeyCode::End, NONE) if modes.cursor => {
+                bytes.extend_from_slice(&[b'\x1B', b'O']);
+                bytes.extend(code.as_final());
+            },
+            (KeyCode::Keypad(_), NONE) if modes.keypad => {
+                bytes.extend_from_slice(&[b'\x1B', b'O']);
+                bytes.extend(code.as_keypad());
+            },
+            (KeyCode::Keypad('\r'), _) => {
+                return Key::Code(KeyCode::Enter, modifiers).encode(modes);
+            },
+            (KeyCode::Keypad(glyph), _) => {
+                return Key::Code(KeyCode::Char(glyph), modifiers).encode(modes);
+            },
+            // The Alt of the cursor and function keys is a parameter.
+            (code, _) if code.as_final().is_some() || code.as_tilde().is_some() => {
+                return Key::Code(code, modifiers).as_bytes();
+            },
+            // The Ctrl swaps BS and DEL.
+            (KeyCode::Backspace, NONE) |
+            (KeyCode::Backspace, CTRL) => {
+                bytes.push(if modes.backspace ^ plain.ctrl { b'\x08' } else { b'\x7F' });
+            },
+            // The modifyOtherKeys level 2 reports all the modified keys,
+            // the level 1 only the keys without legacy sequence.
+            (KeyCode::Char(glyph), plain) if modes.modify_other_keys > 0
+                                          && !(modifiers.is_empty() || modifiers.eq(&SHIFT)) => {
+                let legacy: bool = plain.eq(&NONE) || plain.eq(&SHIFT)
+                                || (plain.eq(&CTRL) && ctrl_byte(glyph).is_some());
+
+                if modes.modify_other_keys > 1 || !legacy {
+                    let _ = write!(bytes, "\x1B[27;{};{}~", modifiers.as_param(), glyph as u32);
+
+                    return bytes;
+                }
+                bytes.extend(Key::Code(code, plain).as_bytes());
+            },
+            // Without modifyOtherKeys, the modifiers without legacy
+            // sequence are lost.
+            (KeyCode::Char(glyph), CTRL) if ctrl_byte(glyph).is_none() => {
+                bytes.extend(Key::Code(code, NONE).as_bytes());
+            },
+            (KeyCode::Char(glyph), plain) if !(plain.eq(&NONE) || plain.eq(&SHIFT) || plain.eq(&CTRL)) => {
+                bytes.extend(Key::Code(code, if ctrl_byte(glyph).is_some() { CTRL } else { NONE }).as_bytes());
+            },
+            (KeyCode::Enter, plain) |
+            (KeyCode::Tab, plain) |
+            (KeyCode::Escape, plain) |
+            (KeyCode::Backspace, plain) if modes.modify_other_keys.eq(&0)
+                                     && !plain.eq(&NONE)
+                                     && !(code.eq(&KeyCode::Tab) && plain.eq(&SHIFT)) => {
+                bytes.extend(Key::Code(code, NONE).as_bytes());
+            },
+            _ => {
+                bytes.extend(Key::Code(code, plain).as_bytes());
+            },
+        }
+        bytes
+    }
+
+    /// The method `as_kitty` returns the sequence of the key according
+    /// to the kitty's progressive enhancement flags.
+    pub fn as_kitty(&self, flags: u32) -> Vec<libc::c_uchar> {
+        let (code, modifiers): (KeyCode, Modifiers) = match (self.is_code(), flags) {
+            (Some(code), 1...31) => (code, self.get_modifiers()),
+            _ => return self.as_bytes(),
+        };
+        let escaped: bool = flags & KITTY_ALL != 0 || match code {
+            KeyCode::Char(_) |
+            KeyCode::Enter |
+            KeyCode::Tab |
+            KeyCode::Backspace => {
+                !(modifiers.is_empty() || modifiers.eq(&SHIFT))
+            },
+            _ => true,
+        };
+        let event: u32 = match *self {
+            Key::Repeat(_, _) if flags & KITTY_EVENT != 0 => 2,
+            Key::Release(_, _) if flags & KITTY_EVENT != 0 => 3,
+            Key::Release(_, _) => return Vec::new(),
+            _ => 1,
+        };
+
+        if !escaped {
+            // The text keys stay as text, they are released in silence.
+            return if event.eq(&3) {
+                Vec::new()
+            } else {
+                Key::Code(code, modifiers).as_bytes()
+            };
+        }
+
+        let (code, modifiers): (KeyCode, Modifiers) = match code {
+            KeyCode::Char(glyph) if glyph.is_uppercase() => {
+                (KeyCode::Char(glyph.to_lowercase().next().unwrap_or(glyph)), modifiers | SHIFT)
+            },
+            code => (code, modifiers),
+        };
+        let mut bytes: Vec<libc::c_uchar> = Vec::new();
+        let param: String = match (modifiers.as_param(), event) {
+            (1, 1) => String::new(),
+            (param, 1) => format!("{}", param),
+            (param, event) => format!("{}:{}", param, event),
+        };
+        // The Function 3 is `CSI 13 ~` (`CSI R` is a cursor's report).
+        let tilde: Option<u32> = match code {
+            KeyCode::Function(3) => Some(13),
+            code => code.as_tilde(),
+        };
+
+        match (code.as_kitty(), code.as_final(), tilde) {
+            (Some(number), _, _) if param.is_empty() => {
+                let _ = write!(bytes, "\x1B[{}u", number);
+            },
+            (Some(number), _, _) => {
+                let _ = write!(bytes, "\x1B[{};{}u", number, param);
+            },
+            (None, _, Some(number)) if param.is_empty() => {
+                let _ = write!(bytes, "\x1B[{}~", number);
+            },
+            (None, _, Some(number)) => {
+                let _ = write!(bytes, "\x1B[{};{}~", number, param);
+            },
+            (None, Some(end), None) if param.is_empty() => {
+                let _ = write!(bytes, "\x1B[{}", end as char);
+            },
+            (None, Some(end), None) => {
+                let _ = write!(bytes, "\x1B[1;{}{}", param, end as char);
+            },
+            (None, None, None) => {},
+        }
+        bytes
+    }
+
+    pub fn as_input(&self) -> (In, libc::size_t) {
+        let bytes: Vec<libc::c_uchar> = self.as_bytes();
+
+        (In::from(bytes.as_slice()), bytes.len())
+    }
+}
+
+impl Default for Key {
+    fn default() -> Key {
+        Key::Str(In::default())
+    }
 }
 
 impl From<u32> for Key {
     fn from(code: u32) -> Self {
         match code {
-            // Enter.
-            13 => Key::Char(ENTER),
-            // BackSpace.
-            127 => Key::Char(BACKSPACE),
             // Up.
-            63232 => Key::Char(UP),
+            63232 => Key::Code(KeyCode::Up, NONE),
             // Down.
-            63233 => Key::Char(DOWN),
+            63233 => Key::Code(KeyCode::Down, NONE),
             // Right.
-            63235 => Key::Char(RIGHT),
+            63235 => Key::Code(KeyCode::Right, NONE),
             // Left.
-            63234 => Key::Char(LEFT),
+            63234 => Key::Code(KeyCode::Left, NONE),
             // End.
-            63275 => Key::Char(END),
+            63275 => Key::Code(KeyCode::End, NONE),
             // Home.
-            63273 => Key::Char(HOME),
+            63273 => Key::Code(KeyCode::Home, NONE),
+            // Insert.
+            63271 => Key::Code(KeyCode::Insert, NONE),
             // Delete.
-            63272 => Key::Char(DELETE),
+            63272 => Key::Code(KeyCode::Delete, NONE),
             // Page Up.
-            63276 => Key::Char(PAGE_UP),
+            63276 => Key::Code(KeyCode::PageUp, NONE),
             // Page Down.
-            63277 => Key::Char(PAGE_DOWN),
-            // Function 1.
-            63236 => Key::Char(FUNCTION_1),
-            // Function 2.
-            63237 => Key::Char(FUNCTION_2),
-            // Function 3.
-            63238 => Key::Char(FUNCTION_3),
-            // Function 4.
-            63239 => Key::Char(FUNCTION_4),
-            // Function 5.
-            63240 => Key::Char(FUNCTION_5),
-            // Function 6.
-            63241 => Key::Char(FUNCTION_6),
-            // Function 7.
-            63242 => Key::Char(FUNCTION_7),
-            // Function 8.
-            63243 => Key::Char(FUNCTION_8),
-            // Function 9.
-            63244 => Key::Char(FUNCTION_9),
-            // Function 10.
-            63245 => Key::Char(FUNCTION_10),
-            // Function 11.
-            63246 => Key::Char(FUNCTION_11),
-            // Function 12.
-            63247 => Key::Char(FUNCTION_12),
-            code => Key::Char(code as u64),
+            63277 => Key::Code(KeyCode::PageDown, NONE),
+            // Function 1 to 35.
+            63236...63270 => Key::Code(KeyCode::Function((code - 63235) as u8), NONE),
+            code => Key::from_code(code).unwrap_or_default(),
         }
     }
 }
@@ -366,88 +776,19 @@ impl From<(In, libc::size_t)> for Key {
     fn from((buf, len): (In, libc::size_t)) -> Self {
         match &buf[..len] {
             // Enter.
-            &[b'\n'] | &[b'\r'] | &[b'\n', b'\r'] => Key::Char(ENTER),
-            // BackSpace.
-            &[b'\x7f'] => Key::Char(BACKSPACE),
-            // Up.
-            &[b'\x1B', b'[', b'A'] => Key::Char(UP),
-            // Down.
-            &[b'\x1B', b'[', b'B'] => Key::Char(DOWN),
-            // Right.
-            &[b'\x1B', b'[', b'C'] => Key::Char(RIGHT),
-            // Left.
-            &[b'\x1B', b'[', b'D'] => Key::Char(LEFT),
-            // End.
-            &[b'\x1B', b'[', b'F'] => Key::Char(END),
-            // Home.
-            &[b'\x1B', b'[', b'1', b'~'] => Key::Char(HOME),
-            // Delete.
-            &[b'\x1B', b'[', b'3', b'~'] => Key::Char(DELETE),
-            // Page Up.
-            &[b'\x1B', b'[', b'5', b'~'] => Key::Char(PAGE_UP),
-            // Page Down.
-            &[b'\x1B', b'[', b'6', b'~'] => Key::Char(PAGE_DOWN),
-            // Alt Up.
-            &[b'\x1B', b'[', b'1', b';', b'9', b'A'] => Key::Char(ALT_UP),
-            // Alt Down.
-            &[b'\x1B', b'[', b'1', b';', b'9', b'B'] => Key::Char(ALT_DOWN),
-            // Alt Right.
-            &[b'\x1B', b'[', b'1', b';', b'9', b'C'] => Key::Char(ALT_RIGHT),
-            // Alt Left.
-            &[b'\x1B', b'[', b'1', b';', b'9', b'D'] => Key::Char(ALT_LEFT),
-            // Function 1.
-            &[b'\x1B', b'O', b'P'] => Key::Char(FUNCTION_1),
-            // Function 2.
-            &[b'\x1B', b'O', b'Q'] => Key::Char(FUNCTION_2),
-            // Function 3.
-            &[b'\x1B', b'O', b'R'] => Key::Char(FUNCTION_3),
-            // Function 4.
-            &[b'\x1B', b'O', b'S'] => Key::Char(FUNCTION_4),
-            // Function 5.
-            &[b'\x1B', b'[', b'1', b'5', b'~'] => Key::Char(FUNCTION_5),
-            // Function 6.
-            &[b'\x1B', b'[', b'1', b'7', b'~'] => Key::Char(FUNCTION_6),
-            // Function 7.
-            &[b'\x1B', b'[', b'1', b'8', b'~'] => Key::Char(FUNCTION_7),
-            // Function 8.
-            &[b'\x1B', b'[', b'1', b'9', b'~'] => Key::Char(FUNCTION_8),
-            // Function 9.
-            &[b'\x1B', b'[', b'2', b'0', b'~'] => Key::Char(FUNCTION_9),
-            // Function 10.
-            &[b'\x1B', b'[', b'2', b'1', b'~'] => Key::Char(FUNCTION_10),
-            // Function 11.
-            &[b'\x1B', b'[', b'2', b'3', b'~'] => Key::Char(FUNCTION_11),
-            // Function 12.
-            &[b'\x1B', b'[', b'2', b'4', b'~'] => Key::Char(FUNCTION_12),
-            // Function 13.
-            &[b'\x1B', b'[', b'2', b'5', b'~'] | &[b'\x1B', b'[', b'1', b';', b'2', b'P'] => Key::Char(FUNCTION_13),
-            // Function 14.
-            &[b'\x1B', b'[', b'2', b'6', b'~'] | &[b'\x1B', b'[', b'1', b';', b'2', b'Q'] => Key::Char(FUNCTION_14),
-            // Function 15.
-            &[b'\x1B', b'[', b'2', b'8', b'~'] | &[b'\x1B', b'[', b'1', b';', b'2', b'R'] => Key::Char(FUNCTION_15),
-            // Function 16.
-            &[b'\x1B', b'[', b'2', b'9', b'~'] | &[b'\x1B', b'[', b'1', b';', b'2', b'S'] => Key::Char(FUNCTION_16),
-            // Function 17.
-            &[b'\x1B', b'[', b'3', b'1', b'~'] => Key::Char(FUNCTION_17),
-            // Function 18.
-            &[b'\x1B', b'[', b'3', b'2', b'~'] => Key::Char(FUNCTION_18),
-            // Function 19.
-            &[b'\x1B', b'[', b'3', b'3', b'~'] => Key::Char(FUNCTION_19),
-            // Byte 0.
-            &[u1] => Key::Char(u1 as u64),
-            // Byte 1.
-            &[u1, u2] => unsafe {
-                Key::Char(mem::transmute::<[u8; 2], u16>([u1, u2]) as u64)
-            },
-            // Byte 2.
-            &[u1, u2, u3]  => unsafe {
-                Key::Char(mem::transmute::<[u8; 4], u32>([u1, u2, u3, b'\0']) as u64)
+            &[b'\n', b'\r'] => Key::Code(KeyCode::Enter, NONE),
+            // Control Sequence Introducer.
+            &[b'\x1B', b'[', ref next..] => Key::from_csi(next).unwrap_or(Key::Str(buf)),
+            // Single Shift Three.
+            &[b'\x1B', b'O', ref next..] if !next.is_empty() => {
+                Key::from_ss3(next).unwrap_or(Key::Str(buf))
             },
-            // Byte 3.
-            &[u1, u2, u3, u4] => unsafe {
-                Key::Char(mem::transmute::<[u8; 4], u32>([u1, u2, u3, u4]) as u64)
+            // Alt with a character.
+            &[b'\x1B', ref next..] if !next.is_empty() => {
+                Key::from_slice(next).map(|key| key.with_modifiers(ALT))
+                                     .unwrap_or(Key::Str(buf))
             },
-            _ => Key::Str(buf),
+            next => Key::from_slice(next).unwrap_or(Key::Str(buf)),
         }
     }
 }
@@ -0,0 +1,79 @@
+/// The struct `KeyModes` is the state of the child's terminal modes
+/// which change the bytes sent for a key.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct KeyModes {
+    /// DECCKM, the cursor keys send `SS3` sequences.
+    pub cursor: bool,
+    /// DECKPAM, the keypad sends `SS3` sequences.
+    pub keypad: bool,
+    /// DECBKM, the backspace sends BS instead of DEL.
+    pub backspace: bool,
+    /// The pasted text is bracketed by `CSI 200 ~` and `CSI 201 ~`.
+    pub paste: bool,
+    /// The xterm's modifyOtherKeys level.
+    pub modify_other_keys: u32,
+    /// The kitty's keyboard flags.
+    pub keyboard: u32,
+}
+
+/// The start of a bracketed paste.
+pub const PASTE_START: &'static [u8] = b"\x1B[200~";
+/// The end of a bracketed paste.
+pub const PASTE_END: &'static [u8] = b"\x1B[201~";
+
+/// The function `strip` returns a text without the paste's markers.
+fn strip(text: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    let mut index: usize = 0;
+
+    while index < text.len() {
+        let rest: &[u8] = &text[index..];
+
+        if rest.starts_with(PASTE_START) || rest.starts_with(PASTE_END) {
+            index += PASTE_START.len();
+        } else {
+            bytes.push(text[index]);
+            index += 1;
+        }
+    }
+    bytes
+}
+
+/// The function `sanitize` returns a text without the paste's markers
+/// nor escapes, so no marker can be joined again, even between the parts
+/// of a paste.
+fn sanitize(text: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = strip(text);
+
+    // The removal of a inner marker can join a outer one.
+    while bytes.windows(PASTE_START.len()).any(|window| window.eq(PASTE_START) || window.eq(PASTE_END)) {
+        bytes = strip(&bytes);
+    }
+    bytes.retain(|&byte| byte.ne(&b'\x1B'));
+    bytes
+}
+
+impl KeyModes {
+
+    /// The method `paste` returns a pasted text bracketed or not
+    /// according to the mode, without the markers embedded in the text.
+    pub fn paste(&self, text: &[u8]) -> Vec<u8> {
+        self.paste_part(text, true, true)
+    }
+
+    /// The method `paste_part` returns a part of pasted text, the first part
+    /// opens the bracket and the last one closes it.
+    pub fn paste_part(&self, text: &[u8], first: bool, last: bool) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(text.len() + PASTE_START.len() + PASTE_END.len());
+
+        if self.paste && first {
+            bytes.extend_from_slice(PASTE_START);
+        }
+        bytes.extend(sanitize(text));
+        if self.paste && last {
+            bytes.extend_from_slice(PASTE_END);
+        }
+        bytes
+    }
+}
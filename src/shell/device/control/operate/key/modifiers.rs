@@ -0,0 +1,62 @@
+use std::ops::BitOr;
+
+/// The struct `Modifiers` is the state of the modifier keys,
+/// encoded by xterm as the parameter `m` of `CSI 1 ; m X`.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
+/// Without modifier.
+pub const NONE: Modifiers = Modifiers { shift: false, ctrl: false, alt: false, meta: false };
+pub const SHIFT: Modifiers = Modifiers { shift: true, ctrl: false, alt: false, meta: false };
+pub const CTRL: Modifiers = Modifiers { shift: false, ctrl: true, alt: false, meta: false };
+pub const ALT: Modifiers = Modifiers { shift: false, ctrl: false, alt: true, meta: false };
+pub const META: Modifiers = Modifiers { shift: false, ctrl: false, alt: false, meta: true };
+
+impl Modifiers {
+
+    /// The constructor method `from_param` returns the modifiers of
+    /// a xterm parameter (one plus the bits shift, alt, ctrl and meta),
+    /// the kitty's bits super and meta are both read as meta.
+    pub fn from_param(param: u32) -> Self {
+        let bits: u32 = param.checked_sub(1).unwrap_or_default();
+
+        Modifiers {
+            shift: bits & 1 != 0,
+            alt: bits & 2 != 0,
+            ctrl: bits & 4 != 0,
+            meta: bits & (8 | 32) != 0,
+        }
+    }
+
+    /// The accessor method `as_param` returns the xterm parameter.
+    pub fn as_param(&self) -> u32 {
+        1 + self.shift as u32
+          + ((self.alt as u32) << 1)
+          + ((self.ctrl as u32) << 2)
+          + ((self.meta as u32) << 3)
+    }
+
+    /// The accessor method `is_empty` returns true without modifier.
+    pub fn is_empty(&self) -> bool {
+        self.eq(&NONE)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers {
+            shift: self.shift | rhs.shift,
+            ctrl: self.ctrl | rhs.ctrl,
+            alt: self.alt | rhs.alt,
+            meta: self.meta | rhs.meta,
+        }
+    }
+}
@@ -13,12 +13,18 @@ pub enum Operate {
     Mouse(Mouse),
     /// The key operate.
     Key(Key),
+    /// The pasted text operate.
+    Paste(In, libc::size_t),
+    /// The focus operate (true when the focus is gained).
+    Focus(bool),
 }
 
 impl Operate {
     /// The constructor method `new` returns evaluated Operate.
     pub fn new(buf: In, len: libc::size_t) -> Self {
-        if let Ok(mouse) = Mouse::new(&buf[..len]) {
+        if let &[b'\x1B', b'[', focus @ b'I'] | &[b'\x1B', b'[', focus @ b'O'] = &buf[..len] {
+            Operate::Focus(focus.eq(&b'I'))
+        } else if let Ok(mouse) = Mouse::new(&buf[..len]) {
             Operate::Mouse(mouse)
         } else {
             Operate::Key(Key::from((buf, len)))
@@ -29,7 +35,7 @@ impl Operate {
     pub fn is_mouse(&self) -> Option<Mouse> {
         match *self {
             Operate::Mouse(mouse) => Some(mouse),
-            Operate::Key(_) => None,
+            _ => None,
         }
     }
 
@@ -41,10 +47,30 @@ impl Operate {
         }
     }
 
+    /// The accessor method `is_focus` returns a Option for the Focus Operate.
+    pub fn is_focus(&self) -> Option<bool> {
+        match *self {
+            Operate::Focus(focus) => Some(focus),
+            _ => None,
+        }
+    }
+
+    /// The accessor method `is_paste` returns a Option for the pasted text.
+    pub fn is_paste(&self) -> Option<&[libc::c_uchar]> {
+        match *self {
+            Operate::Paste(ref text, len) => Some(&text[..len]),
+            _ => None,
+        }
+    }
+
     pub fn as_input(&self) -> (In, libc::size_t) {
         match *self {
             Operate::Key(key) => key.as_input(),
             Operate::Mouse(mouse) => mouse.as_input(),
+            Operate::Paste(text, len) => (text, len),
+            Operate::Focus(focus) => {
+                (In::from(if focus { &b"\x1B[I"[..] } else { &b"\x1B[O"[..] }), 3)
+            },
         }
     }
 }
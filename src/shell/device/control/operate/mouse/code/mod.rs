@@ -117,6 +117,14 @@ pub enum Code {
     CmdShiftCtrlWheelDrag = 61,
     /// The right mouse button, Ctrl, Shift and Command are held while moving pointer
     CmdShiftCtrlRightDrag = 62,
+    /// The pointer is moved without button.
+    Moved = 35,
+    /// The pointer is moved without button while helding Shift.
+    ShiftMoved = 39,
+    /// The pointer is moved without button while helding Command.
+    CmdMoved = 43,
+    /// The pointer is moved without button while helding Ctrl.
+    CtrlMoved = 51,
 }
 
 impl Code {
@@ -191,8 +199,8 @@ impl Code {
             b'\x18' => Ok(Code::CmdCtrlLeft),
             b'\x19' => Ok(Code::CmdCtrlWheel),
             b'\x1A' => Ok(Code::CmdCtrlRight),
-            b'\x58' => Ok(Code::CmdWheelUp),
-            b'\x59' => Ok(Code::CmdWheelDown),
+            b'\x58' => Ok(Code::CmdCtrlWheelUp),
+            b'\x59' => Ok(Code::CmdCtrlWheelDown),
 
             // Command Control Drag.
             b'\x38' => Ok(Code::CmdCtrlLeftDrag),
@@ -206,11 +214,49 @@ impl Code {
 
             // Command Shift Control Drag.
             b'\x3C' => Ok(Code::CmdShiftCtrlLeftDrag),
-            b'\x3E' => Ok(Code::CmdShiftCtrlWheelDrag),
-            b'\x3F' => Ok(Code::CmdShiftCtrlRightDrag),
+            b'\x3D' => Ok(Code::CmdShiftCtrlWheelDrag),
+            b'\x3E' => Ok(Code::CmdShiftCtrlRightDrag),
+
+            // Motion.
+            b'\x23' => Ok(Code::Moved),
+            b'\x27' => Ok(Code::ShiftMoved),
+            b'\x2B' => Ok(Code::CmdMoved),
+            b'\x33' => Ok(Code::CtrlMoved),
             _ => Err(CodeError::NotImplemented),
         }
     }
+
+    /// The method `as_action` returns the button's value of the
+    /// mouse protocol, the reverse of `new`.
+    pub fn as_action(&self) -> u8 {
+        match *self {
+            Code::Right => 2,
+            Code::Wheel => 1,
+            code => code as u8,
+        }
+    }
+
+    /// The method `is_motion` returns true when the pointer is moved.
+    pub fn is_motion(&self) -> bool {
+        self.as_action() & 32 != 0
+    }
+
+    /// The method `is_moved` returns true when the pointer is moved
+    /// without button.
+    pub fn is_moved(&self) -> bool {
+        self.as_action() & 35 == 35
+    }
+
+    /// The method `is_wheel` returns true when the wheel is scrolled.
+    pub fn is_wheel(&self) -> bool {
+        self.as_action() & 64 != 0
+    }
+
+    /// The method `get_button` returns the pressed button without
+    /// the modifiers (0 left, 1 wheel, 2 right, 3 none).
+    pub fn get_button(&self) -> u8 {
+        self.as_action() & 3
+    }
 }
 
 impl From<u8> for Code {
@@ -1,4 +1,5 @@
 mod err;
+mod mode;
 pub mod code;
 
 use std::str;
@@ -7,7 +8,9 @@ use std::io::Write;
 use ::libc;
 
 use super::In;
+use ::shell::display::Winszed;
 pub use self::err::MouseError;
+pub use self::mode::{MouseMode, MouseEncoding};
 use self::code::Code;
 
 #[repr(C)]
@@ -19,42 +22,170 @@ pub struct Mouse {
     pub y: libc::c_ushort,
 }
 
+/// The function `catch_numbers` returns the decimal numbers separated
+/// by ';' and the rest of the buffer.
+fn catch_numbers(buf: &[u8]) -> (Vec<libc::c_uint>, &[u8]) {
+    let mut numbers: Vec<libc::c_uint> = Vec::new();
+    let mut index: usize = 0;
+
+    loop {
+        let len: usize = buf[index..].iter().take_while(|&&digit| digit >= b'0' && digit <= b'9').count();
+
+        if len == 0 {
+            return (numbers, &buf[index..]);
+        }
+        numbers.push(unsafe { str::from_utf8_unchecked(&buf[index..index + len]) }.parse::<libc::c_uint>().unwrap_or_default());
+        index += len;
+        if buf.get(index).eq(&Some(&b';')) {
+            index += 1;
+        } else {
+            return (numbers, &buf[index..]);
+        }
+    }
+}
+
+/// The function `push_utf8` pushes a value of the UTF-8 encoding (`?1005`).
+fn push_utf8(bytes: &mut Vec<u8>, value: libc::c_uint) -> bool {
+    match ::std::char::from_u32(value + 32) {
+        Some(glyph) if value + 32 < 2048 => {
+            let mut buf: [u8; 4] = [0; 4];
+
+            bytes.extend_from_slice(glyph.encode_utf8(&mut buf).as_bytes());
+            true
+        },
+        _ => false,
+    }
+}
+
 impl Mouse {
-    fn with_code(action: u8, next: &[u8]) -> Result<Self, MouseError> {
-        match next {
-            &[ref coordinate.., m @ b'M'...b'm'] => {
-                match Code::new(action) {
-                    Ok(code) => {
-                        coordinate.iter().position(|&sep| sep.eq(&b';')).map(|index: usize| unsafe {
-                            let (term_x, term_y): (&[u8], &[u8]) = coordinate.split_at(index);
-                            let term_y: &[u8] = &term_y[1..term_y.len()];
-                            if let (Ok(x), Ok(y)) = (
-                                u16::from_str_radix(str::from_utf8_unchecked(term_x), 10),
-                                u16::from_str_radix(str::from_utf8_unchecked(term_y), 10)
-                            ) {
-                                Ok(Mouse::from((code, m.eq(&b'M'), [x, y]))) 
-                            } else {
-                                Err(MouseError::FromStrFail)
+
+    /// The function `with_legacy` returns a Mouse from the values of `CSI M`
+    /// or of the urxvt's encoding (the button's value is shifted by 32).
+    fn with_legacy(action: libc::c_uint, x: libc::c_uint, y: libc::c_uint) -> Result<Self, MouseError> {
+        if action < 32 || x < 32 || y < 32 || action > 255 {
+            Err(MouseError::FromStrFail)
+        } else {
+            let action: u8 = action as u8 - 32;
+            // Without motion, the button 3 is a release of any button.
+            let (action, pressed): (u8, bool) = if action & 35 == 3 {
+                (action & !3, false)
+            } else {
+                (action, true)
+            };
+
+            match Code::new(action) {
+                Ok(code) => Ok(Mouse::from((code, pressed, [(x - 32) as libc::c_ushort, (y - 32) as libc::c_ushort]))),
+                Err(why) => Err(MouseError::Code(why)),
+            }
+        }
+    }
+
+    /// The constructor method `new` returns a Mouse from a report
+    /// of the SGR, urxvt, UTF-8 or default encoding.
+    pub fn new(buf: &[u8]) -> Result<Self, MouseError> {
+        match buf {
+            &[b'\x1B', b'[', b'<', ref next..] => {
+                match catch_numbers(next) {
+                    (ref numbers, &[m @ b'M']) | (ref numbers, &[m @ b'm']) if numbers.len() == 3 => {
+                        if numbers[0] > 255 || numbers[1] > 0xFFFF || numbers[2] > 0xFFFF {
+                            Err(MouseError::FromStrFail)
+                        } else {
+                            match Code::new(numbers[0] as u8) {
+                                Ok(code) => Ok(Mouse::from((code, m.eq(&b'M'), [numbers[1] as libc::c_ushort, numbers[2] as libc::c_ushort]))),
+                                Err(why) => Err(MouseError::Code(why)),
                             }
-                        }).unwrap_or(Err(MouseError::PositionNotFound))
+                        }
                     },
-                    Err(why) => Err(MouseError::Code(why)),
+                    (ref numbers, _) if numbers.len() > 1 => Err(MouseError::Other),
+                    _ => Err(MouseError::PositionNotFound),
+                }
+            },
+            &[b'\x1B', b'[', b'M', ref next..] => {
+                let glyphs: Vec<libc::c_uint> = str::from_utf8(next).ok().map(|text| {
+                    text.chars().map(|glyph| glyph as libc::c_uint).collect::<Vec<libc::c_uint>>()
+                }).unwrap_or_default();
+
+                match (next, glyphs.as_slice()) {
+                    (_, &[action, x, y]) => Mouse::with_legacy(action, x, y),
+                    (&[action, x, y], _) => Mouse::with_legacy(action as libc::c_uint, x as libc::c_uint, y as libc::c_uint),
+                    _ => Err(MouseError::Other),
+                }
+            },
+            &[b'\x1B', b'[', b'0'...b'9', ..] => {
+                match catch_numbers(&buf[2..]) {
+                    (ref numbers, &[b'M']) if numbers.len() == 3 => {
+                        if numbers[1] > 0xFFFF + 32 || numbers[2] > 0xFFFF + 32 {
+                            Err(MouseError::FromStrFail)
+                        } else {
+                            Mouse::with_legacy(numbers[0], numbers[1] + 32, numbers[2] + 32)
+                        }
+                    },
+                    _ => Err(MouseError::Other),
                 }
             },
             _ => Err(MouseError::Other),
         }
     }
 
-    pub fn new(buf: &[u8]) -> Result<Self, MouseError> {
-        match buf {
-            &[b'\x1B', b'[', b'<', action1 @ b'0'...b'9', action2 @ b'0'...b'9', b';', ref next..] => {
-                Mouse::with_code((action1 - 48) * 10 + action2 - 48, next)
+    /// The method `encode` returns the bytes of the report that the child
+    /// asks with its tracking mode and its encoding, or nothing when the
+    /// event isn't tracked or when the coordinates can't be encoded.
+    pub fn encode(&self, mode: MouseMode, encoding: MouseEncoding, size: &Winszed) -> Vec<u8> {
+        let tracked: bool = match mode {
+            MouseMode::Off => false,
+            MouseMode::X10 => self.pressed && !self.code.is_motion() && !self.code.is_wheel(),
+            MouseMode::Normal => !self.code.is_motion(),
+            MouseMode::Button => !self.code.is_moved(),
+            MouseMode::Any => true,
+        };
+        // The X10's mode doesn't report the modifiers.
+        let action: libc::c_uint = if mode.eq(&MouseMode::X10) {
+            self.code.get_button() as libc::c_uint
+        } else {
+            self.code.as_action() as libc::c_uint
+        };
+        // Out of SGR, the release doesn't tell the button.
+        let legacy: libc::c_uint = if self.pressed || self.code.is_motion() {
+            action
+        } else {
+            action | 3
+        };
+        let (x, y): (libc::c_uint, libc::c_uint) = (self.x as libc::c_uint, self.y as libc::c_uint);
+        let mut bytes: Vec<u8> = Vec::new();
+
+        if !tracked {
+            return bytes;
+        }
+        match encoding {
+            MouseEncoding::Default => {
+                if x + 32 < 256 && y + 32 < 256 {
+                    bytes.extend_from_slice(&[b'\x1B', b'[', b'M', (legacy + 32) as u8, (x + 32) as u8, (y + 32) as u8]);
+                }
             },
-            &[b'\x1B', b'[', b'<', action @ b'0'...b'9', b';', ref next..] => {
-                Mouse::with_code(action - 48, next)
+            MouseEncoding::Utf8 => {
+                bytes.extend_from_slice(b"\x1B[M");
+                if !(push_utf8(&mut bytes, legacy) && push_utf8(&mut bytes, x) && push_utf8(&mut bytes, y)) {
+                    bytes.clear();
+                }
+            },
+            MouseEncoding::Urxvt => {
+                bytes.extend_from_slice(format!("\x1B[{};{};{}M", legacy + 32, x, y).as_bytes());
+            },
+            MouseEncoding::Sgr | MouseEncoding::SgrPixels => {
+                let (x, y): (libc::c_uint, libc::c_uint) = if encoding.eq(&MouseEncoding::SgrPixels) {
+                    // The pixel at the top-left corner of the cell.
+                    let width: libc::c_uint = size.get_xpixel().checked_div(size.ws_col as libc::c_uint).unwrap_or_default().max(1);
+                    let height: libc::c_uint = size.get_ypixel().checked_div(size.ws_row as libc::c_uint).unwrap_or_default().max(1);
+
+                    (x.saturating_sub(1) * width + 1, y.saturating_sub(1) * height + 1)
+                } else {
+                    (x, y)
+                };
+
+                bytes.extend_from_slice(format!("\x1B[<{};{};{}{}", action, x, y, if self.pressed { 'M' } else { 'm' }).as_bytes());
             },
-            _ => Err(MouseError::Other),
         }
+        bytes
     }
 
     pub fn as_input(&self) -> (In, libc::size_t) {
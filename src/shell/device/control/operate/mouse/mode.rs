@@ -0,0 +1,76 @@
+use ::libc;
+
+/// The enum `MouseMode` is the mouse's tracking requested by the child.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MouseMode {
+    /// The mouse isn't reported.
+    Off,
+    /// X10 (`?9`), only the presses are reported.
+    X10,
+    /// Normal (`?1000`), the presses and releases are reported.
+    Normal,
+    /// Button-event (`?1002`), the motions with a held button are reported too.
+    Button,
+    /// Any-event (`?1003`), all the motions are reported.
+    Any,
+}
+
+impl MouseMode {
+
+    /// The constructor method `from_private` returns the mode of
+    /// a private mode's number.
+    pub fn from_private(number: libc::size_t) -> Option<Self> {
+        match number {
+            9 => Some(MouseMode::X10),
+            1000 => Some(MouseMode::Normal),
+            1002 => Some(MouseMode::Button),
+            1003 => Some(MouseMode::Any),
+            _ => None,
+        }
+    }
+}
+
+impl Default for MouseMode {
+    fn default() -> MouseMode {
+        MouseMode::Off
+    }
+}
+
+/// The enum `MouseEncoding` is the format of the mouse's reports
+/// requested by the child.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MouseEncoding {
+    /// `CSI M Cb Cx Cy` with bytes, the coordinates stop at 223.
+    Default,
+    /// UTF-8 (`?1005`), `CSI M` with UTF-8 characters up to 2015.
+    Utf8,
+    /// SGR (`?1006`), `CSI < Cb ; Cx ; Cy M` or `m` at the release.
+    Sgr,
+    /// urxvt (`?1015`), `CSI Cb ; Cx ; Cy M` with decimal numbers.
+    Urxvt,
+    /// SGR-pixels (`?1016`), the SGR's format with coordinates in pixels.
+    SgrPixels,
+}
+
+impl MouseEncoding {
+
+    /// The constructor method `from_private` returns the encoding of
+    /// a private mode's number.
+    pub fn from_private(number: libc::size_t) -> Option<Self> {
+        match number {
+            1005 => Some(MouseEncoding::Utf8),
+            1006 => Some(MouseEncoding::Sgr),
+            1015 => Some(MouseEncoding::Urxvt),
+            1016 => Some(MouseEncoding::SgrPixels),
+            _ => None,
+        }
+    }
+}
+
+impl Default for MouseEncoding {
+    fn default() -> MouseEncoding {
+        MouseEncoding::Default
+    }
+}
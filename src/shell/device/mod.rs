@@ -2,6 +2,7 @@
 pub mod task;
 pub mod control;
 pub mod state;
+pub mod reactor;
 mod input;
 mod output;
 mod spawn;
@@ -12,8 +13,11 @@ use ::pty::prelude as pty;
 use std::time;
 use std::thread;
 use std::sync::mpsc;
+#[cfg(feature = "task")]
+use std::os::unix::io::AsRawFd;
 
 pub use self::state::DeviceState;
+pub use self::reactor::{Reactor, ReactorError, Token};
 
 pub use self::input::In;
 pub use self::output::Out;
@@ -34,14 +38,19 @@ pub struct Device {
     delay: time::Duration,
     input: mpsc::Receiver<(In, libc::size_t)>,
     output: mpsc::Receiver<(Out, libc::size_t)>,
-    #[cfg(feature = "task")] task: mpsc::Receiver<BufProc>,
+    #[cfg(feature = "task")] task: mpsc::Receiver<DeviceState>,
 }
 
 impl Device {
 
-    /// The constructor method `from_speudo` returns a Device interface iterable for a Master.
+    /// The constructor method `from_speudo` returns a Device interface iterable for a Master,
+    /// the pid is the child which is in the foreground at the start.
     #[allow(unused_variables)]
     pub fn from_speudo(master: pty::Master, pid: libc::pid_t) -> Self {
+        // The task's thread owns a duplicate of the master, the number
+        // of the shell's one can be reused once it's closed.
+        #[cfg(feature = "task")]
+        let fd: libc::c_int = unsafe { libc::dup(master.as_raw_fd()) };
         let (tx_out, rx_out) = mpsc::sync_channel(0);
         let (tx_in, rx_in) = mpsc::sync_channel(0);
 
@@ -60,7 +69,7 @@ impl Device {
             () => {
                 let (tx_task, rx_task) = mpsc::sync_channel(0);
 
-                thread::spawn(move || spawn::task(time::Duration::from_millis(TM_TASK), tx_task, pid));
+                thread::spawn(move || spawn::task(time::Duration::from_millis(TM_TASK), tx_task, pid, fd));
                 Device {
                     delay: time::Duration::from_millis(TM_DEVICE),
                     input: rx_in,
@@ -96,7 +105,7 @@ impl Iterator for Device {
                         Ok((buf, len)) => Some(DeviceState::from_in(buf, len)),
                         _ => {
                             match self.task.recv_timeout(self.delay) {
-                                Ok(name) => Some(DeviceState::Proc(name)),
+                                Ok(event) => Some(event),
                                 #[cfg(feature = "idle")]
                                 _ => Some(DeviceState::from_idle()),
                                 #[cfg(not(feature = "idle"))]
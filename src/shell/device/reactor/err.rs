@@ -0,0 +1,40 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// The enum `ReactorError` defines the possible errors from the Reactor.
+
+#[derive(Debug)]
+pub enum ReactorError {
+    /// The poll(2) call has occured an error.
+    PollFail(io::Error),
+    /// The descriptor isn't registered.
+    NotFound,
+}
+
+impl fmt::Display for ReactorError {
+
+    /// The function `fmt` formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", ::errno::errno())
+    }
+}
+
+impl Error for ReactorError {
+
+    /// The function `description` returns a short description of the error.
+    fn description(&self) -> &str {
+        match *self {
+            ReactorError::PollFail(_) => "poll(2) has occured an error.",
+            ReactorError::NotFound => "The descriptor isn't registered.",
+        }
+    }
+
+    /// The function `cause` returns the lower-level cause of this error, if any.
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ReactorError::PollFail(ref why) => Some(why),
+            _ => None,
+        }
+    }
+}
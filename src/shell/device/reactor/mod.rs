@@ -0,0 +1,116 @@
+mod err;
+
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::io;
+use std::time;
+
+use ::libc;
+
+pub use self::err::ReactorError;
+
+/// The type `Token` is the identifier given at the registration of a descriptor.
+pub type Token = libc::size_t;
+
+/// The struct `Reactor` multiplexes many descriptors (the pty masters of evented
+/// `Shell`s, the standard input...) from a single thread without busy-waiting.
+
+#[derive(Debug, Default)]
+pub struct Reactor {
+    /// List of watched descriptors.
+    fds: Vec<libc::pollfd>,
+    /// Token by descriptor.
+    tokens: Vec<Token>,
+}
+
+impl Reactor {
+
+    /// The constructor method `new` returns a Reactor without descriptor.
+    pub fn new() -> Self {
+        Reactor::default()
+    }
+
+    /// The method `register` watches the readiness of a descriptor.
+    pub fn register<F: AsRawFd>(&mut self, source: &F, token: Token) {
+        self.register_fd(source.as_raw_fd(), token);
+    }
+
+    /// The method `register_fd` watches the readiness of a raw descriptor.
+    pub fn register_fd(&mut self, fd: RawFd, token: Token) {
+        let _ = self.deregister_fd(fd);
+        self.fds.push(libc::pollfd {
+            fd: fd,
+            events: libc::POLLIN,
+            revents: 0,
+        });
+        self.tokens.push(token);
+    }
+
+    /// The mutator method `set_writable` adds or removes the writability
+    /// to the readiness watched of a registered descriptor.
+    pub fn set_writable(&mut self, fd: RawFd, writable: bool) -> Result<(), ReactorError> {
+        match self.fds.iter_mut().find(|pollfd| pollfd.fd.eq(&fd)) {
+            Some(pollfd) => {
+                pollfd.events = if writable {
+                    libc::POLLIN | libc::POLLOUT
+                } else {
+                    libc::POLLIN
+                };
+                Ok(())
+            },
+            None => Err(ReactorError::NotFound),
+        }
+    }
+
+    /// The method `deregister` stops to watch a descriptor.
+    pub fn deregister<F: AsRawFd>(&mut self, source: &F) -> Result<Token, ReactorError> {
+        self.deregister_fd(source.as_raw_fd())
+    }
+
+    /// The method `deregister_fd` stops to watch a raw descriptor.
+    pub fn deregister_fd(&mut self, fd: RawFd) -> Result<Token, ReactorError> {
+        match self.fds.iter().position(|pollfd| pollfd.fd.eq(&fd)) {
+            Some(index) => {
+                self.fds.remove(index);
+                Ok(self.tokens.remove(index))
+            },
+            None => Err(ReactorError::NotFound),
+        }
+    }
+
+    /// The accessor method `is_empty` returns true when no descriptor is watched.
+    pub fn is_empty(&self) -> bool {
+        self.fds.is_empty()
+    }
+
+    /// The method `poll` waits until at least one descriptor is readable,
+    /// writable (when asked) or hung up, or the timeout expires, and returns
+    /// their tokens.
+    pub fn poll(&mut self, timeout: Option<time::Duration>) -> Result<Vec<Token>, ReactorError> {
+        let timeout: libc::c_int = timeout.map(|delay|
+            (delay.as_secs() * 1_000 + (delay.subsec_nanos() / 1_000_000) as u64) as libc::c_int
+        ).unwrap_or(-1);
+
+        self.fds.iter_mut().all(|pollfd| {
+            pollfd.revents = 0;
+            true
+        });
+        unsafe {
+            match libc::poll(self.fds.as_mut_ptr(), self.fds.len() as libc::nfds_t, timeout) {
+                -1 => {
+                    let why: io::Error = io::Error::last_os_error();
+
+                    if why.kind().eq(&io::ErrorKind::Interrupted) {
+                        Ok(Vec::new())
+                    } else {
+                        Err(ReactorError::PollFail(why))
+                    }
+                },
+                _ => Ok(self.fds.iter()
+                                .zip(self.tokens.iter())
+                                .filter(|&(pollfd, _)| pollfd.revents.ne(&0))
+                                .map(|(_, &token)| token)
+                                .collect::<Vec<Token>>()),
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(feature = "task")]
-use super::task::{Proc, BufProc};
+use super::task::{Monitor, ProcEvent};
+#[cfg(feature = "task")]
+use super::state::DeviceState;
 pub use super::{In, Out, Sig};
 
 use std::io::{self, Read};
@@ -12,14 +14,45 @@ use ::libc;
 use ::pty::prelude as pty;
 
 #[cfg(feature = "task")]
-pub fn task(delay: time::Duration, tx_task: mpsc::SyncSender<BufProc>, pid: libc::pid_t) {
-    let mut task = Proc::new(pid).unwrap();
+pub fn task(delay: time::Duration, tx_task: mpsc::SyncSender<DeviceState>, pid: libc::pid_t, master: libc::c_int) {
+    let mut monitor = Monitor::new(pid, master, delay);
+    // The loop ends with the first failed send (the receiver is gone),
+    // the monitor and its connector are dropped with it.
     loop {
-        let next = task.next();
-        if let Some(name) = next {
-            let _ = tx_task.send(name);
+        let events: Vec<ProcEvent> = monitor.next_events();
+        // The connector wakes up for the events of the whole system,
+        // the foreground is read for ours or once by delay.
+        let refresh: bool = monitor.next_refresh() || !events.is_empty();
+
+        if !events.into_iter().all(|event| {
+            tx_task.send(DeviceState::from_task_event(event)).is_ok()
+        }) {
+            break ;
+        }
+        if refresh {
+            if let Some(name) = monitor.next_job() {
+                if tx_task.send(DeviceState::from_task(name)).is_err() {
+                    break ;
+                }
+            }
+            if monitor.next_cwd() && tx_task.send(DeviceState::from_task_cwd()).is_err() {
+                break ;
+            }
         }
-        thread::sleep(delay);
+        if let Some(usage) = monitor.next_usage() {
+            if tx_task.send(DeviceState::from_task_usage(usage)).is_err() {
+                break ;
+            }
+        }
+        // The connector already waits the delay.
+        if !monitor.is_evented() {
+            thread::sleep(delay);
+        }
+    }
+    drop(monitor);
+    // The master is a duplicate owned by the thread.
+    unsafe {
+        libc::close(master);
     }
 }
 
@@ -1,7 +1,7 @@
 use ::libc;
 
 #[cfg(feature = "task")]
-use super::task::BufProc;
+use super::task::{BufProc, ProcEvent, Usage};
 use super::{In, Out, Sig};
 use super::control::Control;
 
@@ -9,6 +9,12 @@ use super::control::Control;
 pub enum DeviceState {
     /// The current task.
     #[cfg(feature = "task")] Proc(BufProc),
+    /// A fork, an exec or an exit of a descendant.
+    #[cfg(feature = "task")] ProcEvent(ProcEvent),
+    /// The resources used by the session.
+    #[cfg(feature = "task")] Usage(Usage),
+    /// The working directory of the foreground has changed.
+    #[cfg(feature = "task")] Cwd,
     /// Update.
     Idle,
     /// As catched a signal.
@@ -27,6 +33,25 @@ impl DeviceState {
         DeviceState::Proc(name)
     }
 
+    #[cfg(feature = "task")]
+    /// The constructor method `from_task_event` returns a descendant's event.
+    pub fn from_task_event(event: ProcEvent) -> Self {
+        DeviceState::ProcEvent(event)
+    }
+
+    #[cfg(feature = "task")]
+    /// The constructor method `from_task_usage` returns a session's usage.
+    pub fn from_task_usage(usage: Usage) -> Self {
+        DeviceState::Usage(usage)
+    }
+
+    #[cfg(feature = "task")]
+    /// The constructor method `from_task_cwd` returns a change of
+    /// the foreground's working directory.
+    pub fn from_task_cwd() -> Self {
+        DeviceState::Cwd
+    }
+
     /// The constructor method `from_idle` returns a Update's event.
     pub fn from_idle() -> Self {
         DeviceState::Idle
@@ -56,6 +81,34 @@ impl DeviceState {
         }
     }
 
+    #[cfg(feature = "task")]
+    /// The accessor method `is_task_event` returns a descendant's event.
+    pub fn is_task_event(self) -> Option<ProcEvent> {
+        match self {
+            DeviceState::ProcEvent(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "task")]
+    /// The accessor method `is_task_usage` returns a session's usage.
+    pub fn is_task_usage(self) -> Option<Usage> {
+        match self {
+            DeviceState::Usage(usage) => Some(usage),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "task")]
+    /// The accessor method `is_task_cwd` returns a change of
+    /// the foreground's working directory.
+    pub fn is_task_cwd(self) -> Option<()> {
+        match self {
+            DeviceState::Cwd => Some(()),
+            _ => None,
+        }
+    }
+
     /// The accessor method `is_idle` returns a Option for Update's event.
     pub fn is_idle(&self) -> Option<()> {
         match *self {
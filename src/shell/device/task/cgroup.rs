@@ -0,0 +1,137 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::{thread, time};
+
+use super::err::ProcError;
+use super::usage::Limits;
+use ::libc;
+
+/// The mount point of the cgroup v2.
+const SPEC_CGROUP: &'static str = "/sys/fs/cgroup";
+
+/// The cgroup of the current process.
+const SPEC_SELF_CGROUP: &'static str = "/proc/self/cgroup";
+
+/// The delay between two checks of the subtree's emptiness in milliseconds.
+const TM_EMPTY: u64 = 10;
+/// The number of checks of the subtree's emptiness before the removal.
+const LIMIT_EMPTY: usize = 100;
+
+/// The struct `Cgroup` is a cgroup v2's subtree which bounds the sum of
+/// the memory and of the processes of a session, the subtree is removed
+/// when it's dropped.
+
+#[derive(Debug)]
+pub struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+
+    /// The constructor method `new` creates a subtree of the current
+    /// process' cgroup with the limits of memory and of processes.
+    ///
+    /// The current cgroup must be delegated to the user with the `memory`
+    /// and `pids` controllers enabled for its children (as a systemd's
+    /// scope with `Delegate=yes`): by the "no internal processes" rule,
+    /// the controllers can't be enabled while the current process is in
+    /// the cgroup.
+    pub fn new(name: &str, limits: &Limits) -> Result<Self, ProcError> {
+        let mut own: String = String::new();
+
+        try!(fs::File::open(SPEC_SELF_CGROUP).and_then(|mut file| file.read_to_string(&mut own)).map_err(|why| ProcError::Cgroup(why)));
+
+        let parent: PathBuf = match own.lines().find(|line| line.starts_with("0::")) {
+            Some(line) => Path::new(SPEC_CGROUP).join(line[3..].trim_left_matches('/')),
+            None => return Err(ProcError::Cgroup(io::Error::new(io::ErrorKind::NotFound, "The cgroup v2 isn't mounted."))),
+        };
+
+        // The controllers may have been enabled by the delegation.
+        if let Err(why) = Cgroup::write_to(&parent.join("cgroup.subtree_control"), "+memory +pids") {
+            let mut controllers: String = String::new();
+
+            try!(fs::File::open(parent.join("cgroup.subtree_control")).and_then(|mut file| file.read_to_string(&mut controllers))
+                                                                       .map_err(|why| ProcError::Cgroup(why)));
+            if (limits.get_memory().is_some() && !controllers.split_whitespace().any(|controller| controller.eq("memory"))) ||
+               (limits.get_processes().is_some() && !controllers.split_whitespace().any(|controller| controller.eq("pids"))) {
+                return Err(why);
+            }
+        }
+        try!(fs::create_dir(parent.join(name)).map_err(|why| ProcError::Cgroup(why)));
+
+        let cgroup: Cgroup = Cgroup { path: parent.join(name) };
+
+        if let Some(memory) = limits.get_memory() {
+            try!(Cgroup::write_to(&cgroup.path.join("memory.max"), &memory.to_string()));
+        }
+        if let Some(processes) = limits.get_processes() {
+            try!(Cgroup::write_to(&cgroup.path.join("pids.max"), &processes.to_string()));
+        }
+        Ok(cgroup)
+    }
+
+    /// The function `write_to` writes a value to a cgroup's file.
+    fn write_to(path: &Path, value: &str) -> Result<(), ProcError> {
+        fs::OpenOptions::new().write(true)
+                              .open(path)
+                              .and_then(|mut file| file.write_all(value.as_bytes()))
+                              .map_err(|why| ProcError::Cgroup(why))
+    }
+
+    /// The accessor method `get_path` returns the directory of the subtree.
+    pub fn get_path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// The method `add` moves a process to the subtree, its future
+    /// children are in the subtree too.
+    pub fn add(&self, pid: libc::pid_t) -> Result<(), ProcError> {
+        Cgroup::write_to(&self.path.join("cgroup.procs"), &pid.to_string())
+    }
+
+    /// The method `kill` kills all the processes of the subtree, with
+    /// `cgroup.kill` or else by their pids (before Linux 5.14).
+    pub fn kill(&self) -> Result<(), ProcError> {
+        if Cgroup::write_to(&self.path.join("cgroup.kill"), "1").is_err() {
+            let mut procs: String = String::new();
+
+            try!(fs::File::open(self.path.join("cgroup.procs")).and_then(|mut file| file.read_to_string(&mut procs))
+                                                               .map_err(|why| ProcError::Cgroup(why)));
+            procs.lines().filter_map(|pid| pid.trim().parse::<libc::pid_t>().ok()).all(|pid| unsafe {
+                libc::kill(pid, libc::SIGKILL);
+                true
+            });
+        }
+        Ok(())
+    }
+
+    /// The method `is_populated` returns true while the subtree
+    /// holds a process (see `cgroup.events`).
+    pub fn is_populated(&self) -> bool {
+        let mut events: String = String::new();
+
+        match fs::File::open(self.path.join("cgroup.events")).and_then(|mut file| file.read_to_string(&mut events)) {
+            Ok(_) => !events.lines().any(|line| line.trim().eq("populated 0")),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Drop for Cgroup {
+    /// The subtree can't be removed while it holds a process: the
+    /// processes are killed and the subtree is removed once it's empty.
+    fn drop(&mut self) {
+        if self.is_populated() && self.kill().is_ok() {
+            (0..LIMIT_EMPTY).any(|_| {
+                if self.is_populated() {
+                    thread::sleep(time::Duration::from_millis(TM_EMPTY));
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        let _ = fs::remove_dir(&self.path);
+    }
+}
@@ -0,0 +1,221 @@
+use std::io;
+use std::mem;
+use std::ptr;
+use std::time::Duration;
+
+use super::err::ProcError;
+use super::event::ProcEvent;
+use ::libc;
+
+/// The family of the connector.
+const NETLINK_CONNECTOR: libc::c_int = 11;
+/// The done's type of the netlink's messages.
+const NLMSG_DONE: u16 = 3;
+/// The index and the value of the proc connector.
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+/// The operations of the multicast's subscription.
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+const PROC_CN_MCAST_IGNORE: u32 = 2;
+/// The kinds of the proc's events.
+const PROC_EVENT_FORK: u32 = 0x00000001;
+const PROC_EVENT_EXEC: u32 = 0x00000002;
+const PROC_EVENT_EXIT: u32 = 0x80000000;
+
+/// The size of the proc event's header (what, cpu and timestamp).
+const PROC_EVENT_HDRLEN: usize = 16;
+/// The default capacity of the reception's buffer.
+const SPEC_CAPACITY_RECV: usize = 4096;
+
+#[repr(C)]
+struct sockaddr_nl {
+    nl_family: libc::sa_family_t,
+    nl_pad: libc::c_ushort,
+    nl_pid: u32,
+    nl_groups: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct nlmsghdr {
+    nlmsg_len: u32,
+    nlmsg_type: u16,
+    nlmsg_flags: u16,
+    nlmsg_seq: u32,
+    nlmsg_pid: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct cn_msg {
+    idx: u32,
+    val: u32,
+    seq: u32,
+    ack: u32,
+    len: u16,
+    flags: u16,
+}
+
+/// The subscription's message: the headers and the operation.
+#[repr(C)]
+struct cn_mcast {
+    header: nlmsghdr,
+    message: cn_msg,
+    operation: u32,
+}
+
+/// The function `read_u32` returns the native's number at an offset.
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    if offset + mem::size_of::<u32>() <= buf.len() {
+        unsafe {
+            Some(ptr::read_unaligned(buf.as_ptr().offset(offset as isize) as *const u32))
+        }
+    } else {
+        None
+    }
+}
+
+/// The struct `Connector` listens the proc's events of the kernel
+/// (fork, exec and exit) with the netlink's proc connector.
+
+#[derive(Debug)]
+pub struct Connector {
+    fd: libc::c_int,
+}
+
+impl Connector {
+
+    /// The constructor method `new` subscribes to the proc connector,
+    /// a reception waits the timeout at most. The subscription requires
+    /// the `CAP_NET_ADMIN` capability.
+    pub fn new(timeout: Duration) -> Result<Self, ProcError> {
+        unsafe {
+            let fd: libc::c_int = libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, NETLINK_CONNECTOR);
+
+            if fd < 0 {
+                return Err(ProcError::Connector(io::Error::last_os_error()));
+            }
+            let connector: Connector = Connector { fd: fd };
+            let mut address: sockaddr_nl = mem::zeroed();
+            let time: libc::timeval = libc::timeval {
+                tv_sec: timeout.as_secs() as libc::time_t,
+                tv_usec: (timeout.subsec_nanos() / 1000) as libc::suseconds_t,
+            };
+
+            // The pid is chosen by the kernel, a process can hold
+            // many connectors (one by shell).
+            address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+            address.nl_groups = CN_IDX_PROC;
+            if libc::bind(fd,
+                          &address as *const sockaddr_nl as *const libc::sockaddr,
+                          mem::size_of::<sockaddr_nl>() as libc::socklen_t) < 0 ||
+               libc::setsockopt(fd, libc::SOL_SOCKET, libc::SO_RCVTIMEO,
+                                &time as *const libc::timeval as *const libc::c_void,
+                                mem::size_of::<libc::timeval>() as libc::socklen_t) < 0 {
+                return Err(ProcError::Connector(io::Error::last_os_error()));
+            }
+            try!(connector.subscribe(PROC_CN_MCAST_LISTEN).map_err(|why| ProcError::Connector(why)));
+            Ok(connector)
+        }
+    }
+
+    /// The method `subscribe` sends a multicast's operation to the proc connector.
+    fn subscribe(&self, operation: u32) -> io::Result<()> {
+        let message: cn_mcast = cn_mcast {
+            header: nlmsghdr {
+                nlmsg_len: mem::size_of::<cn_mcast>() as u32,
+                nlmsg_type: NLMSG_DONE,
+                nlmsg_flags: 0,
+                nlmsg_seq: 0,
+                nlmsg_pid: 0,
+            },
+            message: cn_msg {
+                idx: CN_IDX_PROC,
+                val: CN_VAL_PROC,
+                seq: 0,
+                ack: 0,
+                len: mem::size_of::<u32>() as u16,
+                flags: 0,
+            },
+            operation: operation,
+        };
+
+        unsafe {
+            if libc::send(self.fd,
+                          &message as *const cn_mcast as *const libc::c_void,
+                          mem::size_of::<cn_mcast>(), 0) < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// The method `recv` returns the events received before the timeout,
+    /// the threads are ignored.
+    pub fn recv(&self) -> io::Result<Vec<ProcEvent>> {
+        let mut buf: [u8; SPEC_CAPACITY_RECV] = [0; SPEC_CAPACITY_RECV];
+        let len: libc::ssize_t = unsafe {
+            libc::recv(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0)
+        };
+
+        if len < 0 {
+            let why: io::Error = io::Error::last_os_error();
+
+            return match why.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted => Ok(Vec::new()),
+                _ => Err(why),
+            };
+        }
+        let mut events: Vec<ProcEvent> = Vec::new();
+        let mut offset: usize = 0;
+        let headers: usize = mem::size_of::<nlmsghdr>() + mem::size_of::<cn_msg>();
+
+        // A datagram can hold many netlink's messages aligned on 4.
+        while let Some(size) = read_u32(&buf[..len as usize], offset).map(|size| size as usize) {
+            if size < headers || offset + size > len as usize {
+                break ;
+            }
+            if let Some(event) = Connector::parse(&buf[offset + headers..offset + size]) {
+                events.push(event);
+            }
+            offset += (size + 3) & !3;
+        }
+        Ok(events)
+    }
+
+    /// The function `parse` returns the event of a proc event's message.
+    fn parse(message: &[u8]) -> Option<ProcEvent> {
+        let data = |index: usize| -> Option<libc::pid_t> {
+            read_u32(message, PROC_EVENT_HDRLEN + 4 * index).map(|number| number as libc::pid_t)
+        };
+
+        match read_u32(message, 0) {
+            // parent_pid, parent_tgid, child_pid, child_tgid.
+            Some(PROC_EVENT_FORK) => match (data(1), data(2), data(3)) {
+                (Some(parent), Some(pid), Some(tgid)) if pid == tgid => Some(ProcEvent::Fork(pid, parent)),
+                _ => None,
+            },
+            // process_pid, process_tgid.
+            Some(PROC_EVENT_EXEC) => match (data(0), data(1)) {
+                (Some(pid), Some(tgid)) if pid == tgid => Some(ProcEvent::Exec(pid)),
+                _ => None,
+            },
+            // process_pid, process_tgid, exit_code.
+            Some(PROC_EVENT_EXIT) => match (data(0), data(1), data(2)) {
+                (Some(pid), Some(tgid), Some(code)) if pid == tgid => Some(ProcEvent::Exit(pid, Some(code))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Connector {
+    fn drop(&mut self) {
+        let _ = self.subscribe(PROC_CN_MCAST_IGNORE);
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
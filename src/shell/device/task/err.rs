@@ -10,12 +10,29 @@ pub enum ProcError {
     /// Can't read the sub-directory.
     #[cfg(any(target_os = "linux", target_os = "android"))]
     ReadDir(io::Error),
+    /// Can't read a file of the process.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ReadFile(io::Error),
+    /// There isn't a valid stat's file.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    StatUnvalid,
+    /// Can't listen the proc connector.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Connector(io::Error),
+    /// Can't write the cgroup.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Cgroup(io::Error),
     /// Can't count the number of process.
     #[cfg(target_os = "macos")]
     ListAllPidLen,
     /// There isn't a valid number of process.
     #[cfg(target_os = "macos")]
     ListAllPidLenUnvalid,
+    /// Can't get the informations of the process.
+    #[cfg(target_os = "macos")]
+    PidInfo,
+    /// The process isn't in the list.
+    NotFound,
 }
 
 impl fmt::Display for ProcError {
@@ -33,10 +50,21 @@ impl Error for ProcError {
       match *self {
           #[cfg(any(target_os = "linux", target_os = "android"))]
           ProcError::ReadDir(_) => "Can't read the sub-directory.",
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::ReadFile(_) => "Can't read a file of the process.",
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::StatUnvalid => "There isn't a valid stat's file.",
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::Connector(_) => "Can't listen the proc connector.",
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::Cgroup(_) => "Can't write the cgroup.",
           #[cfg(target_os = "macos")]
           ProcError::ListAllPidLen => "Can't count the number of process.",
           #[cfg(target_os = "macos")]
           ProcError::ListAllPidLenUnvalid => "There isn't a valid number of process.",
+          #[cfg(target_os = "macos")]
+          ProcError::PidInfo => "Can't get the informations of the process.",
+          ProcError::NotFound => "The process isn't in the list.",
       }
   }
 
@@ -46,7 +74,12 @@ impl Error for ProcError {
       match *self {
           #[cfg(any(target_os = "linux", target_os = "android"))]
           ProcError::ReadDir(ref why) => Some(why),
-          #[cfg(target_os = "macos")]
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::ReadFile(ref why) => Some(why),
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::Connector(ref why) => Some(why),
+          #[cfg(any(target_os = "linux", target_os = "android"))]
+          ProcError::Cgroup(ref why) => Some(why),
           _ => None,
       }
   }
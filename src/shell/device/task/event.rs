@@ -0,0 +1,27 @@
+use ::libc;
+
+/// The enum `ProcEvent` is a change of a descendant of the child.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcEvent {
+    /// A process is forked: the new pid and the pid of its parent.
+    Fork(libc::pid_t, libc::pid_t),
+    /// A process executes a new program.
+    Exec(libc::pid_t),
+    /// A process exits with its wait's status when it's known
+    /// (the polling doesn't know it).
+    Exit(libc::pid_t, Option<libc::c_int>),
+}
+
+impl ProcEvent {
+
+    /// The accessor method `get_pid` returns the pid of the process
+    /// concerned by the event.
+    pub fn get_pid(&self) -> libc::pid_t {
+        match *self {
+            ProcEvent::Fork(pid, _) |
+            ProcEvent::Exec(pid) |
+            ProcEvent::Exit(pid, _) => pid,
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use ::libc;
 
 const MAXCOMLEN: usize = 16; // MAXCOMLEN.
+const MAXPATHLEN: usize = 1024; // MAXPATHLEN.
 
 pub const PROC_PIDTASKALLINFO: i32 = 2;
+pub const PROC_PIDVNODEPATHINFO: i32 = 9;
+pub const PROC_PIDPATHINFO_MAXSIZE: usize = 4 * MAXPATHLEN;
 pub const CTL_KERN: libc::c_int = 1;
 pub const KERN_ARGMAX: libc::c_int = 8;
 pub const KERN_PROCARGS2: libc::c_int = 49;
@@ -61,6 +64,18 @@ pub struct proc_taskallinfo {
     pub ptinfo: proc_taskinfo,
 }
 
+#[repr(C)]
+pub struct vnode_info_path {
+    pub vip_vi: [u8; 152], /* vnode_info */
+    pub vip_path: [u8; MAXPATHLEN],
+}
+
+#[repr(C)]
+pub struct proc_vnodepathinfo {
+    pub pvi_cdir: vnode_info_path, /* current directory */
+    pub pvi_rdir: vnode_info_path, /* root directory */
+}
+
 extern "C" {
     pub fn proc_pidinfo(
         pid: libc::c_int,
@@ -69,6 +84,11 @@ extern "C" {
         buffer: *mut libc::c_void,
         buffersize: libc::c_int
     ) -> libc::c_int;
+    pub fn proc_pidpath(
+        pid: libc::c_int,
+        buffer: *mut libc::c_void,
+        buffersize: u32
+    ) -> libc::c_int;
     pub fn proc_listallpids(
         buffer: *mut libc::c_void,
         buffersize: libc::c_int
@@ -0,0 +1,107 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use ::libc;
+use ::time;
+
+/// The struct `ProcessInfo` is the description of a process: its command
+/// line with the arguments, its directories and its resources.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: libc::pid_t,
+    pub ppid: libc::pid_t,
+    /// The status's letter (`R`, `S`, `Z`...).
+    pub state: libc::c_uchar,
+    /// The unsized-name.
+    pub name: String,
+    /// The arguments, the first is the program.
+    pub cmdline: Vec<String>,
+    /// The working directory.
+    pub cwd: Option<PathBuf>,
+    /// The path of the executable.
+    pub exe: Option<PathBuf>,
+    pub uid: libc::uid_t,
+    /// The time when the process has started.
+    pub start: time::Timespec,
+    /// The resident set size in bytes.
+    pub rss: u64,
+    /// The virtual memory size in bytes.
+    pub vsz: u64,
+    /// The time spent by the process in the user and the system modes.
+    pub cpu: Duration,
+}
+
+impl ProcessInfo {
+
+    /// The accessor method `get_pid` returns the pid.
+    pub fn get_pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// The accessor method `get_ppid` returns the pid of the parent.
+    pub fn get_ppid(&self) -> libc::pid_t {
+        self.ppid
+    }
+
+    /// The accessor method `get_state` returns the status's letter.
+    pub fn get_state(&self) -> libc::c_uchar {
+        self.state
+    }
+
+    /// The accessor method `get_name` returns the unsized-name.
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The accessor method `get_cmdline` returns the arguments.
+    pub fn get_cmdline(&self) -> &[String] {
+        self.cmdline.as_slice()
+    }
+
+    /// The method `get_command` returns the command line as typed (`vim
+    /// ~/src/foo.rs`), or the name when the process hasn't arguments
+    /// (a kernel's thread or a zombie).
+    pub fn get_command(&self) -> String {
+        if self.cmdline.is_empty() {
+            self.name.clone()
+        } else {
+            self.cmdline.join(" ")
+        }
+    }
+
+    /// The accessor method `get_cwd` returns the working directory.
+    pub fn get_cwd(&self) -> Option<&Path> {
+        self.cwd.as_ref().map(|cwd| cwd.as_path())
+    }
+
+    /// The accessor method `get_exe` returns the path of the executable.
+    pub fn get_exe(&self) -> Option<&Path> {
+        self.exe.as_ref().map(|exe| exe.as_path())
+    }
+
+    /// The accessor method `get_uid` returns the user's id.
+    pub fn get_uid(&self) -> libc::uid_t {
+        self.uid
+    }
+
+    /// The accessor method `get_start` returns the time when the process has started.
+    pub fn get_start(&self) -> time::Timespec {
+        self.start
+    }
+
+    /// The accessor method `get_rss` returns the resident set size in bytes.
+    pub fn get_rss(&self) -> u64 {
+        self.rss
+    }
+
+    /// The accessor method `get_vsz` returns the virtual memory size in bytes.
+    pub fn get_vsz(&self) -> u64 {
+        self.vsz
+    }
+
+    /// The accessor method `get_cpu` returns the time spent by the process.
+    pub fn get_cpu(&self) -> Duration {
+        self.cpu
+    }
+}
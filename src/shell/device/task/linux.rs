@@ -1,11 +1,14 @@
 use std::fs;
 use std::ops::Not;
 use std::path::{Path, PathBuf};
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read};
+use std::time::Duration;
 
-use super::{SPEC_PROC, SPEC_SUBD_STATUS, SPEC_CAPACITY_PROC};
+use super::{SPEC_PROC, SPEC_SUBD_STATUS, SPEC_SUBD_STAT, SPEC_SUBD_CMDLINE, SPEC_SUBD_CWD, SPEC_SUBD_EXE, SPEC_CAPACITY_PROC};
 use super::err::ProcError;
+use super::info::ProcessInfo;
 use ::libc;
+use ::time;
 
 #[derive(Debug)]
 pub struct Proc {
@@ -15,6 +18,8 @@ pub struct Proc {
     pub running_pid: libc::pid_t,
     /// List by pid, ppid. status and unsized-name.
     pub list: Vec<(libc::pid_t, libc::pid_t, libc::c_uchar, String)>,
+    /// The master of the pty whose foreground's job is followed.
+    pub master: Option<libc::c_int>,
 }
 
 impl Proc {
@@ -38,39 +43,107 @@ impl Proc {
                                                }
                                            )
                                   )).all(|entry| {
-                                      self.push(entry).is_some()
+                                      // A process can exit while it's read.
+                                      self.push(entry);
+                                      true
                                   });
                 Ok(())
             },
         }
     }
 
-    /// The method `push` adds a new process from a file descriptor.
+    /// The method `push` adds a new process from a file descriptor,
+    /// the fields are found by their keys (the kernels add some lines
+    /// as `Umask`).
     fn push(&mut self, entry: fs::File) -> Option<()> {
         let file = io::BufReader::new(entry);
-        let mut iter = file.lines();
-        match (
-            iter.next(), iter.next(), iter.next(),
-            iter.skip(2).next()
-        ) {
-            (Some(Ok(mut name)),
-            Some(Ok(state)),
-            Some(Ok(mut pid)),
-            Some(Ok(mut ppid))) => unsafe {
-                let (len_pid, len_ppid, len_name) = (
-                    pid.len(), ppid.len(), name.len()
-                );
-                Some(self.list.push((
-                    pid.slice_mut_unchecked(6, len_pid).parse::<libc::pid_t>()
-                        .ok().unwrap_or_default(),
-                    ppid.slice_mut_unchecked(6, len_ppid).parse::<libc::pid_t>()
-                        .ok().unwrap_or_default(),
-                    *state.as_bytes().get(7).unwrap_or(&b'\x00'),
-                    name.slice_mut_unchecked(6, len_name).parse::<String>()
-                        .ok().unwrap_or_default()
-                )))
+        let (mut name, mut state, mut pid, mut ppid): (Option<String>, Option<libc::c_uchar>, Option<libc::pid_t>, Option<libc::pid_t>) = (None, None, None, None);
+
+        file.lines().filter_map(|line| line.ok()).all(|line| {
+            let mut fields = line.splitn(2, ':');
+
+            match (fields.next(), fields.next().map(|value| value.trim())) {
+                (Some("Name"), Some(value)) => name = Some(value.to_string()),
+                (Some("State"), Some(value)) => state = value.bytes().next(),
+                (Some("Pid"), Some(value)) => pid = value.parse::<libc::pid_t>().ok(),
+                (Some("PPid"), Some(value)) => ppid = value.parse::<libc::pid_t>().ok(),
+                _ => {},
+            }
+            name.is_none() || state.is_none() || pid.is_none() || ppid.is_none()
+        });
+        match (name, state, pid, ppid) {
+            (Some(name), Some(state), Some(pid), Some(ppid)) => {
+                Some(self.list.push((pid, ppid, state, name)))
             },
             _ => None,
         }
     }
 }
+
+impl ProcessInfo {
+
+    /// The constructor method `new` returns the description of a process
+    /// read from its `stat`, `status`, `cmdline`, `cwd` and `exe` files.
+    pub fn new(pid: libc::pid_t) -> Result<Self, ProcError> {
+        let path: PathBuf = Path::new(SPEC_PROC).join(pid.to_string());
+        let mut stat: String = String::new();
+        let mut status: String = String::new();
+        let mut cmdline: Vec<u8> = Vec::new();
+
+        try!(fs::File::open(path.join(SPEC_SUBD_STAT)).and_then(|mut file| file.read_to_string(&mut stat)).map_err(|why| ProcError::ReadFile(why)));
+        try!(fs::File::open(path.join(SPEC_SUBD_STATUS)).and_then(|mut file| file.read_to_string(&mut status)).map_err(|why| ProcError::ReadFile(why)));
+        try!(fs::File::open(path.join(SPEC_SUBD_CMDLINE)).and_then(|mut file| file.read_to_end(&mut cmdline)).map_err(|why| ProcError::ReadFile(why)));
+
+        // The name is between parentheses and can hold spaces,
+        // the fields are counted from the state (the third).
+        let (name, fields): (&str, Vec<&str>) = match (stat.find('('), stat.rfind(')')) {
+            (Some(start), Some(end)) if start < end => {
+                (&stat[start + 1..end], stat[end + 1..].split_whitespace().collect::<Vec<&str>>())
+            },
+            _ => return Err(ProcError::StatUnvalid),
+        };
+        let field = |index: usize| -> u64 {
+            fields.get(index).and_then(|field| field.parse::<u64>().ok()).unwrap_or_default()
+        };
+        let (tick, page): (u64, u64) = unsafe {
+            (libc::sysconf(libc::_SC_CLK_TCK).max(1) as u64, libc::sysconf(libc::_SC_PAGESIZE).max(0) as u64)
+        };
+        let boot: u64 = ProcessInfo::get_boot_time().unwrap_or_default();
+        let start: u64 = field(19);
+        let cpu: u64 = field(11) + field(12);
+
+        Ok(ProcessInfo {
+            pid: pid,
+            ppid: field(1) as libc::pid_t,
+            state: fields.get(0).and_then(|state| state.bytes().next()).unwrap_or_default(),
+            name: name.to_string(),
+            cmdline: cmdline.split(|&byte| byte == b'\0')
+                            .filter(|argument| !argument.is_empty())
+                            .map(|argument| String::from_utf8_lossy(argument).into_owned())
+                            .collect::<Vec<String>>(),
+            cwd: fs::read_link(path.join(SPEC_SUBD_CWD)).ok(),
+            exe: fs::read_link(path.join(SPEC_SUBD_EXE)).ok(),
+            uid: status.lines()
+                       .find(|line| line.starts_with("Uid:"))
+                       .and_then(|line| line.split_whitespace().nth(1))
+                       .and_then(|uid| uid.parse::<libc::uid_t>().ok())
+                       .unwrap_or_default(),
+            start: time::Timespec::new((boot + start / tick) as i64,
+                                       (start % tick * 1_000_000_000 / tick) as i32),
+            rss: field(21) * page,
+            vsz: field(20),
+            cpu: Duration::new(cpu / tick, (cpu % tick * 1_000_000_000 / tick) as u32),
+        })
+    }
+
+    /// The function `get_boot_time` returns the seconds since the epoch
+    /// when the system has booted (the `btime` of `/proc/stat`).
+    fn get_boot_time() -> Option<u64> {
+        fs::File::open(Path::new(SPEC_PROC).join(SPEC_SUBD_STAT)).ok().and_then(|file| {
+            io::BufReader::new(file).lines()
+                                    .filter_map(|line| line.ok())
+                                    .find(|line| line.starts_with("btime"))
+                                    .and_then(|line| line.split_whitespace().nth(1).and_then(|btime| btime.parse::<u64>().ok()))
+        })
+    }
+}
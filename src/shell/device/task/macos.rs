@@ -1,8 +1,12 @@
 use std::mem;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use super::ffi;
 use super::err::ProcError;
+use super::info::ProcessInfo;
 use ::libc;
+use ::time;
 
 unsafe fn get_unchecked_str(cp: *mut u8, start: *mut u8) -> String {
     let len = cp as usize - start as usize;
@@ -13,6 +17,13 @@ unsafe fn get_unchecked_str(cp: *mut u8, start: *mut u8) -> String {
     tmp
 }
 
+/// The function `get_c_str` returns the text of a buffer until its nul.
+fn get_c_str(buffer: &[u8]) -> String {
+    let len: usize = buffer.iter().position(|&byte| byte == b'\0').unwrap_or(buffer.len());
+
+    String::from_utf8_lossy(&buffer[..len]).into_owned()
+}
+
 #[derive(Debug)]
 pub struct Proc {
     /// The first pid of the tree.
@@ -21,6 +32,8 @@ pub struct Proc {
     pub running_pid: libc::pid_t,
     /// List by pid, ppid. status and unsized-name.
     pub list: Vec<(libc::pid_t, libc::pid_t, libc::c_uchar, String)>,
+    /// The master of the pty whose foreground's job is followed.
+    pub master: Option<libc::c_int>,
 }
 
 impl Proc {
@@ -104,3 +117,97 @@ impl Proc {
         }
     }
 }
+
+impl ProcessInfo {
+
+    /// The constructor method `new` returns the description of a process
+    /// from its task's informations, its vnode's paths and its arguments.
+    pub fn new(pid: libc::pid_t) -> Result<Self, ProcError> {
+        unsafe {
+            let taskallinfo_size = mem::size_of::<ffi::proc_taskallinfo>() as i32;
+            let vnodepathinfo_size = mem::size_of::<ffi::proc_vnodepathinfo>() as i32;
+            let mut task_info = mem::zeroed::<ffi::proc_taskallinfo>();
+            let mut vnode_info = mem::zeroed::<ffi::proc_vnodepathinfo>();
+            let mut path: [u8; ffi::PROC_PIDPATHINFO_MAXSIZE] = [0; ffi::PROC_PIDPATHINFO_MAXSIZE];
+
+            if ffi::proc_pidinfo(pid,
+                                 ffi::PROC_PIDTASKALLINFO,
+                                 0,
+                                 &mut task_info as *mut ffi::proc_taskallinfo as *mut libc::c_void,
+                                 taskallinfo_size) != taskallinfo_size {
+                return Err(ProcError::PidInfo);
+            }
+            let cwd: Option<PathBuf> = if ffi::proc_pidinfo(pid,
+                                                            ffi::PROC_PIDVNODEPATHINFO,
+                                                            0,
+                                                            &mut vnode_info as *mut ffi::proc_vnodepathinfo as *mut libc::c_void,
+                                                            vnodepathinfo_size) == vnodepathinfo_size {
+                Some(PathBuf::from(get_c_str(&vnode_info.pvi_cdir.vip_path)))
+            } else {
+                None
+            };
+            let exe: Option<PathBuf> = if ffi::proc_pidpath(pid, path.as_mut_ptr() as *mut libc::c_void, path.len() as u32) > 0 {
+                Some(PathBuf::from(get_c_str(&path)))
+            } else {
+                None
+            };
+            let name: String = match get_c_str(&task_info.pbsd.pbi_name) {
+                ref name if name.is_empty() => get_c_str(&task_info.pbsd.pbi_comm),
+                name => name,
+            };
+            // The times are in nanoseconds.
+            let cpu: u64 = task_info.ptinfo.pti_total_user + task_info.ptinfo.pti_total_system;
+
+            Ok(ProcessInfo {
+                pid: pid,
+                ppid: task_info.pbsd.ppbi_pid as libc::pid_t,
+                state: task_info.pbsd.pbi_status as libc::c_uchar,
+                name: name,
+                cmdline: ProcessInfo::get_arguments(pid),
+                cwd: cwd,
+                exe: exe,
+                uid: task_info.pbsd.pbi_uid,
+                start: time::Timespec::new(task_info.pbsd.pbi_start_tvsec as i64,
+                                           (task_info.pbsd.pbi_start_tvusec * 1000) as i32),
+                rss: task_info.ptinfo.pti_resident_size,
+                vsz: task_info.ptinfo.pti_virtual_size,
+                cpu: Duration::new(cpu / 1_000_000_000, (cpu % 1_000_000_000) as u32),
+            })
+        }
+    }
+
+    /// The function `get_arguments` returns the arguments of a process,
+    /// the `KERN_PROCARGS2`'s buffer holds the number of arguments, the path
+    /// of the executable and the arguments separated by nuls.
+    fn get_arguments(pid: libc::pid_t) -> Vec<String> {
+        unsafe {
+            let mut mib: [libc::c_int; 3] = [ffi::CTL_KERN, ffi::KERN_ARGMAX, 0];
+            let mut argmax: libc::c_int = 0;
+            let mut size = mem::size_of::<libc::c_int>();
+
+            if libc::sysctl(mib.as_mut_ptr(), 2, (&mut argmax) as *mut i32 as *mut libc::c_void,
+                            &mut size, ::std::ptr::null_mut(), 0) == -1 || argmax < 1 {
+                return Vec::new();
+            }
+            let mut proc_args: Vec<u8> = vec![0; argmax as usize];
+            let mut size = proc_args.len();
+
+            mib[1] = ffi::KERN_PROCARGS2;
+            mib[2] = pid as libc::c_int;
+            if libc::sysctl(mib.as_mut_ptr(), 3, proc_args.as_mut_ptr() as *mut libc::c_void,
+                            &mut size, ::std::ptr::null_mut(), 0) == -1 || size < mem::size_of::<libc::c_int>() {
+                return Vec::new();
+            }
+            proc_args.truncate(size);
+
+            let argc: libc::c_int = *(proc_args.as_ptr() as *const libc::c_int);
+
+            proc_args[mem::size_of::<libc::c_int>()..].split(|&byte| byte == b'\0')
+                                                       .filter(|argument| !argument.is_empty())
+                                                       .skip(1)
+                                                       .take(argc.max(0) as usize)
+                                                       .map(|argument| String::from_utf8_lossy(argument).into_owned())
+                                                       .collect::<Vec<String>>()
+        }
+    }
+}
@@ -1,4 +1,9 @@
 mod err;
+mod info;
+mod tree;
+mod event;
+mod monitor;
+mod usage;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod linux;
@@ -6,20 +11,35 @@ mod linux;
 mod macos;
 #[cfg(target_os = "macos")]
 mod ffi;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod connector;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod cgroup;
 
 #[cfg(feature = "task")]
 use std::io::Write;
 #[cfg(feature = "task")]
 use std::ops::Not;
+use std::path::PathBuf;
 
 pub use self::err::ProcError;
+pub use self::info::ProcessInfo;
+pub use self::tree::ProcessTree;
+pub use self::event::ProcEvent;
+pub use self::monitor::Monitor;
+pub use self::usage::{Usage, Limit, Limits};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::connector::Connector;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use self::cgroup::Cgroup;
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub use self::linux::*;
 #[cfg(target_os = "macos")]
 pub use self::macos::*;
 
-pub type BufProc = (libc::pid_t, [libc::c_uchar; 32]);
+/// The job's event by pid, process group, session and name.
+pub type BufProc = (libc::pid_t, libc::pid_t, libc::pid_t, [libc::c_uchar; 32]);
 
 use ::libc;
 
@@ -31,6 +51,22 @@ const SPEC_PROC: &'static str = "/proc";
 #[cfg(any(target_os = "linux", target_os = "android"))]
 const SPEC_SUBD_STATUS: &'static str = "status";
 
+/// The stat's sub-directory.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SPEC_SUBD_STAT: &'static str = "stat";
+
+/// The command line's sub-directory.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SPEC_SUBD_CMDLINE: &'static str = "cmdline";
+
+/// The link to the working directory.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SPEC_SUBD_CWD: &'static str = "cwd";
+
+/// The link to the executable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SPEC_SUBD_EXE: &'static str = "exe";
+
 /// The default capacity of proc dictionary.
 const SPEC_CAPACITY_PROC: usize = 512;
 
@@ -47,22 +83,90 @@ impl Proc {
     }
 
 
+    /// The constructor method `from_master` returns a Proc which follows
+    /// the foreground's job of the pty's master, the process' table isn't
+    /// read.
+    pub fn from_master(first_pid: libc::pid_t, master: libc::c_int) -> Self {
+        let mut status: Proc = Proc::default();
+
+        status.first_pid = first_pid;
+        status.running_pid = first_pid;
+        status.master = Some(master);
+        status
+    }
+
+    /// The function `as_buf` returns the job's event of a process.
+    fn as_buf(pid: libc::pid_t, name: &str) -> BufProc {
+        let mut source: [libc::c_uchar; 32] = [b'\0'; 32];
+        {
+            let mut buffer: &mut [libc::c_uchar] = &mut source[..];
+
+            let _ = buffer.write(name.as_bytes());
+        }
+        unsafe {
+            (pid, libc::getpgid(pid), libc::getsid(pid), source)
+        }
+    }
+
     /// The accessor method `get_name` returns the name of
     /// the process according to the pid.
     pub fn get_name(&self, pid: libc::pid_t)-> Option<BufProc> {
         self.list.iter().find(
             |&&(ref current_pid, _, _, _)| pid.eq(current_pid)
         ).and_then(|&(_, _, _, ref name): &(_, _, _, String)| {
-            let mut source: [libc::c_uchar; 32] = [b'\0'; 32];
-            {
-                let mut buffer: &mut [libc::c_uchar] = &mut source[..];
+            Some(Proc::as_buf(pid, name))
+        })
+    }
 
-                let _ = buffer.write(name.as_bytes());
+    /// The accessor method `get_foreground` returns the process group
+    /// in the foreground of the pty (`tcgetpgrp` on the master).
+    pub fn get_foreground(&self) -> Option<libc::pid_t> {
+        self.master.and_then(|master| {
+            match unsafe { libc::tcgetpgrp(master) } {
+                pgid if pgid > 0 => Some(pgid),
+                _ => None,
             }
-            Some((pid, source))
         })
     }
 
+    /// The method `get_tree` returns the tree of a process of the list,
+    /// the foreground's job of the master is marked.
+    pub fn get_tree(&self, pid: libc::pid_t) -> Option<ProcessTree> {
+        ProcessTree::new(&self.list, pid, self.get_foreground())
+    }
+
+    /// The method `get_job` returns the job's event of the group's
+    /// leader, or nothing when the leader has exited.
+    pub fn get_job(&self, pgid: libc::pid_t) -> Option<BufProc> {
+        ProcessInfo::new(pgid).ok().map(|info| Proc::as_buf(pgid, info.get_name()))
+    }
+
+    /// The method `get_info` returns the description of a process
+    /// of the list according to the pid.
+    pub fn get_info(&self, pid: libc::pid_t) -> Option<ProcessInfo> {
+        self.list.iter().find(
+            |&&(ref current_pid, _, _, _)| pid.eq(current_pid)
+        ).and_then(|_| ProcessInfo::new(pid).ok())
+    }
+
+    /// The method `get_cwd` returns the working directory of a process.
+    pub fn get_cwd(&self, pid: libc::pid_t) -> Option<PathBuf> {
+        ProcessInfo::new(pid).ok().and_then(|info| info.cwd)
+    }
+
+    /// The method `get_foreground_cwd` returns the working directory of
+    /// the foreground's leader, or of the child without foreground.
+    pub fn get_foreground_cwd(&self) -> Option<PathBuf> {
+        self.get_foreground().and_then(|pgid| self.get_cwd(pgid))
+                             .or_else(|| self.get_cwd(self.first_pid))
+    }
+
+    /// The method `get_running_info` returns the description of
+    /// the process which the tree is on.
+    pub fn get_running_info(&self) -> Option<ProcessInfo> {
+        self.get_info(self.running_pid)
+    }
+
   /// The method `current_pid` returns the pid which the process is on
   pub fn current_pid(&self) -> libc::pid_t
   { fn currpid(list: &Vec<(libc::pid_t, libc::pid_t, libc::c_uchar, String)>, from_pid: libc::pid_t, cur_pid: &mut libc::pid_t)
@@ -104,7 +208,13 @@ impl Iterator for Proc
 { type Item = BufProc;
 
   fn next(&mut self) -> Option<BufProc>
-  { self.list.clear();
+  { if self.master.is_some()
+    { return match self.get_foreground()
+      { Some(pgid) if pgid != self.running_pid =>
+          { self.running_pid = pgid;
+            self.get_job(pgid) },
+        _ => None, }}
+    self.list.clear();
     self.with_list_process().unwrap();
     let new_pid = self.current_pid();
     if self.running_pid != new_pid && new_pid > 1
@@ -123,6 +233,7 @@ impl Default for Proc {
             first_pid: 0,
             running_pid: 0,
             list: Vec::with_capacity(SPEC_CAPACITY_PROC),
+            master: None,
         }
     }
 }
@@ -0,0 +1,219 @@
+use std::time::{Duration, Instant};
+use std::path::PathBuf;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use super::connector::Connector;
+use super::event::ProcEvent;
+use super::usage::Usage;
+use super::{Proc, BufProc, ProcessInfo};
+use ::libc;
+
+/// The delay between two usages of the session.
+const TM_USAGE: u64 = 1000;
+
+/// The struct `Monitor` follows the descendants of the child and
+/// the pty's foreground, with the proc connector when it's available
+/// or by polling the process' table.
+
+#[derive(Debug)]
+pub struct Monitor {
+    /// The follower of the foreground's job.
+    task: Proc,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    connector: Option<Connector>,
+    /// The descendants by pid, ppid and name.
+    descendants: Vec<(libc::pid_t, libc::pid_t, String)>,
+    /// The time of the last usage.
+    usage: Instant,
+    /// The delay between two refreshes without event.
+    delay: Duration,
+    /// The time of the last refresh of the foreground.
+    refresh: Instant,
+    /// The working directory of the foreground.
+    cwd: Option<PathBuf>,
+}
+
+impl Monitor {
+
+    /// The constructor method `new` returns a Monitor of the child's pid,
+    /// the delay is the timeout of a reception from the proc connector.
+    #[allow(unused_variables)]
+    pub fn new(pid: libc::pid_t, master: libc::c_int, delay: Duration) -> Self {
+        let mut monitor: Monitor = Monitor {
+            task: Proc::from_master(pid, master),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            connector: Connector::new(delay).ok(),
+            descendants: Vec::new(),
+            usage: Instant::now(),
+            delay: delay,
+            refresh: Instant::now(),
+            cwd: None,
+        };
+
+        monitor.poll();
+        monitor.cwd = monitor.task.get_foreground_cwd();
+        monitor
+    }
+
+    /// The accessor method `is_evented` returns true when the events come
+    /// from the proc connector, the monitor doesn't need to sleep.
+    pub fn is_evented(&self) -> bool {
+        match () {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            () => self.connector.is_some(),
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            () => false,
+        }
+    }
+
+    /// The method `next_job` returns the new foreground's job.
+    pub fn next_job(&mut self) -> Option<BufProc> {
+        self.task.next()
+    }
+
+    /// The method `next_refresh` returns true when the foreground's job
+    /// and working directory are to be read again: once by delay, the
+    /// events of the descendants refresh them sooner.
+    pub fn next_refresh(&mut self) -> bool {
+        if self.refresh.elapsed() >= self.delay {
+            self.refresh = Instant::now();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The method `get_usage` returns the resources used by the child
+    /// and its descendants.
+    pub fn get_usage(&self) -> Usage {
+        let mut pids: Vec<libc::pid_t> = vec![self.task.first_pid];
+
+        pids.extend(self.descendants.iter().map(|&(pid, _, _)| pid));
+        Usage::from_pids(&pids)
+    }
+
+    /// The method `next_usage` returns the usage of the session
+    /// once by second.
+    pub fn next_usage(&mut self) -> Option<Usage> {
+        if self.usage.elapsed() >= Duration::from_millis(TM_USAGE) {
+            self.usage = Instant::now();
+            Some(self.get_usage())
+        } else {
+            None
+        }
+    }
+
+    /// The accessor method `get_cwd` returns the last working directory
+    /// of the foreground.
+    pub fn get_cwd(&self) -> Option<&PathBuf> {
+        self.cwd.as_ref()
+    }
+
+    /// The method `next_cwd` returns true when the working directory
+    /// of the foreground has changed since the last call.
+    pub fn next_cwd(&mut self) -> bool {
+        let cwd: Option<PathBuf> = self.task.get_foreground_cwd();
+
+        if cwd.is_some() && cwd.ne(&self.cwd) {
+            self.cwd = cwd;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The method `next_events` returns the forks, the execs and the exits
+    /// of the descendants since the last call. The lost events of a full
+    /// socket's buffer are recovered by a polling, the monitor falls back
+    /// to the polling when the connector fails.
+    pub fn next_events(&mut self) -> Vec<ProcEvent> {
+        match () {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            () => {
+                match self.connector.as_ref().map(|connector| connector.recv()) {
+                    Some(Ok(events)) => events.into_iter().filter(|&event| self.watch(event)).collect::<Vec<ProcEvent>>(),
+                    Some(Err(ref why)) if why.raw_os_error().eq(&Some(libc::ENOBUFS)) => self.poll(),
+                    Some(Err(_)) => {
+                        self.connector = None;
+                        self.poll()
+                    },
+                    None => self.poll(),
+                }
+            },
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            () => self.poll(),
+        }
+    }
+
+    /// The method `watch` updates the descendants with an event of the
+    /// connector, it returns false when the event concerns another process.
+    pub fn watch(&mut self, event: ProcEvent) -> bool {
+        let first_pid: libc::pid_t = self.task.first_pid;
+
+        match event {
+            ProcEvent::Fork(pid, parent) => {
+                if parent.eq(&first_pid) || self.descendants.iter().any(|&(current, _, _)| current.eq(&parent)) {
+                    self.descendants.push((pid, parent, Monitor::get_name(pid)));
+                    true
+                } else {
+                    false
+                }
+            },
+            ProcEvent::Exec(pid) => {
+                match self.descendants.iter_mut().find(|&&mut (current, _, _)| current.eq(&pid)) {
+                    Some(&mut (_, _, ref mut name)) => {
+                        *name = Monitor::get_name(pid);
+                        true
+                    },
+                    None => false,
+                }
+            },
+            ProcEvent::Exit(pid, _) => {
+                let len: usize = self.descendants.len();
+
+                self.descendants.retain(|&(current, _, _)| current.ne(&pid));
+                self.descendants.len().ne(&len)
+            },
+        }
+    }
+
+    /// The function `get_name` returns the name of a process, or nothing
+    /// when it has already exited.
+    fn get_name(pid: libc::pid_t) -> String {
+        ProcessInfo::new(pid).map(|process| process.get_name().to_string())
+                             .unwrap_or_default()
+    }
+
+    /// The method `poll` reads the process' table and returns the
+    /// differences with the last known descendants: a new pid is a fork,
+    /// a new name is an exec (when the last one is known) and a missing pid
+    /// is an exit.
+    fn poll(&mut self) -> Vec<ProcEvent> {
+        let first_pid: libc::pid_t = self.task.first_pid;
+        let descendants: Vec<(libc::pid_t, libc::pid_t, String)> = Proc::new(first_pid).ok()
+            .and_then(|task| task.get_tree(first_pid))
+            .map(|tree| {
+                tree.get_descendants().into_iter()
+                    .map(|process| (process.get_pid(), process.get_ppid(), process.get_name().to_string()))
+                    .collect::<Vec<(libc::pid_t, libc::pid_t, String)>>()
+            }).unwrap_or_default();
+        let mut events: Vec<ProcEvent> = self.descendants.iter().filter_map(|&(pid, _, _)| {
+            if descendants.iter().any(|&(current, _, _)| current.eq(&pid)) {
+                None
+            } else {
+                Some(ProcEvent::Exit(pid, None))
+            }
+        }).collect::<Vec<ProcEvent>>();
+
+        descendants.iter().all(|&(pid, ppid, ref name)| {
+            match self.descendants.iter().find(|&&(current, _, _)| current.eq(&pid)) {
+                None => events.push(ProcEvent::Fork(pid, ppid)),
+                Some(&(_, _, ref before)) if !before.is_empty() && before.ne(name) => events.push(ProcEvent::Exec(pid)),
+                Some(_) => {},
+            }
+            true
+        });
+        self.descendants = descendants;
+        events
+    }
+}
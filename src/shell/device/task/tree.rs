@@ -0,0 +1,98 @@
+use ::libc;
+
+/// The struct `ProcessTree` is a process with its descendants.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessTree {
+    pub pid: libc::pid_t,
+    pub ppid: libc::pid_t,
+    /// The unsized-name.
+    pub name: String,
+    /// The status (a letter on Linux, a number on macOS).
+    pub state: libc::c_uchar,
+    /// The process is in the foreground's group of the pty.
+    pub foreground: bool,
+    pub children: Vec<ProcessTree>,
+}
+
+impl ProcessTree {
+
+    /// The constructor method `new` returns the tree of a pid from a list
+    /// by pid, ppid, status and name, the processes in the foreground's
+    /// group are marked.
+    pub fn new(
+        list: &[(libc::pid_t, libc::pid_t, libc::c_uchar, String)],
+        pid: libc::pid_t,
+        foreground: Option<libc::pid_t>,
+    ) -> Option<Self> {
+        list.iter().find(|&&(current, _, _, _)| current.eq(&pid)).map(|&(pid, ppid, state, ref name)| {
+            ProcessTree {
+                pid: pid,
+                ppid: ppid,
+                name: name.clone(),
+                state: state,
+                foreground: foreground.map(|pgid| unsafe { libc::getpgid(pid) }.eq(&pgid)).unwrap_or_default(),
+                children: list.iter()
+                              .filter(|&&(child, parent, _, _)| parent.eq(&pid) && child.ne(&pid))
+                              .filter_map(|&(child, _, _, _)| ProcessTree::new(list, child, foreground))
+                              .collect::<Vec<ProcessTree>>(),
+            }
+        })
+    }
+
+    /// The accessor method `get_pid` returns the pid.
+    pub fn get_pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// The accessor method `get_ppid` returns the pid of the parent.
+    pub fn get_ppid(&self) -> libc::pid_t {
+        self.ppid
+    }
+
+    /// The accessor method `get_name` returns the unsized-name.
+    pub fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The accessor method `get_state` returns the status.
+    pub fn get_state(&self) -> libc::c_uchar {
+        self.state
+    }
+
+    /// The accessor method `is_foreground` returns true when the process
+    /// is in the foreground's group.
+    pub fn is_foreground(&self) -> bool {
+        self.foreground
+    }
+
+    /// The accessor method `get_children` returns the direct children.
+    pub fn get_children(&self) -> &[ProcessTree] {
+        self.children.as_slice()
+    }
+
+    /// The method `get_descendants` returns all the descendants,
+    /// each before its children.
+    pub fn get_descendants(&self) -> Vec<&ProcessTree> {
+        let mut descendants: Vec<&ProcessTree> = Vec::new();
+
+        self.children.iter().all(|child| {
+            descendants.push(child);
+            descendants.extend(child.get_descendants());
+            true
+        });
+        descendants
+    }
+
+    /// The method `get_background` returns the descendants which aren't
+    /// in the foreground's group (the background's jobs).
+    pub fn get_background(&self) -> Vec<&ProcessTree> {
+        self.get_descendants().into_iter().filter(|process| !process.foreground).collect::<Vec<&ProcessTree>>()
+    }
+
+    /// The method `is_busy` returns true when the process has live
+    /// descendants, the session shouldn't be closed without warning.
+    pub fn is_busy(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use super::info::ProcessInfo;
+use ::libc;
+
+/// The struct `Usage` is the sum of the resources used by the processes
+/// of a session.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// The number of processes.
+    pub processes: usize,
+    /// The time spent in the user and the system modes.
+    pub cpu: Duration,
+    /// The resident set size in bytes.
+    pub rss: u64,
+    /// The virtual memory size in bytes.
+    pub vsz: u64,
+}
+
+impl Usage {
+
+    /// The constructor method `from_pids` returns the sum of the resources
+    /// of the processes, the exited ones are skipped.
+    pub fn from_pids(pids: &[libc::pid_t]) -> Self {
+        pids.iter().filter_map(|&pid| ProcessInfo::new(pid).ok()).fold(Usage::default(), |usage, info| {
+            Usage {
+                processes: usage.processes + 1,
+                cpu: usage.cpu + info.get_cpu(),
+                rss: usage.rss + info.get_rss(),
+                vsz: usage.vsz + info.get_vsz(),
+            }
+        })
+    }
+
+    /// The accessor method `get_processes` returns the number of processes.
+    pub fn get_processes(&self) -> usize {
+        self.processes
+    }
+
+    /// The accessor method `get_cpu` returns the time spent by the processes.
+    pub fn get_cpu(&self) -> Duration {
+        self.cpu
+    }
+
+    /// The accessor method `get_rss` returns the resident set size in bytes.
+    pub fn get_rss(&self) -> u64 {
+        self.rss
+    }
+
+    /// The accessor method `get_vsz` returns the virtual memory size in bytes.
+    pub fn get_vsz(&self) -> u64 {
+        self.vsz
+    }
+}
+
+/// The enum `Limit` is the resource whose limit is hit.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Limit {
+    /// The time spent by the processes.
+    Cpu,
+    /// The resident memory of the processes.
+    Memory,
+    /// The number of processes.
+    Processes,
+}
+
+/// The struct `Limits` is the resources allowed to a session.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    cpu: Option<Duration>,
+    /// The memory in bytes.
+    memory: Option<u64>,
+    processes: Option<u64>,
+}
+
+impl Limits {
+
+    /// The constructor method `new` returns the limits of a session,
+    /// none is unlimited.
+    pub fn new(cpu: Option<Duration>, memory: Option<u64>, processes: Option<u64>) -> Self {
+        Limits {
+            cpu: cpu,
+            memory: memory,
+            processes: processes,
+        }
+    }
+
+    /// The accessor method `get_cpu` returns the limit of time.
+    pub fn get_cpu(&self) -> Option<Duration> {
+        self.cpu
+    }
+
+    /// The accessor method `get_memory` returns the limit of memory in bytes.
+    pub fn get_memory(&self) -> Option<u64> {
+        self.memory
+    }
+
+    /// The accessor method `get_processes` returns the limit of processes.
+    pub fn get_processes(&self) -> Option<u64> {
+        self.processes
+    }
+
+    /// The method `as_rlimits` returns the resources and the values of the
+    /// rlimits set to the child at the spawn: they bound each process, not
+    /// the sum of the session (see `Cgroup`). The memory is left to the
+    /// cgroup as `RLIMIT_AS` bounds the virtual size, not the resident one,
+    /// and the processes as `RLIMIT_NPROC` counts all those of the user.
+    pub fn as_rlimits(&self) -> Vec<(libc::c_int, libc::rlim_t)> {
+        let mut rlimits: Vec<(libc::c_int, libc::rlim_t)> = Vec::new();
+
+        if let Some(cpu) = self.cpu {
+            // The seconds are rounded up, the zero is unlimited.
+            let seconds: u64 = cpu.as_secs() + if cpu.subsec_nanos() > 0 { 1 } else { 0 };
+
+            rlimits.push((libc::RLIMIT_CPU as libc::c_int, seconds as libc::rlim_t));
+        }
+        rlimits
+    }
+
+    /// The method `exceeded` returns the first limit hit by a usage.
+    pub fn exceeded(&self, usage: &Usage) -> Option<Limit> {
+        if self.cpu.map(|cpu| usage.cpu >= cpu).unwrap_or_default() {
+            Some(Limit::Cpu)
+        } else if self.memory.map(|memory| usage.rss >= memory).unwrap_or_default() {
+            Some(Limit::Memory)
+        } else if self.processes.map(|processes| usage.processes as u64 >= processes).unwrap_or_default() {
+            Some(Limit::Processes)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+
+/// The alphabet of base64.
+const BASE64: &'static [u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The function `encode_base64` returns the base64 of the bytes.
+pub fn encode_base64(bytes: &[u8]) -> Vec<u8> {
+    let mut text: Vec<u8> = Vec::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    bytes.chunks(3).all(|chunk| {
+        let group: u32 = chunk.iter().enumerate().fold(0, |group, (index, &byte)| {
+            group | (byte as u32) << (16 - 8 * index)
+        });
+
+        (0..4).all(|index| {
+            if index <= chunk.len() {
+                text.push(BASE64[(group >> (18 - 6 * index) & 0x3F) as usize]);
+            } else {
+                text.push(b'=');
+            }
+            true
+        })
+    });
+    text
+}
+
+/// The function `decode_base64` returns the bytes of a base64 text,
+/// or nothing when the text isn't base64.
+pub fn decode_base64(text: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len() / 4 * 3);
+    let mut group: u32 = 0;
+    let mut count: usize = 0;
+
+    for &glyph in text.iter().take_while(|&&glyph| glyph != b'=') {
+        match BASE64.iter().position(|&letter| letter == glyph) {
+            Some(value) => {
+                group = group << 6 | value as u32;
+                count += 1;
+                if count == 4 {
+                    bytes.extend_from_slice(&[(group >> 16) as u8, (group >> 8) as u8, group as u8]);
+                    group = 0;
+                    count = 0;
+                }
+            },
+            None => return None,
+        }
+    }
+    match count {
+        0 => Some(bytes),
+        2 => {
+            bytes.push((group >> 4) as u8);
+            Some(bytes)
+        },
+        3 => {
+            bytes.extend_from_slice(&[(group >> 10) as u8, (group >> 2) as u8]);
+            Some(bytes)
+        },
+        _ => None,
+    }
+}
+
+/// The enum `ClipboardTarget` is a selection named by OSC 52.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ClipboardTarget {
+    /// `c`, the clipboard.
+    Clipboard,
+    /// `p`, the primary selection.
+    Primary,
+    /// `s`, the selection (the primary or the clipboard by the host).
+    Select,
+    /// `0` to `7`, a cut buffer.
+    Cut(u8),
+}
+
+impl ClipboardTarget {
+
+    /// The constructor method `new` returns the target of a letter.
+    pub fn new(letter: u8) -> Option<Self> {
+        match letter {
+            b'c' => Some(ClipboardTarget::Clipboard),
+            b'p' => Some(ClipboardTarget::Primary),
+            b's' => Some(ClipboardTarget::Select),
+            b'0'...b'7' => Some(ClipboardTarget::Cut(letter - b'0')),
+            _ => None,
+        }
+    }
+
+    /// The method `as_letter` returns the letter of the target.
+    pub fn as_letter(&self) -> u8 {
+        match *self {
+            ClipboardTarget::Clipboard => b'c',
+            ClipboardTarget::Primary => b'p',
+            ClipboardTarget::Select => b's',
+            ClipboardTarget::Cut(index) => b'0' + index,
+        }
+    }
+
+    /// The method `as_bit` returns the bit of the target in `Clipboard`.
+    fn as_bit(&self) -> u16 {
+        match *self {
+            ClipboardTarget::Clipboard => 1,
+            ClipboardTarget::Primary => 2,
+            ClipboardTarget::Select => 4,
+            ClipboardTarget::Cut(index) => 8 << index,
+        }
+    }
+}
+
+/// The enum `ClipboardPolicy` is what the child may do with OSC 52.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ClipboardPolicy {
+    /// The requests are ignored, the child's access is opt-in.
+    Ignore,
+    /// The child may set the clipboard.
+    Write,
+    /// The child may set and read the clipboard (see `ClipboardProvider`).
+    ReadWrite,
+}
+
+impl Default for ClipboardPolicy {
+    fn default() -> ClipboardPolicy {
+        ClipboardPolicy::Ignore
+    }
+}
+
+/// The trait `ClipboardProvider` gives to the shell the content of
+/// the host's clipboard to answer the child's queries.
+pub trait ClipboardProvider: fmt::Debug + Send {
+    /// The method `get` returns the content of a target.
+    fn get(&mut self, target: ClipboardTarget) -> Option<Vec<u8>>;
+}
+
+/// The struct `Clipboard` is a OSC 52 request of the child: the set of
+/// targets with a new content (see `Display::get_clipboard`), or a query.
+
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Clipboard {
+    targets: u16,
+    query: bool,
+}
+
+impl Clipboard {
+
+    /// The constructor method `new` returns a request from the letters of
+    /// the targets, none is the selection and the first cut buffer.
+    pub fn new(targets: &[u8], query: bool) -> Self {
+        let targets: Vec<ClipboardTarget> = if targets.is_empty() {
+            vec![ClipboardTarget::Select, ClipboardTarget::Cut(0)]
+        } else {
+            targets.iter().filter_map(|&letter| ClipboardTarget::new(letter)).collect::<Vec<ClipboardTarget>>()
+        };
+
+        Clipboard {
+            targets: targets.iter().fold(0, |bits, target| bits | target.as_bit()),
+            query: query,
+        }
+    }
+
+    /// The accessor method `get_targets` returns the targets.
+    pub fn get_targets(&self) -> Vec<ClipboardTarget> {
+        [ClipboardTarget::Clipboard, ClipboardTarget::Primary, ClipboardTarget::Select].iter()
+            .cloned()
+            .chain((0..8).map(|index| ClipboardTarget::Cut(index)))
+            .filter(|target| self.has(*target))
+            .collect::<Vec<ClipboardTarget>>()
+    }
+
+    /// The method `has` returns true when the request names the target.
+    pub fn has(&self, target: ClipboardTarget) -> bool {
+        self.targets & target.as_bit() != 0
+    }
+
+    /// The accessor method `is_query` returns true when the child asks
+    /// the content.
+    pub fn is_query(&self) -> bool {
+        self.query
+    }
+
+    /// The method `answer` returns the answer of a query with a content.
+    pub fn answer(&self, content: &[u8]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = b"\x1B]52;".to_vec();
+
+        bytes.extend(self.get_targets().iter().map(|target| target.as_letter()));
+        bytes.push(b';');
+        bytes.extend(encode_base64(content));
+        bytes.extend_from_slice(b"\x1B\\");
+        bytes
+    }
+}
@@ -3,12 +3,16 @@ use super::winsz::WinszedError;
 use std::fmt;
 use std::error::Error;
 
+use ::regex;
+
 /// The enum `DisplayError` defines the possible errors from constructor Display.
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum DisplayError {
     /// Winszed has occured an error.
     WinszedFail(WinszedError),
+    /// The pattern of a search isn't a valid regular expression.
+    RegexFail(regex::Error),
 }
 
 impl fmt::Display for DisplayError {
@@ -26,6 +30,7 @@ impl Error for DisplayError {
     fn description(&self) -> &str {
         match *self {
             DisplayError::WinszedFail(_) => "Winszed interface has occured an error.",
+            DisplayError::RegexFail(_) => "The pattern isn't a valid regular expression.",
         }
     }
 
@@ -33,6 +38,7 @@ impl Error for DisplayError {
     fn cause(&self) -> Option<&Error> {
         match *self {
             DisplayError::WinszedFail(ref err) => Some(err),
+            DisplayError::RegexFail(ref err) => Some(err),
         }
     }
 }
@@ -0,0 +1,150 @@
+use ::libc;
+
+/// The enum `MarkKind` is a semantic prompt's mark of the shell
+/// integration (OSC 133).
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MarkKind {
+    /// `A`, the prompt starts.
+    Prompt,
+    /// `B`, the prompt ends and the command starts.
+    Command,
+    /// `C`, the command is run and its output starts.
+    Output,
+    /// `D`, the command ends.
+    End,
+}
+
+impl MarkKind {
+
+    /// The constructor method `new` returns the kind of a mark's letter.
+    pub fn new(letter: &[u8]) -> Option<Self> {
+        match letter {
+            b"A" => Some(MarkKind::Prompt),
+            b"B" => Some(MarkKind::Command),
+            b"C" => Some(MarkKind::Output),
+            b"D" => Some(MarkKind::End),
+            _ => None,
+        }
+    }
+}
+
+/// The struct `Mark` is a semantic prompt's mark anchored to a line
+/// counted from the start of the session, it follows the scroll.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mark {
+    kind: MarkKind,
+    /// The line since the start of the session.
+    line: libc::size_t,
+    column: libc::size_t,
+    /// The exit's status given with the end of a command.
+    exit: Option<libc::c_int>,
+}
+
+impl Mark {
+
+    /// The constructor method `new` returns a mark at a line and a column.
+    pub fn new(kind: MarkKind, line: libc::size_t, column: libc::size_t, exit: Option<libc::c_int>) -> Self {
+        Mark {
+            kind: kind,
+            line: line,
+            column: column,
+            exit: exit,
+        }
+    }
+
+    /// The accessor method `get_kind` returns the kind.
+    pub fn get_kind(&self) -> MarkKind {
+        self.kind
+    }
+
+    /// The accessor method `get_line` returns the line since the start
+    /// of the session (see `Display::get_scrolled`).
+    pub fn get_line(&self) -> libc::size_t {
+        self.line
+    }
+
+    /// The accessor method `get_column` returns the column.
+    pub fn get_column(&self) -> libc::size_t {
+        self.column
+    }
+
+    /// The accessor method `get_exit` returns the exit's status of an end.
+    pub fn get_exit(&self) -> Option<libc::c_int> {
+        self.exit
+    }
+
+    /// The mutator method `set_line` moves the mark to another line.
+    pub fn set_line(&mut self, line: libc::size_t) {
+        self.line = line;
+    }
+}
+
+/// The struct `Command` is a command of the session: its prompt, and
+/// the marks of its line, of its output and of its end when they're known.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Command {
+    prompt: Mark,
+    command: Option<Mark>,
+    output: Option<Mark>,
+    end: Option<Mark>,
+}
+
+impl Command {
+
+    /// The constructor method `from_marks` returns the commands of the marks,
+    /// a command starts with a prompt (the marks before the first prompt
+    /// are ignored).
+    pub fn from_marks(marks: &[Mark]) -> Vec<Self> {
+        let mut commands: Vec<Command> = Vec::new();
+
+        marks.iter().all(|&mark| {
+            match (mark.get_kind(), commands.last_mut()) {
+                (MarkKind::Prompt, _) => commands.push(Command {
+                    prompt: mark,
+                    command: None,
+                    output: None,
+                    end: None,
+                }),
+                (MarkKind::Command, Some(command)) => command.command = Some(mark),
+                (MarkKind::Output, Some(command)) => command.output = Some(mark),
+                (MarkKind::End, Some(command)) => command.end = Some(mark),
+                (_, None) => {},
+            }
+            true
+        });
+        commands
+    }
+
+    /// The accessor method `get_prompt` returns the mark of the prompt.
+    pub fn get_prompt(&self) -> &Mark {
+        &self.prompt
+    }
+
+    /// The accessor method `get_command` returns the mark of the command's line.
+    pub fn get_command(&self) -> Option<&Mark> {
+        self.command.as_ref()
+    }
+
+    /// The accessor method `get_output` returns the mark of the output.
+    pub fn get_output(&self) -> Option<&Mark> {
+        self.output.as_ref()
+    }
+
+    /// The accessor method `get_end` returns the mark of the end.
+    pub fn get_end(&self) -> Option<&Mark> {
+        self.end.as_ref()
+    }
+
+    /// The accessor method `get_exit` returns the exit's status.
+    pub fn get_exit(&self) -> Option<libc::c_int> {
+        self.end.and_then(|end| end.get_exit())
+    }
+
+    /// The method `is_running` returns true when the command runs.
+    pub fn is_running(&self) -> bool {
+        self.output.is_some() && self.end.is_none()
+    }
+}
@@ -2,6 +2,11 @@ mod err;
 pub mod winsz;
 pub mod cursor;
 pub mod character;
+pub mod selection;
+pub mod clipboard;
+pub mod mark;
+pub mod notification;
+pub mod search;
 
 use std::ops::{self, BitAnd, Add, Sub, Mul, Not};
 use std::io::{self, Write};
@@ -9,6 +14,9 @@ use std::iter;
 use std::fmt;
 use std::mem;
 use std::cmp;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
 
 use ::libc;
 
@@ -17,10 +25,25 @@ pub use self::err::DisplayError;
 use self::cursor::Cursor;
 use self::character::color;
 pub use self::character::Character;
+pub use self::selection::{Selection, SelectionMode};
+pub use self::clipboard::{Clipboard, ClipboardTarget, ClipboardPolicy, ClipboardProvider};
+pub use self::mark::{Mark, MarkKind, Command};
+pub use self::notification::{Notification, NotificationHandler};
+pub use self::search::{Search, SearchCase, SearchDirection};
 use self::character::attribute::Attribute;
+use super::device::control::operate::key::KeyModes;
+use super::device::control::operate::mouse::{MouseMode, MouseEncoding};
 
 pub const LIMIT_X: libc::size_t = 1000;
 pub const LIMIT_Y: libc::size_t = 1000;
+/// The depth of the stack of keyboard's flags.
+pub const LIMIT_KEYBOARD: libc::size_t = 16;
+/// The length of a operating system command kept until its end.
+pub const LIMIT_OSC: libc::size_t = 1 << 20;
+/// The number of semantic prompt's marks kept.
+pub const LIMIT_MARKS: libc::size_t = 4096;
+/// The number of child's notifications kept.
+pub const LIMIT_NOTIFICATIONS: libc::size_t = 16;
 
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Coordinate {
@@ -277,8 +300,21 @@ impl Default for Newline {
 pub struct Table {
     pub save_position: Coordinate,
     pub show_cursor: bool,
-    pub mouse_handle: (bool, bool, bool, bool),
+    /// The mouse's tracking requested by the child.
+    pub mouse_mode: MouseMode,
+    /// The format of the mouse's reports.
+    pub mouse_encoding: MouseEncoding,
     pub ss_mod: bool,
+    /// DECKPAM, the keypad is in application mode.
+    pub keypad: bool,
+    /// DECBKM, the backspace sends BS.
+    pub backspace: bool,
+    /// The pasted text is bracketed.
+    pub paste: bool,
+    /// The xterm's modifyOtherKeys level.
+    pub modify_keys: libc::c_uint,
+    /// The child listens the focus' changes.
+    pub focus: bool,
     pub region: Coordinate,
     pub collection: Character,
     /// Out of Bound
@@ -288,6 +324,35 @@ pub struct Table {
     pub bell: libc::size_t,
     pub screen: Cursor<Vec<Character>>,
     pub newline: Newline,
+    /// The stack of kitty's keyboard flags requested by the child.
+    pub keyboard: Vec<libc::c_uint>,
+    /// The answers to the child's queries, waiting to be written.
+    pub answer: Vec<u8>,
+    /// The operating system command not yet ended.
+    pub osc: Option<Vec<u8>>,
+    /// The operating system command is longer than `LIMIT_OSC` and is
+    /// swallowed until its end.
+    pub osc_discard: bool,
+    /// What the child may do with the clipboard.
+    pub clipboard_policy: ClipboardPolicy,
+    /// The last content given by the child to the clipboard.
+    pub clipboard: Vec<u8>,
+    /// The last request of clipboard, not yet popped.
+    pub clipboard_request: Option<Clipboard>,
+    /// The number of lines scrolled out of the screen's top.
+    pub scrolled: libc::size_t,
+    /// The semantic prompt's marks (OSC 133).
+    pub marks: Vec<Mark>,
+    /// The host and the working directory given by the child (OSC 7).
+    pub cwd: Option<(String, PathBuf)>,
+    /// The working directory has changed, not yet popped.
+    pub cwd_changed: bool,
+    /// The bell has rung, not yet popped.
+    pub bell_rung: bool,
+    /// The last notifications of the child (OSC 9 and OSC 777).
+    pub notifications: Vec<Notification>,
+    /// The number of notifications not yet popped.
+    pub notified: libc::size_t,
 }
 
 #[derive(Debug, Clone)]
@@ -314,8 +379,14 @@ impl Display {
             table: Table {
               save_position: Coordinate::default(),
               show_cursor: true,
-              mouse_handle: (false, false, false, false),
+              mouse_mode: MouseMode::Off,
+              mouse_encoding: MouseEncoding::Default,
               ss_mod: false,
+              keypad: false,
+              backspace: false,
+              paste: false,
+              modify_keys: 0,
+              focus: false,
               newline: Newline::from(size),
               region: Coordinate::from((0, size.get_row())),
               collection: Character::default(),
@@ -323,6 +394,20 @@ impl Display {
               line_wrap: true,
               size: size,
               bell: 0,
+              keyboard: Vec::new(),
+              answer: Vec::new(),
+              osc: None,
+              osc_discard: false,
+              clipboard_policy: ClipboardPolicy::default(),
+              clipboard: Vec::new(),
+              clipboard_request: None,
+              scrolled: 0,
+              marks: Vec::new(),
+              cwd: None,
+              cwd_changed: false,
+              bell_rung: false,
+              notifications: Vec::new(),
+              notified: 0,
               screen: Cursor::new(
                 (0..size.row_by_col()).map(|_: usize|
                                               Character::default()
@@ -337,9 +422,389 @@ impl Display {
         self.table.ss_mod
     }
 
-    /// The accessor method `get_mouse` returns the value of mouse_handle'.
-    pub fn get_mouse(&self) -> (bool, bool, bool, bool) {
-        self.table.mouse_handle
+    /// The accessor method `get_key_modes` returns the modes of the child
+    /// which change the bytes of a key.
+    pub fn get_key_modes(&self) -> KeyModes {
+        KeyModes {
+            cursor: self.table.ss_mod,
+            keypad: self.table.keypad,
+            backspace: self.table.backspace,
+            paste: self.table.paste,
+            modify_other_keys: self.table.modify_keys,
+            keyboard: self.get_keyboard(),
+        }
+    }
+
+    /// The accessor method `get_focus_report` returns true when the child
+    /// listens the focus' changes.
+    pub fn get_focus_report(&self) -> bool {
+        self.table.focus
+    }
+
+    /// The accessor method `get_keyboard` returns the kitty's keyboard flags
+    /// of the child.
+    pub fn get_keyboard(&self) -> libc::c_uint {
+        self.table.keyboard.last().cloned().unwrap_or_default()
+    }
+
+    /// The method `pop_answer` returns and forgets the answers to the
+    /// child's queries.
+    pub fn pop_answer(&mut self) -> Vec<u8> {
+        mem::replace(&mut self.table.answer, Vec::new())
+    }
+
+    /// The accessor method `get_clipboard_policy` returns what the child
+    /// may do with the clipboard.
+    pub fn get_clipboard_policy(&self) -> ClipboardPolicy {
+        self.table.clipboard_policy
+    }
+
+    /// The mutator method `set_clipboard_policy` changes what the child
+    /// may do with the clipboard.
+    pub fn set_clipboard_policy(&mut self, policy: ClipboardPolicy) {
+        self.table.clipboard_policy = policy;
+    }
+
+    /// The accessor method `get_clipboard` returns the last content
+    /// given by the child to the clipboard.
+    pub fn get_clipboard(&self) -> &[u8] {
+        self.table.clipboard.as_slice()
+    }
+
+    /// The method `pop_clipboard` returns and forgets the last request
+    /// of clipboard.
+    pub fn pop_clipboard(&mut self) -> Option<Clipboard> {
+        self.table.clipboard_request.take()
+    }
+
+    /// The method `osc_continue` reads a operating system command until
+    /// its end (BEL or ST), the command is kept between the writes.
+    /// A command longer than `LIMIT_OSC` is swallowed without being applied.
+    fn osc_continue(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut osc: Vec<u8> = self.table.osc.take().unwrap_or_default();
+        // The ST can be cut between two writes.
+        let from: libc::size_t = osc.len().saturating_sub(1);
+
+        osc.extend_from_slice(buf);
+        match osc[from..].iter().enumerate().find(|&(index, &byte)| {
+            byte.eq(&b'\x07') || (byte.eq(&b'\x1B') && osc.get(from + index + 1).eq(&Some(&b'\\')))
+        }).map(|(index, &byte)| (from + index, if byte.eq(&b'\x07') { 1 } else { 2 })) {
+            Some((end, len)) => {
+                let next: Vec<u8> = osc[end + len..].to_vec();
+
+                if !mem::replace(&mut self.table.osc_discard, false) {
+                    self.osc(&osc[..end]);
+                }
+                self.write(&next)
+            },
+            None => {
+                if osc.len() > LIMIT_OSC {
+                    // Only the last byte is kept, it may start the ST.
+                    let last: usize = osc.len() - 1;
+
+                    osc.drain(..last);
+                    self.table.osc_discard = true;
+                }
+                self.table.osc = Some(osc);
+                Ok(0)
+            },
+        }
+    }
+
+    /// The method `osc` applies a operating system command.
+    fn osc(&mut self, command: &[u8]) {
+        let mut fields = command.splitn(3, |&byte| byte.eq(&b';'));
+
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(b"52"), Some(targets), Some(data)) => {
+                match (self.table.clipboard_policy, data) {
+                    (ClipboardPolicy::Ignore, _) => {},
+                    (ClipboardPolicy::ReadWrite, b"?") => {
+                        self.table.clipboard_request = Some(Clipboard::new(targets, true));
+                    },
+                    (_, b"?") => {},
+                    (_, data) => {
+                        if let Some(content) = clipboard::decode_base64(data) {
+                            self.table.clipboard = content;
+                            self.table.clipboard_request = Some(Clipboard::new(targets, false));
+                        }
+                    },
+                }
+            },
+            (Some(b"9"), Some(_), _) |
+            (Some(b"777"), Some(_), _) => {
+                if let Some(notification) = Notification::from_osc(command) {
+                    if self.table.notifications.len() >= LIMIT_NOTIFICATIONS {
+                        self.table.notifications.remove(0);
+                    }
+                    self.table.notifications.push(notification);
+                    self.table.notified = cmp::min(self.table.notified + 1, LIMIT_NOTIFICATIONS);
+                }
+            },
+            (Some(b"7"), Some(url), rest) => {
+                // The path can hold a `;`.
+                let mut url: Vec<u8> = url.to_vec();
+
+                if let Some(rest) = rest {
+                    url.push(b';');
+                    url.extend_from_slice(rest);
+                }
+                let (host, path): (&[u8], &[u8]) = if url.starts_with(b"file://") {
+                    let url: &[u8] = &url[7..];
+
+                    match url.iter().position(|&byte| byte.eq(&b'/')) {
+                        Some(index) => (&url[..index], &url[index..]),
+                        None => return ,
+                    }
+                } else if url.starts_with(b"/") {
+                    (b"", &url[..])
+                } else {
+                    return ;
+                };
+                let cwd: (String, PathBuf) = (
+                    String::from_utf8_lossy(host).into_owned(),
+                    PathBuf::from(OsStr::from_bytes(&decode_percent(path))),
+                );
+
+                if self.table.cwd.as_ref().ne(&Some(&cwd)) {
+                    self.table.cwd = Some(cwd);
+                    self.table.cwd_changed = true;
+                }
+            },
+            (Some(b"133"), Some(kind), rest) => {
+                if let Some(kind) = MarkKind::new(kind) {
+                    let exit: Option<libc::c_int> = rest.and_then(|rest| {
+                        rest.split(|&byte| byte.eq(&b';')).next()
+                    }).and_then(|exit| {
+                        String::from_utf8_lossy(exit).parse::<libc::c_int>().ok()
+                    });
+                    let mark: Mark = Mark::new(kind, self.table.scrolled + self.table.oob.y, self.table.oob.x, exit);
+
+                    if self.table.marks.len() >= LIMIT_MARKS {
+                        self.table.marks.remove(0);
+                    }
+                    self.table.marks.push(mark);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// The accessor method `get_cwd` returns the working directory
+    /// given by the child (OSC 7).
+    pub fn get_cwd(&self) -> Option<&Path> {
+        self.table.cwd.as_ref().map(|&(_, ref cwd)| cwd.as_path())
+    }
+
+    /// The accessor method `get_cwd_host` returns the host of the working
+    /// directory given by the child, empty when it's local.
+    pub fn get_cwd_host(&self) -> Option<&str> {
+        self.table.cwd.as_ref().map(|&(ref host, _)| host.as_str())
+    }
+
+    /// The method `pop_cwd` returns and forgets the change of the working
+    /// directory.
+    pub fn pop_cwd(&mut self) -> bool {
+        mem::replace(&mut self.table.cwd_changed, false)
+    }
+
+    /// The accessor method `get_bell` returns the number of bells
+    /// rung by the child.
+    pub fn get_bell(&self) -> libc::size_t {
+        self.table.bell
+    }
+
+    /// The method `pop_bell` returns and forgets the ring of the bell.
+    pub fn pop_bell(&mut self) -> bool {
+        mem::replace(&mut self.table.bell_rung, false)
+    }
+
+    /// The accessor method `get_notifications` returns the last
+    /// notifications of the child, from the oldest.
+    pub fn get_notifications(&self) -> &[Notification] {
+        self.table.notifications.as_slice()
+    }
+
+    /// The method `pop_notifications` returns and forgets the number of
+    /// new notifications, which are the last of `get_notifications`.
+    pub fn pop_notifications(&mut self) -> libc::size_t {
+        mem::replace(&mut self.table.notified, 0)
+    }
+
+    /// The accessor method `get_scrolled` returns the number of lines
+    /// scrolled out of the screen's top, the line of a mark on the screen
+    /// is its line less this number.
+    pub fn get_scrolled(&self) -> libc::size_t {
+        self.table.scrolled
+    }
+
+    /// The accessor method `get_marks` returns the semantic prompt's marks.
+    pub fn get_marks(&self) -> &[Mark] {
+        self.table.marks.as_slice()
+    }
+
+    /// The method `get_commands` returns the commands of the session.
+    pub fn get_commands(&self) -> Vec<Command> {
+        Command::from_marks(&self.table.marks)
+    }
+
+    /// The method `next_prompt` returns the first prompt after a line
+    /// (since the start of the session).
+    pub fn next_prompt(&self, line: libc::size_t) -> Option<&Mark> {
+        self.table.marks.iter().find(|mark| {
+            mark.get_kind().eq(&MarkKind::Prompt) && mark.get_line().gt(&line)
+        })
+    }
+
+    /// The method `previous_prompt` returns the last prompt before a line
+    /// (since the start of the session).
+    pub fn previous_prompt(&self, line: libc::size_t) -> Option<&Mark> {
+        self.table.marks.iter().rev().find(|mark| {
+            mark.get_kind().eq(&MarkKind::Prompt) && mark.get_line().lt(&line)
+        })
+    }
+
+    /// The method `get_text_between` returns the text from a mark until
+    /// another (excluded) or until the cursor, or nothing when the start
+    /// has scrolled out of the screen.
+    fn get_text_between(&self, from: &Mark, to: Option<&Mark>) -> Option<String> {
+        let col: libc::size_t = self.table.size.get_col();
+        let start: Coordinate = match from.get_line().checked_sub(self.table.scrolled) {
+            Some(y) if y.lt(&self.table.size.get_row()) => Coordinate::from((from.get_column(), y)),
+            _ => return None,
+        };
+        let end: Coordinate = match to {
+            Some(to) => Coordinate::from((to.get_column(), to.get_line().saturating_sub(self.table.scrolled))),
+            None => self.table.oob,
+        };
+
+        if col.eq(&0) || (end.y, end.x) <= (start.y, start.x) {
+            return Some(String::new());
+        }
+        let mut selection: Selection = Selection::new(SelectionMode::Linear, start);
+
+        selection.extend(if end.x.gt(&0) {
+            Coordinate::from((end.x - 1, end.y))
+        } else {
+            Coordinate::from((col - 1, end.y - 1))
+        });
+        Some(selection.get_text(self))
+    }
+
+    /// The method `get_command_line` returns the line typed for a command.
+    pub fn get_command_line(&self, command: &Command) -> Option<String> {
+        command.get_command().and_then(|from| {
+            self.get_text_between(from, command.get_output())
+        }).map(|line| line.trim().to_string())
+    }
+
+    /// The method `get_output` returns the output of a command until its
+    /// end, or until the cursor when it runs.
+    pub fn get_output(&self, command: &Command) -> Option<String> {
+        command.get_output().and_then(|from| self.get_text_between(from, command.get_end()))
+    }
+
+    /// The method `get_last_output` returns the output of the last command
+    /// which has an output.
+    pub fn get_last_output(&self) -> Option<String> {
+        self.get_commands().iter().rev().find(|command| command.get_output().is_some()).and_then(|command| {
+            self.get_output(command)
+        })
+    }
+
+    /// The method `scroll_marks` follows a scroll of the lines between
+    /// the base and the bottom of the region: the marks of the line which
+    /// leaves the region are forgotten unless it leaves the screen's top.
+    fn scroll_marks(&mut self, base: libc::size_t, up: bool) {
+        let (scrolled, bottom): (libc::size_t, libc::size_t) = (self.table.scrolled, self.table.region.y);
+
+        if up && base.eq(&0) && bottom.eq(&self.table.size.get_row()) {
+            self.table.scrolled += 1;
+        } else if up {
+            self.table.marks.retain(|mark| mark.get_line().ne(&(scrolled + base)));
+            self.table.marks.iter_mut().filter(|mark| {
+                mark.get_line().gt(&(scrolled + base)) && mark.get_line().lt(&(scrolled + bottom))
+            }).all(|mark| {
+                let line: libc::size_t = mark.get_line();
+
+                mark.set_line(line - 1);
+                true
+            });
+        } else {
+            self.table.marks.retain(|mark| mark.get_line().add(&1).ne(&(scrolled + bottom)));
+            self.table.marks.iter_mut().filter(|mark| {
+                mark.get_line().ge(&(scrolled + base)) && mark.get_line().add(&1).lt(&(scrolled + bottom))
+            }).all(|mark| {
+                let line: libc::size_t = mark.get_line();
+
+                mark.set_line(line + 1);
+                true
+            });
+        }
+    }
+
+    /// The method `keyboard` applies a kitty's keyboard request
+    /// (`CSI > flags u`, `CSI < n u`, `CSI = flags ; mode u`).
+    fn keyboard(&mut self, prefix: u8, params: &[libc::size_t]) {
+        let first: libc::c_uint = params.first().cloned().unwrap_or_default() as libc::c_uint;
+
+        match prefix {
+            b'>' => {
+                if self.table.keyboard.len() >= LIMIT_KEYBOARD {
+                    self.table.keyboard.remove(0);
+                }
+                self.table.keyboard.push(first);
+            },
+            b'<' => {
+                let len: libc::size_t = self.table.keyboard.len();
+
+                self.table.keyboard.truncate(len.saturating_sub(cmp::max(first as libc::size_t, 1)));
+            },
+            _ => {
+                let current: libc::c_uint = self.get_keyboard();
+                let flags: libc::c_uint = match params.get(1).cloned().unwrap_or(1) {
+                    2 => current | first,
+                    3 => current & !first,
+                    _ => first,
+                };
+
+                if let Some(last) = self.table.keyboard.last_mut() {
+                    *last = flags;
+                    return ;
+                }
+                self.table.keyboard.push(flags);
+            },
+        }
+    }
+
+    /// The accessor method `get_mouse_mode` returns the mouse's tracking
+    /// requested by the child.
+    pub fn get_mouse_mode(&self) -> MouseMode {
+        self.table.mouse_mode
+    }
+
+    /// The accessor method `get_mouse_encoding` returns the format of
+    /// the mouse's reports requested by the child.
+    pub fn get_mouse_encoding(&self) -> MouseEncoding {
+        self.table.mouse_encoding
+    }
+
+    /// The method `mouse` sets or resets a private mode of the mouse,
+    /// the reset of a mode which isn't the current one is ignored.
+    fn mouse(&mut self, number: libc::size_t, set: bool) {
+        if let Some(mode) = MouseMode::from_private(number) {
+            if set {
+                self.table.mouse_mode = mode;
+            } else if self.table.mouse_mode.eq(&mode) {
+                self.table.mouse_mode = MouseMode::Off;
+            }
+        } else if let Some(encoding) = MouseEncoding::from_private(number) {
+            if set {
+                self.table.mouse_encoding = encoding;
+            } else if self.table.mouse_encoding.eq(&encoding) {
+                self.table.mouse_encoding = MouseEncoding::Default;
+            }
+        }
     }
 
     /// The accessor `get_window_size` returns the window size interface.
@@ -364,6 +829,21 @@ impl Display {
         &self.table.newline
     }
 
+    /// The accessor method `get_line` returns the characters of a line.
+    pub fn get_line(&self, y: libc::size_t) -> Option<&[Character]> {
+        let col: libc::size_t = self.table.size.get_col();
+
+        self.table.screen.get_ref().chunks(cmp::max(col, 1)).nth(y)
+    }
+
+    /// The accessor method `is_wrapped` returns true when the line
+    /// continues on the next one (without newline).
+    pub fn is_wrapped(&self, y: libc::size_t) -> bool {
+        y.add(&1).lt(&self.table.size.get_row()).bitand(
+            self.table.newline.into_iter().any(|pos| pos.y.eq(&y)).not()
+        )
+    }
+
     /// Converts a Vector of Character into a byte vector.
     pub fn into_bytes(&self) -> Vec<libc::c_uchar> {
         let mut screen: Vec<libc::c_uchar> = Vec::new();
@@ -375,6 +855,27 @@ impl Display {
         screen
     }
 
+    /// The accessor method `get_text` returns the glyphs of the screen
+    /// by line, without the trailing spaces.
+    pub fn get_text(&self) -> String {
+        let col: libc::size_t = self.table.size.get_col();
+
+        if col.eq(&0) {
+            return String::new();
+        }
+        self.table.screen.get_ref()
+                         .chunks(col)
+                         .map(|characters| {
+                             characters.iter()
+                                       .map(|character| character.get_glyph())
+                                       .collect::<String>()
+                                       .trim_right()
+                                       .to_string()
+                         })
+                         .collect::<Vec<String>>()
+                         .join("\n")
+    }
+
     /// The method `clear` purges the screen vector.
     pub fn clear(&mut self) -> io::Result<libc::size_t> {
         self.table.screen.get_mut().iter_mut().all(|term: &mut Character| {
@@ -574,7 +1075,8 @@ impl Display {
     /// The method `scroll_down` append an empty line on bottom of the screen
     /// (the cursor doesn't move)
     pub fn scroll_down(&mut self, base: libc::size_t)
-    { let col = self.table.size.get_col();
+    { self.scroll_marks(base, false);
+      let col = self.table.size.get_col();
       let collection = self.table.collection;
       if self.table.show_cursor
       { self.clear_cursor(); }
@@ -601,7 +1103,8 @@ impl Display {
     /// The method `scroll_up` insert an empty line on top of the screen
     /// (the cursor doesn't move)
     pub fn scroll_up(&mut self, base: libc::size_t)
-    { let col = self.table.size.get_col();
+    { self.scroll_marks(base, true);
+      let col = self.table.size.get_col();
       let collection = self.table.collection;
       if self.table.show_cursor
       { self.clear_cursor(); }
@@ -807,7 +1310,8 @@ impl Display {
         if let Some(save_terminal) = self.save_terminal.clone() {
             self.table.save_position = save_terminal.save_position;
             self.table.show_cursor = save_terminal.show_cursor;
-            self.table.mouse_handle = save_terminal.mouse_handle;
+            self.table.mouse_mode = save_terminal.mouse_mode;
+            self.table.mouse_encoding = save_terminal.mouse_encoding;
             self.table.ss_mod = save_terminal.ss_mod;
             self.table.newline = save_terminal.newline.clone();
             self.table.region = save_terminal.region;
@@ -816,6 +1320,8 @@ impl Display {
             self.table.line_wrap = save_terminal.line_wrap;
             self.table.screen = save_terminal.screen.clone();
             self.table.bell = save_terminal.bell;
+            self.table.scrolled = save_terminal.scrolled;
+            self.table.marks = save_terminal.marks.clone();
             if self.table.size != save_terminal.size {
                 self.table.size = save_terminal.size;
                 let _ = self.resize();
@@ -893,6 +1399,29 @@ impl<'a> IntoIterator for &'a Display {
     }
 }
 
+/// The function `decode_percent` returns the bytes of an url's
+/// percent-encoded text.
+fn decode_percent(text: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    let mut index: libc::size_t = 0;
+
+    while let Some(&byte) = text.get(index) {
+        match (byte, text.get(index + 1..index + 3).and_then(|hex| {
+            u8::from_str_radix(&String::from_utf8_lossy(hex), 16).ok()
+        })) {
+            (b'%', Some(decoded)) => {
+                bytes.push(decoded);
+                index += 3;
+            },
+            (byte, _) => {
+                bytes.push(byte);
+                index += 1;
+            },
+        }
+    }
+    bytes
+}
+
 impl Default for Display {
     fn default() -> Display {
         unsafe {
@@ -930,6 +1459,9 @@ impl Write for Display {
                 { self.color_cursor(); }
                 Ok(0) },
 
+            _ if self.table.osc.is_some() =>
+              { self.osc_continue(buf) },
+
             //---------- TERMINAL SAVE -----------
             &[b'\x1B', b'[', b'?', b'1', b'0', b'4', b'9', b'h', ref next..] =>
               { self.save_terminal();
@@ -939,29 +1471,58 @@ impl Write for Display {
                 self.write(next) },
 
             //----------.mouse_handle -----------
-            &[b'\x1B', b'[', b'?', b'9', b'h', ref next..] =>
-              { self.table.mouse_handle.0 = true;
+            &[b'\x1B', b'[', b'?', b'9', set @ b'h', ref next..] |
+            &[b'\x1B', b'[', b'?', b'9', set @ b'l', ref next..] =>
+              { self.mouse(9, set.eq(&b'h'));
+                self.write(next) },
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', mode @ b'0'...b'6', set @ b'h', ref next..] |
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', mode @ b'0'...b'6', set @ b'l', ref next..]
+            if mode != b'1' && mode != b'4' =>
+              { self.mouse(1000 + (mode - b'0') as libc::size_t, set.eq(&b'h'));
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'9', b'l', ref next..] =>
-              { self.table.mouse_handle.0 = false;
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'1', mode @ b'5'...b'6', set @ b'h', ref next..] |
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'1', mode @ b'5'...b'6', set @ b'l', ref next..] =>
+              { self.mouse(1010 + (mode - b'0') as libc::size_t, set.eq(&b'h'));
+                self.write(next) },
+
+            //------------ KEYBOARD -------------
+            &[b'\x1B', b'[', b'?', b'u', ref next..] =>
+              { let flags = self.get_keyboard();
+                self.table.answer.extend_from_slice(format!("\x1B[?{}u", flags).as_bytes());
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'0', b'h', ref next..] =>
-              { self.table.mouse_handle.1 = true;
+            &[b'\x1B', b'[', prefix, ref next..] if (prefix == b'>' || prefix == b'<' || prefix == b'=')
+                                                    && self.catch_numbers(Vec::new(), next).1.first() == Some(&b'u') =>
+              { let (flags, next) = self.catch_numbers(Vec::new(), next);
+                self.keyboard(prefix, &flags);
+                self.write(&next[1..]) },
+            &[b'\x1B', b'[', b'>', ref next..] if self.catch_numbers(Vec::new(), next).1.first() == Some(&b'm') =>
+              { let (params, next) = self.catch_numbers(Vec::new(), next);
+                if params.first() == Some(&4)
+                { self.table.modify_keys = params.get(1).cloned().unwrap_or_default() as libc::c_uint; }
+                self.write(&next[1..]) },
+            &[b'\x1B', b'=', ref next..] =>
+              { self.table.keypad = true;
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'0', b'l', ref next..] =>
-              { self.table.mouse_handle.1 = false;
+            &[b'\x1B', b'>', ref next..] =>
+              { self.table.keypad = false;
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'2', b'h', ref next..] =>
-              { self.table.mouse_handle.2 = true;
+            &[b'\x1B', b'[', b'?', b'6', b'7', b'h', ref next..] =>
+              { self.table.backspace = true;
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'2', b'l', ref next..] =>
-              { self.table.mouse_handle.2 = false;
+            &[b'\x1B', b'[', b'?', b'6', b'7', b'l', ref next..] =>
+              { self.table.backspace = false;
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'6', b'h', ref next..] =>
-              { self.table.mouse_handle.3 = true;
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'4', b'h', ref next..] =>
+              { self.table.focus = true;
                 self.write(next) },
-            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'6', b'l', ref next..] =>
-              { self.table.mouse_handle.3 = false;
+            &[b'\x1B', b'[', b'?', b'1', b'0', b'0', b'4', b'l', ref next..] =>
+              { self.table.focus = false;
+                self.write(next) },
+            &[b'\x1B', b'[', b'?', b'2', b'0', b'0', b'4', b'h', ref next..] =>
+              { self.table.paste = true;
+                self.write(next) },
+            &[b'\x1B', b'[', b'?', b'2', b'0', b'0', b'4', b'l', ref next..] =>
+              { self.table.paste = false;
                 self.write(next) },
 
             //------------ SETTINGS -------------
@@ -1121,10 +1682,14 @@ impl Write for Display {
               { self.table.collection.clear();
                 self.write(next) },
 
+            //------------ OSC -------------
+            &[b'\x1B', b']', ref next..] =>
+              { self.table.osc = Some(Vec::new());
+                self.osc_continue(next) },
+
             &[b'\x1B', b'[', b'?', ref next..] |
             &[b'\x1B', b'[', b'>', ref next..] |
             &[b'\x1B', b'[', ref next..] |
-            &[b'\x1B', b']', ref next..] |
             &[b'\x1B', b'(', ref next..] |
             &[b'\x1B', b'?', ref next..] |
             &[b'\x1B', ref next..] =>
@@ -1291,6 +1856,7 @@ impl Write for Display {
 
             &[b'\x07', ref next..] =>
               { self.table.bell += 1;
+                self.table.bell_rung = true;
                 self.write(next) },
             &[b'\x0A', b'\x0D', ref next..] |
             &[b'\x0A', ref next..] |
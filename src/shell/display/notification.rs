@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// The struct `Notification` is a desktop's notification asked by
+/// the child (OSC 9 or OSC 777).
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Notification {
+    /// The title, OSC 9 hasn't one.
+    title: Option<String>,
+    body: String,
+}
+
+impl Notification {
+
+    /// The constructor method `new` returns a notification.
+    pub fn new(title: Option<String>, body: String) -> Self {
+        Notification {
+            title: title,
+            body: body,
+        }
+    }
+
+    /// The constructor method `from_osc` returns the notification of
+    /// a OSC 9 (`9;body`) or of a OSC 777 (`777;notify;title;body`),
+    /// the ConEmu's commands of OSC 9 (`9;4;...`) aren't notifications.
+    pub fn from_osc(command: &[u8]) -> Option<Self> {
+        let mut fields = command.splitn(3, |&byte| byte.eq(&b';'));
+
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(b"9"), Some(number), Some(_)) if !number.is_empty() &&
+                                                   number.iter().all(|&byte| byte >= b'0' && byte <= b'9') => None,
+            (Some(b"9"), Some(_), _) => Some(Notification::new(
+                None,
+                String::from_utf8_lossy(&command[2..]).into_owned(),
+            )),
+            (Some(b"777"), Some(b"notify"), Some(rest)) => {
+                let mut rest = rest.splitn(2, |&byte| byte.eq(&b';'));
+
+                match (rest.next(), rest.next()) {
+                    (Some(title), body) => Some(Notification::new(
+                        Some(String::from_utf8_lossy(title).into_owned()),
+                        String::from_utf8_lossy(body.unwrap_or_default()).into_owned(),
+                    )),
+                    (None, _) => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// The accessor method `get_title` returns the title.
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_ref().map(|title| title.as_str())
+    }
+
+    /// The accessor method `get_body` returns the body.
+    pub fn get_body(&self) -> &str {
+        self.body.as_str()
+    }
+}
+
+/// The trait `NotificationHandler` alerts the user of the child's bells
+/// and notifications, the shell limits the rate of the calls.
+pub trait NotificationHandler: fmt::Debug + Send {
+    /// The method `bell` is called by the child's bell.
+    fn bell(&mut self);
+
+    /// The method `notify` is called by the child's notification.
+    fn notify(&mut self, notification: &Notification);
+}
@@ -0,0 +1,132 @@
+use std::cmp;
+
+use ::libc;
+use ::regex;
+
+use super::{Display, Coordinate};
+use super::err::DisplayError;
+
+/// The enum `SearchCase` is the way a search compares the letters.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SearchCase {
+    /// The case is compared.
+    Sensitive,
+    /// The case is ignored.
+    Insensitive,
+    /// The case is ignored unless the pattern has a capital letter.
+    Smart,
+}
+
+impl Default for SearchCase {
+    fn default() -> SearchCase {
+        SearchCase::Sensitive
+    }
+}
+
+/// The enum `SearchDirection` is the way a search goes from a cell.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SearchDirection {
+    /// To the end of the screen.
+    Forward,
+    /// To the start of the screen.
+    Backward,
+}
+
+/// The struct `Search` is a literal or a regular expression which finds
+/// the ranges of cells of a `Display`, a match can continue on the
+/// soft-wrapped lines.
+
+#[derive(Clone, Debug)]
+pub struct Search {
+    regex: regex::Regex,
+}
+
+impl Search {
+
+    /// The constructor method `new` returns the search of a literal text.
+    pub fn new(text: &str, case: SearchCase) -> Result<Self, DisplayError> {
+        Search::build(&regex::escape(text), text, case)
+    }
+
+    /// The constructor method `from_regex` returns the search of
+    /// a regular expression.
+    pub fn from_regex(pattern: &str, case: SearchCase) -> Result<Self, DisplayError> {
+        Search::build(pattern, pattern, case)
+    }
+
+    /// The function `build` compiles the pattern, the smart case
+    /// looks for the capital letters of the text.
+    fn build(pattern: &str, text: &str, case: SearchCase) -> Result<Self, DisplayError> {
+        let insensitive: bool = match case {
+            SearchCase::Sensitive => false,
+            SearchCase::Insensitive => true,
+            SearchCase::Smart => !text.chars().any(|glyph| glyph.is_uppercase()),
+        };
+
+        regex::RegexBuilder::new(pattern).case_insensitive(insensitive)
+                                         .build()
+                                         .map(|regex| Search { regex: regex })
+                                         .map_err(|why| DisplayError::RegexFail(why))
+    }
+
+    /// The accessor method `get_pattern` returns the regular expression.
+    pub fn get_pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// The method `find_all` returns the first and the last cells (both
+    /// included) of the matches on the display, from the top.
+    pub fn find_all(&self, display: &Display) -> Vec<(Coordinate, Coordinate)> {
+        let col: libc::size_t = display.get_window_size().get_col();
+        let row: libc::size_t = display.get_window_size().get_row();
+        let mut matches: Vec<(Coordinate, Coordinate)> = Vec::new();
+        let mut y: libc::size_t = 0;
+
+        if col.eq(&0) {
+            return matches;
+        }
+        while y < row {
+            // The text of a line and of its soft-wrapped continuations,
+            // with the cell of each glyph.
+            let mut text: String = String::new();
+            let mut cells: Vec<(libc::size_t, Coordinate)> = Vec::new();
+
+            loop {
+                if let Some(line) = display.get_line(y) {
+                    line.iter().enumerate().all(|(x, character)| {
+                        cells.push((text.len(), Coordinate::from((x, y))));
+                        text.push(character.get_glyph());
+                        true
+                    });
+                }
+                y += 1;
+                if !display.is_wrapped(y - 1) || y >= row {
+                    break ;
+                }
+            }
+            let len: libc::size_t = text.trim_right().len();
+
+            self.regex.find_iter(&text[..len]).filter(|found| found.start() < found.end()).all(|found| {
+                let first: libc::size_t = cells.iter().rposition(|&(offset, _)| offset <= found.start()).unwrap_or_default();
+                let last: libc::size_t = cells.iter().rposition(|&(offset, _)| offset < found.end()).unwrap_or_default();
+
+                matches.push((cells[first].1, cells[cmp::max(first, last)].1));
+                true
+            });
+        }
+        matches
+    }
+
+    /// The method `find` returns the first match after a cell, or the
+    /// last match before it.
+    pub fn find(&self, display: &Display, from: &Coordinate, direction: SearchDirection) -> Option<(Coordinate, Coordinate)> {
+        let matches: Vec<(Coordinate, Coordinate)> = self.find_all(display);
+
+        match direction {
+            SearchDirection::Forward => matches.into_iter().find(|&(start, _)| (start.y, start.x) > (from.y, from.x)),
+            SearchDirection::Backward => matches.into_iter().rev().find(|&(start, _)| (start.y, start.x) < (from.y, from.x)),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::cmp;
+
+use ::libc;
+
+use super::{Display, Coordinate};
+use super::character::Character;
+use ::shell::state::Gesture;
+
+/// The default separators of words, added to the spaces.
+pub const DEFAULT_SEPARATORS: &'static str = " \t,;:!?'\"`()[]{}<>|&=";
+
+/// The enum `SelectionMode` is the way the cells between the anchor
+/// and the cursor of a `Selection` are chosen.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// The cells from the anchor to the cursor, line after line.
+    Linear,
+    /// The rectangle between the anchor and the cursor.
+    Block,
+    /// As linear, extended to the whole words.
+    Word,
+    /// As linear, extended to the whole lines.
+    Line,
+}
+
+impl Default for SelectionMode {
+    fn default() -> SelectionMode {
+        SelectionMode::Linear
+    }
+}
+
+/// The struct `Selection` is a range of cells of a `Display`, from an
+/// anchor to a cursor counted from 0, which extracts its text.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection {
+    mode: SelectionMode,
+    anchor: Coordinate,
+    cursor: Coordinate,
+    /// The glyphs which end a word.
+    separators: String,
+}
+
+impl Selection {
+
+    /// The constructor method `new` returns a selection of a cell.
+    pub fn new(mode: SelectionMode, at: Coordinate) -> Self {
+        Selection {
+            mode: mode,
+            anchor: at,
+            cursor: at,
+            separators: DEFAULT_SEPARATORS.to_string(),
+        }
+    }
+
+    /// The constructor method `from_gesture` returns a selection started
+    /// by a click of the left button (linear, word or line by the number
+    /// of clicks) or by a drag.
+    pub fn from_gesture(gesture: Gesture) -> Option<Self> {
+        match gesture {
+            Gesture::Click(0, count, [x, y]) => {
+                let mode: SelectionMode = match count {
+                    2 => SelectionMode::Word,
+                    3 => SelectionMode::Line,
+                    _ => SelectionMode::Linear,
+                };
+
+                Some(Selection::new(mode, Selection::cell(x, y)))
+            },
+            Gesture::DragStart(0, [x, y], to) => {
+                let mut selection: Selection = Selection::new(SelectionMode::Linear, Selection::cell(x, y));
+
+                selection.extend(Selection::cell(to[0], to[1]));
+                Some(selection)
+            },
+            _ => None,
+        }
+    }
+
+    /// The function `cell` returns the cell of a gesture's coordinates.
+    fn cell(x: libc::c_ushort, y: libc::c_ushort) -> Coordinate {
+        Coordinate::from((x.saturating_sub(1) as libc::size_t, y.saturating_sub(1) as libc::size_t))
+    }
+
+    /// The accessor method `get_mode` returns the mode.
+    pub fn get_mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// The accessor method `get_anchor` returns the cell where the selection starts.
+    pub fn get_anchor(&self) -> &Coordinate {
+        &self.anchor
+    }
+
+    /// The accessor method `get_cursor` returns the cell where the selection ends.
+    pub fn get_cursor(&self) -> &Coordinate {
+        &self.cursor
+    }
+
+    /// The accessor method `get_separators` returns the glyphs which end a word.
+    pub fn get_separators(&self) -> &str {
+        self.separators.as_str()
+    }
+
+    /// The mutator method `set_mode` changes the mode.
+    pub fn set_mode(&mut self, mode: SelectionMode) {
+        self.mode = mode;
+    }
+
+    /// The mutator method `set_separators` changes the glyphs which end a word.
+    pub fn set_separators(&mut self, separators: &str) {
+        self.separators = separators.to_string();
+    }
+
+    /// The mutator method `extend` moves the cursor.
+    pub fn extend(&mut self, to: Coordinate) {
+        self.cursor = to;
+    }
+
+    /// The mutator method `extend_with` moves the cursor with a drag,
+    /// it returns false when the gesture isn't a drag of the left button.
+    pub fn extend_with(&mut self, gesture: Gesture) -> bool {
+        match gesture {
+            Gesture::DragStart(0, _, [x, y]) |
+            Gesture::DragUpdate(0, _, [x, y]) |
+            Gesture::DragEnd(0, _, [x, y]) => {
+                self.extend(Selection::cell(x, y));
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// The method `is_separator` returns true when the glyph ends a word.
+    fn is_separator(&self, glyph: char) -> bool {
+        glyph.is_whitespace() || self.separators.contains(glyph)
+    }
+
+    /// The method `get_range` returns the first and the last cells (both
+    /// included) of the selection on the display, the words and the lines
+    /// follow the soft-wrapped lines.
+    pub fn get_range(&self, display: &Display) -> (Coordinate, Coordinate) {
+        let col: libc::size_t = cmp::max(display.get_window_size().get_col(), 1);
+        let (start, end): (Coordinate, Coordinate) = match self.mode {
+            SelectionMode::Block => {
+                return (
+                    Coordinate::from((cmp::min(self.anchor.x, self.cursor.x), cmp::min(self.anchor.y, self.cursor.y))),
+                    Coordinate::from((cmp::max(self.anchor.x, self.cursor.x), cmp::max(self.anchor.y, self.cursor.y))),
+                );
+            },
+            _ if (self.anchor.y, self.anchor.x) <= (self.cursor.y, self.cursor.x) => (self.anchor, self.cursor),
+            _ => (self.cursor, self.anchor),
+        };
+        let (mut first, mut last): (libc::size_t, libc::size_t) = (start.y * col + start.x, end.y * col + end.x);
+        // The move from a cell to the previous one is possible on the line
+        // or at the start of a line which continues the previous line.
+        let previous = |index: libc::size_t| -> bool {
+            index.checked_sub(1).map(|before| index % col != 0 || display.is_wrapped(before / col)).unwrap_or_default()
+        };
+        let next = |index: libc::size_t| -> bool {
+            (index + 1) % col != 0 || (display.is_wrapped(index / col) && (index + 1) / col < display.get_window_size().get_row())
+        };
+
+        match self.mode {
+            SelectionMode::Word => {
+                let glyph = |index: libc::size_t| -> char {
+                    display.get_line(index / col).and_then(|line| line.get(index % col)).map(|character| character.get_glyph()).unwrap_or(' ')
+                };
+
+                if !self.is_separator(glyph(first)) {
+                    while previous(first) && !self.is_separator(glyph(first - 1)) {
+                        first -= 1;
+                    }
+                }
+                if !self.is_separator(glyph(last)) {
+                    while next(last) && !self.is_separator(glyph(last + 1)) {
+                        last += 1;
+                    }
+                }
+            },
+            SelectionMode::Line => {
+                first -= first % col;
+                while previous(first) {
+                    first -= col;
+                }
+                last += col - 1 - last % col;
+                while next(last) {
+                    last += col;
+                }
+            },
+            _ => {},
+        }
+        (Coordinate::from((first % col, first / col)), Coordinate::from((last % col, last / col)))
+    }
+
+    /// The method `contains` returns true when the cell is selected.
+    pub fn contains(&self, display: &Display, at: &Coordinate) -> bool {
+        let (start, end): (Coordinate, Coordinate) = self.get_range(display);
+
+        if self.mode.eq(&SelectionMode::Block) {
+            at.x >= start.x && at.x <= end.x && at.y >= start.y && at.y <= end.y
+        } else {
+            (at.y, at.x) >= (start.y, start.x) && (at.y, at.x) <= (end.y, end.x)
+        }
+    }
+
+    /// The method `get_text` returns the selected text, a soft-wrapped line
+    /// is joined to the next one and the trailing spaces of the lines
+    /// are removed.
+    pub fn get_text(&self, display: &Display) -> String {
+        let col: libc::size_t = display.get_window_size().get_col();
+        let (start, end): (Coordinate, Coordinate) = self.get_range(display);
+        let mut text: String = String::new();
+
+        if col.eq(&0) {
+            return text;
+        }
+        (start.y..end.y + 1).all(|y| {
+            let (from, to): (libc::size_t, libc::size_t) = match self.mode {
+                SelectionMode::Block => (start.x, end.x),
+                _ => (
+                    if y.eq(&start.y) { start.x } else { 0 },
+                    if y.eq(&end.y) { end.x } else { col - 1 },
+                ),
+            };
+            let glyphs: String = display.get_line(y).map(|line: &[Character]| {
+                line.iter()
+                    .skip(from)
+                    .take((to + 1).saturating_sub(from))
+                    .map(|character| character.get_glyph())
+                    .collect::<String>()
+            }).unwrap_or_default();
+
+            if self.mode.ne(&SelectionMode::Block) && to.eq(&(col - 1)) && display.is_wrapped(y) && y.lt(&end.y) {
+                text.push_str(&glyphs);
+            } else {
+                text.push_str(glyphs.trim_right());
+                if y.lt(&end.y) {
+                    text.push('\n');
+                }
+            }
+            true
+        });
+        text
+    }
+}
@@ -3,14 +3,21 @@ use std::error::Error;
 use std::fmt;
 
 use super::termios::TermiosError;
+#[cfg(feature = "task")]
+use super::device::task::ProcError;
 
 /// The enum `ShellError` defines the possible errors from constructor Shell.
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Debug)]
 pub enum ShellError {
     ForkFail(ForkError),
     TermiosFail(TermiosError),
+    /// The limits of the session can't be enforced.
+    #[cfg(feature = "task")]
+    ProcFail(ProcError),
     NotFound,
+    /// The shell doesn't own the host's terminal.
+    NotHost,
 }
 
 
@@ -29,8 +36,11 @@ impl Error for ShellError {
         match *self {
             ShellError::ForkFail(_) => "The pseudo tty has occured an error.",
             ShellError::TermiosFail(_) => "The termios has occured an error.",
+            #[cfg(feature = "task")]
+            ShellError::ProcFail(_) => "The limits of the session can't be enforced.",
             ShellError::NotFound => "The $SHELL variable of environement \
                                      was empty during the compile time",
+            ShellError::NotHost => "The shell doesn't own the host's terminal.",
         }
     }
 
@@ -39,6 +49,8 @@ impl Error for ShellError {
         match *self {
             ShellError::ForkFail(ref err) => Some(err),
             ShellError::TermiosFail(ref err) => Some(err),
+            #[cfg(feature = "task")]
+            ShellError::ProcFail(ref err) => Some(err),
             _ => None,
         }
     }
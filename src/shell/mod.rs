@@ -4,35 +4,60 @@ pub mod state;
 pub mod termios;
 mod err;
 
-use std::os::unix::io::AsRawFd;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::io::{self, Write};
 use std::mem;
 use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use ::libc;
 use ::child::exec;
 use ::pty::prelude as pty;
 
-use self::device::Device;
+use self::device::{Device, DeviceState, In, Out};
+use self::device::control::Key;
+#[cfg(feature = "task")]
+use self::device::task::{Proc, ProcError, ProcessTree, Usage, Limits};
+#[cfg(all(feature = "task", any(target_os = "linux", target_os = "android")))]
+use self::device::task::Cgroup;
 use self::termios::Termios;
 pub use self::state::ShellState;
 pub use self::err::ShellError;
-use self::display::Display;
+use self::display::{Display, ClipboardPolicy, ClipboardProvider, Notification, NotificationHandler};
 pub use self::display::winsz::Winszed;
 
 pub use super::parent::Parent;
 
+/// The default interval between two alerts of the user in milliseconds.
+const DEFAULT_NOTIFICATION: u64 = 1000;
+
 /// The struct `Shell` is the speudo terminal interface.
 
 #[derive(Debug)]
 pub struct Shell {
     pid: libc::pid_t,
     #[allow(dead_code)]
-    config: Termios,
+    config: Option<Termios>,
     speudo: pty::Master,
-    device: Device,
+    device: Option<Device>,
     state: ShellState,
     screen: Display,
+    /// The host's clipboard which answers the child's queries.
+    clipboard: Option<Box<ClipboardProvider>>,
+    /// The handler which alerts the user of the bells and notifications.
+    notifier: Option<Box<NotificationHandler>>,
+    /// The minimal interval between two bells or two notifications.
+    notification_interval: Duration,
+    /// The time of the last notification.
+    notified: Option<Instant>,
+    /// The latest notification held back by the interval.
+    notification_pending: Option<Notification>,
+    /// The time of the last bell.
+    rung: Option<Instant>,
+    /// The cgroup which bounds the session.
+    #[cfg(all(feature = "task", any(target_os = "linux", target_os = "android")))]
+    cgroup: Option<Cgroup>,
 }
 
 impl Shell {
@@ -44,6 +69,87 @@ impl Shell {
       interval: Option<i64>,
       command: Option<&str>,
       windows: Option<Winszed>,
+  ) -> Result<Self, ShellError> {
+      Shell::from_fork(repeat, interval, command, windows, false, &[], None)
+  }
+
+  /// The constructor method `new_evented` returns a shell interface without
+  /// device's thread nor terminal configuration: the master is non-blocking
+  /// and must be polled (see `Reactor`) then read with `next_evented`.
+  pub fn new_evented (
+      repeat: Option<i64>,
+      interval: Option<i64>,
+      command: Option<&str>,
+      windows: Option<Winszed>,
+  ) -> Result<Self, ShellError> {
+      Shell::from_fork(repeat, interval, command, windows, true, &[], None)
+  }
+
+  /// The constructor method `new_limited` returns a shell interface whose
+  /// session is limited: the child's rlimit of time is set at the spawn, the
+  /// sum of the memory and of the processes is bounded by a cgroup v2's
+  /// subtree (see `Cgroup::new`, the current cgroup must be delegated) which
+  /// the child joins before its exec, and `ShellState::is_task_limit` reports
+  /// a hit limit. The shell isn't returned when the limits of the memory or
+  /// of the processes can't be enforced.
+  #[cfg(feature = "task")]
+  pub fn new_limited (
+      repeat: Option<i64>,
+      interval: Option<i64>,
+      command: Option<&str>,
+      windows: Option<Winszed>,
+      limits: Limits,
+  ) -> Result<Self, ShellError> {
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      {
+          if limits.get_memory().is_some() || limits.get_processes().is_some() {
+              // The child waits on the pipe until it's in the cgroup.
+              let mut gate: [libc::c_int; 2] = [0; 2];
+
+              if unsafe { libc::pipe(gate.as_mut_ptr()) } == -1 {
+                  return Err(ShellError::ProcFail(ProcError::Cgroup(io::Error::last_os_error())));
+              }
+              let shell: Result<Shell, ShellError> = Shell::from_fork(repeat, interval, command, windows, false, &limits.as_rlimits(), Some(gate)).and_then(|mut shell| {
+                  let pid: libc::pid_t = shell.pid;
+
+                  shell.state.set_limits(limits);
+                  shell.cgroup = Some(try!(Cgroup::new(&format!("pty-proc-{}", pid), &limits).and_then(|cgroup| {
+                      cgroup.add(pid).map(|_| cgroup)
+                  }).map_err(|why| ShellError::ProcFail(why))));
+                  unsafe {
+                      libc::write(gate[1], b"\0".as_ptr() as *const libc::c_void, 1);
+                  }
+                  Ok(shell)
+              });
+
+              // The child exits without exec when the pipe is closed empty.
+              unsafe {
+                  libc::close(gate[0]);
+                  libc::close(gate[1]);
+              }
+              return shell;
+          }
+      }
+      #[cfg(not(any(target_os = "linux", target_os = "android")))]
+      {
+          if limits.get_processes().is_some() {
+              return Err(ShellError::ProcFail(ProcError::NotFound));
+          }
+      }
+      let mut shell: Shell = try!(Shell::from_fork(repeat, interval, command, windows, false, &limits.as_rlimits(), None));
+
+      shell.state.set_limits(limits);
+      Ok(shell)
+  }
+
+  fn from_fork (
+      repeat: Option<i64>,
+      interval: Option<i64>,
+      command: Option<&str>,
+      windows: Option<Winszed>,
+      evented: bool,
+      rlimits: &[(libc::c_int, libc::rlim_t)],
+      gate: Option<[libc::c_int; 2]>,
   ) -> Result<Self, ShellError> {
       unsafe {
             let winsz: Winszed =
@@ -58,23 +164,281 @@ impl Shell {
                 Ok(fork) => match fork {
                     pty::Fork::Child(_) => {
                         libc::ioctl(libc::STDIN_FILENO, libc::TIOCSWINSZ, &winsz);
+                        rlimits.iter().all(|&(resource, value)| {
+                            libc::setrlimit(resource as _, &libc::rlimit {
+                                rlim_cur: value,
+                                rlim_max: value,
+                            });
+                            true
+                        });
+                        // The child waits a byte of the parent before its exec.
+                        if let Some(gate) = gate {
+                            let mut byte: libc::c_uchar = 0;
+                            let mut len: libc::ssize_t;
+
+                            libc::close(gate[1]);
+                            loop {
+                                len = libc::read(gate[0], &mut byte as *mut libc::c_uchar as *mut libc::c_void, 1);
+                                if len != -1 || ::errno::errno().0 != libc::EINTR {
+                                    break ;
+                                }
+                            }
+                            libc::close(gate[0]);
+                            if len != 1 {
+                                libc::_exit(1);
+                            }
+                        }
                         exec(command.unwrap_or("/bin/bash"))
                     },
                     pty::Fork::Parent(pid, master) => {
                         mem::forget(fork);
+                        if evented {
+                            let flags: libc::c_int = libc::fcntl(master.as_raw_fd(), libc::F_GETFL);
+                            libc::fcntl(master.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK);
+                        }
                         Ok(Shell {
                             pid: pid,
-                            config: Termios::default(),
+                            config: if evented {
+                                None
+                            } else {
+                                Some(Termios::default())
+                            },
                             speudo: master,
-                            device: Device::from_speudo(master, libc::getpid()),
+                            device: if evented {
+                                None
+                            } else {
+                                Some(Device::from_speudo(master, pid))
+                            },
                             state: ShellState::new(repeat, interval),
                             screen: Display::from_winszed(winsz),
+                            clipboard: None,
+                            notifier: None,
+                            notification_interval: Duration::from_millis(DEFAULT_NOTIFICATION),
+                            notified: None,
+                            notification_pending: None,
+                            rung: None,
+                            #[cfg(all(feature = "task", any(target_os = "linux", target_os = "android")))]
+                            cgroup: None,
                         })
                     },
                 }
             }
         }
     }
+
+    /// The method `next_evented` reads the available output of an evented
+    /// shell and returns the new state, `Ok(None)` when the child has hung up
+    /// and an error of kind `WouldBlock` when there is nothing to read.
+    pub fn next_evented(&mut self) -> io::Result<Option<ShellState>> {
+        let mut bytes: Out = Out::default();
+
+        unsafe {
+            match libc::read(self.speudo.as_raw_fd(),
+                             bytes.as_mut_ptr() as *mut libc::c_void,
+                             bytes.len()) {
+                -1 => {
+                    let why: io::Error = io::Error::last_os_error();
+
+                    // The master returns EIO when the slave side is closed.
+                    if why.raw_os_error().eq(&Some(libc::EIO)) {
+                        Ok(None)
+                    } else {
+                        Err(why)
+                    }
+                },
+                0 => Ok(None),
+                len => Ok(Some(Parent::next(self, DeviceState::from_out(bytes, len as libc::size_t)))),
+            }
+        }
+    }
+
+    /// The method `next_input` updates the state from an input read
+    /// by the caller (the shell doesn't own the standard input when evented).
+    pub fn next_input(&mut self, buf: &[u8]) -> ShellState {
+        let input: In = In::from(buf);
+        let len: libc::size_t = buf.len().min(input.len());
+
+        Parent::next(self, DeviceState::from_in(input, len))
+    }
+
+    /// The accessor method `is_evented` returns true when the shell
+    /// doesn't have device's thread.
+    pub fn is_evented(&self) -> bool {
+        self.device.is_none()
+    }
+
+    /// The mutator method `set_keyboard_flags` asks the host's terminal to
+    /// report the keys with the kitty's keyboard protocol, the flags are
+    /// popped when the shell is dropped (the shell must own the terminal).
+    pub fn set_keyboard_flags(&mut self, flags: libc::c_uint) -> Result<(), ShellError> {
+        match self.config.as_mut() {
+            Some(config) => config.push_keyboard(flags).map_err(|why| ShellError::TermiosFail(why)),
+            None => Err(ShellError::NotHost),
+        }
+    }
+
+    /// The method `send_key` writes to the child the sequence of a key
+    /// according to the child's modes, as a terminal would.
+    pub fn send_key(&mut self, key: Key) -> io::Result<()> {
+        let bytes: Vec<u8> = key.encode(&self.screen.get_key_modes());
+
+        self.speudo.write_all(&bytes)
+    }
+
+    /// The method `paste` writes to the child a text bracketed when
+    /// the child has enabled the bracketed paste.
+    pub fn paste(&mut self, text: &[u8]) -> io::Result<()> {
+        let bytes: Vec<u8> = self.screen.get_key_modes().paste(text);
+
+        self.speudo.write_all(&bytes)
+    }
+
+    /// The method `send_focus` writes to the child a focus' change
+    /// when the child listens them.
+    pub fn send_focus(&mut self, focus: bool) -> io::Result<()> {
+        if self.screen.get_focus_report() {
+            self.speudo.write_all(if focus { b"\x1B[I" } else { b"\x1B[O" })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The mutator method `set_click` changes the time limit between
+    /// the clicks of a double or triple click.
+    pub fn set_click(&mut self, click: libc::c_long) {
+        self.state.set_click(click);
+    }
+
+    /// The mutator method `set_clipboard_policy` changes what the child
+    /// may do with the clipboard: nothing by default, the queries are
+    /// ignored unless `ClipboardPolicy::ReadWrite`.
+    pub fn set_clipboard_policy(&mut self, policy: ClipboardPolicy) {
+        self.screen.set_clipboard_policy(policy);
+    }
+
+    /// The mutator method `set_clipboard_provider` gives the host's clipboard
+    /// which answers the child's queries.
+    pub fn set_clipboard_provider(&mut self, provider: Option<Box<ClipboardProvider>>) {
+        self.clipboard = provider;
+    }
+
+    /// The mutator method `set_notification_handler` gives the handler
+    /// which alerts the user of the child's bells and notifications.
+    pub fn set_notification_handler(&mut self, handler: Option<Box<NotificationHandler>>) {
+        self.notifier = handler;
+    }
+
+    /// The mutator method `set_notification_interval` changes the minimal
+    /// interval between two bells or two notifications: the bells in between
+    /// are dropped and the latest notification is given once it has passed.
+    pub fn set_notification_interval(&mut self, interval: Duration) {
+        self.notification_interval = interval;
+    }
+
+    /// The method `get_cwd` returns the working directory given by the
+    /// child (OSC 7), or else the one of the pty's foreground.
+    pub fn get_cwd(&self) -> Option<PathBuf> {
+        self.screen.get_cwd().map(|cwd| cwd.to_path_buf()).or_else(|| self.get_foreground_cwd())
+    }
+
+    /// The method `get_foreground_cwd` returns the working directory
+    /// of the pty's foreground.
+    #[cfg(feature = "task")]
+    fn get_foreground_cwd(&self) -> Option<PathBuf> {
+        Proc::from_master(self.pid, self.speudo.as_raw_fd()).get_foreground_cwd()
+    }
+
+    /// The method `get_foreground_cwd` returns nothing without the task's feature.
+    #[cfg(not(feature = "task"))]
+    fn get_foreground_cwd(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// The method `process_tree` returns the tree of the child with its
+    /// descendants, the processes of the pty's foreground are marked.
+    #[cfg(feature = "task")]
+    pub fn process_tree(&self) -> Result<ProcessTree, ProcError> {
+        let mut task: Proc = try!(Proc::new(self.pid));
+
+        task.master = Some(self.speudo.as_raw_fd());
+        task.get_tree(self.pid).ok_or(ProcError::NotFound)
+    }
+
+    /// The method `usage` returns the resources used by the child
+    /// and its descendants.
+    #[cfg(feature = "task")]
+    pub fn usage(&self) -> Result<Usage, ProcError> {
+        self.process_tree().map(|tree| {
+            let mut pids: Vec<libc::pid_t> = vec![tree.get_pid()];
+
+            pids.extend(tree.get_descendants().iter().map(|process| process.get_pid()));
+            Usage::from_pids(&pids)
+        })
+    }
+
+    /// The accessor method `get_cgroup` returns the cgroup which bounds
+    /// the session, if the limits have been enforced by a cgroup.
+    #[cfg(all(feature = "task", any(target_os = "linux", target_os = "android")))]
+    pub fn get_cgroup(&self) -> Option<&Cgroup> {
+        self.cgroup.as_ref()
+    }
+
+    /// The method `answer` writes to the child the answers of the display
+    /// and of the clipboard.
+    fn answer(&mut self) {
+        let mut answer: Vec<u8> = self.screen.pop_answer();
+
+        if let (Some(query), Some(provider)) = (self.state.is_output_clipboard(), self.clipboard.as_mut()) {
+            if query.is_query() {
+                if let Some(content) = query.get_targets().first().and_then(|&target| provider.get(target)) {
+                    answer.extend(query.answer(&content));
+                }
+            }
+        }
+        if !answer.is_empty() {
+            let _ = self.speudo.write_all(&answer);
+        }
+        self.notify();
+    }
+
+    /// The method `notify` gives the new bells and notifications to the
+    /// handler, at most one bell and one notification by interval; the
+    /// latest notification held back is given at the first update after it.
+    fn notify(&mut self) {
+        if let Some(handler) = self.notifier.as_mut() {
+            let count: libc::size_t = self.state.is_output_notification().unwrap_or_default();
+            let notifications = self.screen.get_notifications();
+            let interval: Duration = self.notification_interval;
+            let allow = |last: &mut Option<Instant>| -> bool {
+                if last.map(|last| last.elapsed() >= interval).unwrap_or(true) {
+                    *last = Some(Instant::now());
+                    true
+                } else {
+                    false
+                }
+            };
+
+            if self.notification_pending.is_some() && allow(&mut self.notified) {
+                if let Some(notification) = self.notification_pending.take() {
+                    handler.notify(&notification);
+                }
+            }
+            let notified: &mut Option<Instant> = &mut self.notified;
+            let pending: &mut Option<Notification> = &mut self.notification_pending;
+
+            notifications[notifications.len().saturating_sub(count)..].iter().all(|notification| {
+                if allow(notified) {
+                    handler.notify(notification);
+                } else {
+                    *pending = Some(notification.clone());
+                }
+                true
+            });
+            if self.state.is_output_bell().is_some() && allow(&mut self.rung) {
+                handler.bell();
+            }
+        }
+    }
 }
 
 impl Parent for Shell {
@@ -130,7 +494,10 @@ impl Parent for Shell {
             #[cfg(feature = "auto-resize")]
             () => {
                 self.state.update_from(&mut self.screen, event);
-                if let Some(size) = self.state.is_resized() {
+                self.answer();
+                // The evented shells are sized by their owner (a pane
+                // can be smaller than the host's terminal).
+                if let (Some(size), false) = (self.state.is_resized(), self.is_evented()) {
                     self.set_window_size_with(&size);
                 }
                 self.state
@@ -138,6 +505,7 @@ impl Parent for Shell {
             #[cfg(not(feature = "auto-resize"))]
             () => {
                 self.state.update_from(&mut self.screen, event);
+                self.answer();
                 self.state
             },
         }
@@ -148,11 +516,12 @@ impl Iterator for Shell {
     type Item = ShellState;
 
     fn next(&mut self) -> Option<ShellState> {
-        match self.device.next() {
+        match self.device.as_mut().and_then(|device| device.next()) {
             None => None,
             #[cfg(feature = "auto-resize")]
             Some(event) => {
                 self.state.update_from(&mut self.screen, event);
+                self.answer();
                 if let Some(size) = self.state.is_resized() {
                     self.set_window_size_with(&size);
                 }
@@ -161,6 +530,7 @@ impl Iterator for Shell {
             #[cfg(not(feature = "auto-resize"))]
             Some(event) => {
                 self.state.update_from(&mut self.screen, event);
+                self.answer();
                 Some(self.state)
             },
         }
@@ -177,6 +547,13 @@ impl Write for Shell {
     }
 }
 
+impl AsRawFd for Shell {
+    /// The accessor function `as_raw_fd` returns the master's descriptor.
+    fn as_raw_fd(&self) -> RawFd {
+        self.speudo.as_raw_fd()
+    }
+}
+
 impl fmt::Display for Shell {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.screen)
@@ -199,11 +576,19 @@ impl Default for Shell {
 
             Shell {
                 pid: 0,
-                config: mem::zeroed(),
+                config: None,
                 speudo: master,
-                device: Device::from_speudo(master, 0),
+                device: Some(Device::from_speudo(master, 0)),
                 state: ShellState::default(),
                 screen: Display::default(),
+                clipboard: None,
+                notifier: None,
+                notification_interval: Duration::from_millis(DEFAULT_NOTIFICATION),
+                notified: None,
+                notification_pending: None,
+                rung: None,
+                #[cfg(all(feature = "task", any(target_os = "linux", target_os = "android")))]
+                cgroup: None,
             }
         }
     }
@@ -0,0 +1,86 @@
+use std::ops::{Add, BitAnd};
+
+use ::libc;
+use ::time;
+
+use super::Mouse;
+
+/// The enum `Gesture` is a mouse's action recognized from the presses,
+/// the releases and the motions; the cells are counted from 1.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gesture {
+    /// A button is pressed: the button (0 left, 1 wheel, 2 right),
+    /// the number of clicks (1 to 3) and the cell.
+    Click(u8, libc::c_ulong, [libc::c_ushort; 2]),
+    /// The pointer starts to move with a held button: the button,
+    /// the cell of the press and the current cell.
+    DragStart(u8, [libc::c_ushort; 2], [libc::c_ushort; 2]),
+    /// The pointer moves with a held button.
+    DragUpdate(u8, [libc::c_ushort; 2], [libc::c_ushort; 2]),
+    /// The held button is released after a drag.
+    DragEnd(u8, [libc::c_ushort; 2], [libc::c_ushort; 2]),
+    /// The wheel is scrolled: the delta (negative up) and the cell.
+    Wheel(libc::c_int, [libc::c_ushort; 2]),
+}
+
+/// The struct `Tracker` remembers the last press and the last click
+/// to recognize the gestures.
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tracker {
+    /// The held button with the cell of its press.
+    press: Option<(u8, [libc::c_ushort; 2])>,
+    /// The last click with its cell, its time and its number.
+    click: Option<(u8, [libc::c_ushort; 2], time::Tm, libc::c_ulong)>,
+    /// The held button has moved.
+    drag: bool,
+}
+
+impl Tracker {
+
+    /// The method `update` returns the gesture of a Mouse event, the clicks
+    /// are counted when they are at the same cell before the time limit.
+    pub fn update(&mut self, mouse: Mouse, now: time::Tm, limit: libc::c_long) -> Option<Gesture> {
+        let cell: [libc::c_ushort; 2] = [mouse.x, mouse.y];
+
+        if mouse.code.is_wheel() {
+            Some(Gesture::Wheel(if mouse.code.get_button() == 0 { -1 } else { 1 }, cell))
+        } else if mouse.code.is_motion() {
+            match self.press {
+                Some((button, from)) if !mouse.code.is_moved() => {
+                    if self.drag {
+                        Some(Gesture::DragUpdate(button, from, cell))
+                    } else if from.ne(&cell) {
+                        self.drag = true;
+                        Some(Gesture::DragStart(button, from, cell))
+                    } else {
+                        None
+                    }
+                },
+                _ => None,
+            }
+        } else if mouse.pressed {
+            let button: u8 = mouse.code.get_button();
+            let count: libc::c_ulong = match self.click {
+                Some((before, at, time, count)) if before.eq(&button).bitand(at.eq(&cell)).bitand(
+                    time.add(time::Duration::milliseconds(limit)) >= now
+                ) => count % 3 + 1,
+                _ => 1,
+            };
+
+            self.press = Some((button, cell));
+            self.click = Some((button, cell, now, count));
+            self.drag = false;
+            Some(Gesture::Click(button, count, cell))
+        } else {
+            let drag: bool = self.drag;
+
+            self.drag = false;
+            match self.press.take() {
+                Some((button, from)) if drag => Some(Gesture::DragEnd(button, from, cell)),
+                _ => None,
+            }
+        }
+    }
+}
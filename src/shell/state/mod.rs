@@ -1,6 +1,8 @@
 pub const DEFAULT_REPEAT: libc::c_long = 1_000i64;
 pub const DEFAULT_INTERVAL: libc::c_long = 1_000i64;
+pub const DEFAULT_CLICK: libc::c_long = 300i64;
 mod buf;
+mod gesture;
 
 use std::fmt;
 use std::mem;
@@ -13,28 +15,21 @@ use ::time;
 
 pub use super::device::In;
 
-use super::display::Display;
+use super::display::{Display, Clipboard};
 use super::device::control::Control;
 
 pub use super::display::winsz::Winszed;
 #[cfg(feature = "task")]
 pub use super::device::BufProc;
+#[cfg(feature = "task")]
+pub use super::device::task::{ProcEvent, Usage, Limit, Limits};
 pub use super::device::{Out, DeviceState};
-pub use super::device::control::operate::key::Key;
+pub use super::device::control::operate::key::{Key, PASTE_START, PASTE_END};
 pub use super::device::control::operate::mouse::Mouse;
 
 use self::buf::Buf;
-
-fn catch_numbers<'a>(mut acc: Vec<libc::size_t>, buf: &'a [u8]) -> (Vec<libc::size_t>, &'a [u8])
-{ match parse_number!(buf)
-  { Some((number, &[b';', ref next..])) =>
-      { acc.push(number);
-        catch_numbers(acc, next) },
-    Some((number, &[ref next..])) =>
-      { acc.push(number);
-        (acc, next) },
-    _ =>
-      { (acc, buf) }, }}
+use self::gesture::Tracker;
+pub use self::gesture::Gesture;
 
 #[derive(Default, Copy, Clone)]
 pub struct ShellState {
@@ -43,6 +38,8 @@ pub struct ShellState {
     size: Option<Winszed>,
     /// The time limit required for a repetition.
     interval: libc::c_long,
+    /// The time limit between the clicks of a double or triple click.
+    click: libc::c_long,
     /// Update.
     idle: Option<()>,
     /// The pressed character.
@@ -53,9 +50,37 @@ pub struct ShellState {
     in_repeat: Option<libc::c_ulong>,
     /// The segment intervals.
     in_interval: Option<time::Tm>,
+    /// The host is pasting a text.
+    in_paste: bool,
+    /// The beginning of the paste's end, cut between two reads.
+    in_paste_tail: ([libc::c_uchar; 5], libc::size_t),
+    /// The focus reported by the host.
+    in_focus: Option<bool>,
+    /// The recognized mouse's gesture.
+    in_gesture: Option<Gesture>,
+    /// The mouse's presses and clicks.
+    gesture: Tracker,
     /// The output of last text //printed.
     out_last: Option<(Out, libc::size_t)>,
+    /// The child's request of clipboard.
+    out_clipboard: Option<Clipboard>,
+    /// The working directory has changed.
+    out_cwd: bool,
+    /// The child's bell.
+    out_bell: Option<()>,
+    /// The number of child's notifications.
+    out_notification: Option<libc::size_t>,
     #[cfg(feature = "task")] task: Option<BufProc>,
+    /// The fork, the exec or the exit of a descendant.
+    #[cfg(feature = "task")] task_event: Option<ProcEvent>,
+    /// The periodic usage of the session.
+    #[cfg(feature = "task")] task_usage: Option<Usage>,
+    /// The limit hit by the last usage.
+    #[cfg(feature = "task")] task_limit: Option<Limit>,
+    /// The limits of the session.
+    #[cfg(feature = "task")] limits: Limits,
+    /// The limit hit by the usages since the last change.
+    #[cfg(feature = "task")] limit: Option<Limit>,
     /// The tmp buffer
     buffer: Buf,
 }
@@ -72,26 +97,51 @@ impl ShellState {
             () => ShellState {
                 repeat: repeat.unwrap_or(DEFAULT_REPEAT),
                 interval: interval.unwrap_or(DEFAULT_INTERVAL),
+                click: DEFAULT_CLICK,
                 idle: None,
                 in_down: None,
                 in_up: None,
                 in_repeat: None,
                 in_interval: None,
+                in_paste: false,
+                in_paste_tail: ([0; 5], 0),
+                in_focus: None,
+                in_gesture: None,
+                gesture: Tracker::default(),
                 out_last: None,
+                out_clipboard: None,
+                out_cwd: false,
+                out_bell: None,
+                out_notification: None,
                 buffer: Buf([0; 100], 0),
                 task: None,
+                task_event: None,
+                task_usage: None,
+                task_limit: None,
+                limits: Limits::default(),
+                limit: None,
                 size: None,
             },
             #[cfg(not(feature = "task"))]
             () => ShellState {
                 repeat: repeat.unwrap_or(DEFAULT_REPEAT),
                 interval: interval.unwrap_or(DEFAULT_INTERVAL),
+                click: DEFAULT_CLICK,
                 idle: None,
                 in_down: None,
                 in_up: None,
                 in_repeat: None,
                 in_interval: None,
+                in_paste: false,
+                in_paste_tail: ([0; 5], 0),
+                in_focus: None,
+                in_gesture: None,
+                gesture: Tracker::default(),
                 out_last: None,
+                out_clipboard: None,
+                out_cwd: false,
+                out_bell: None,
+                out_notification: None,
                 buffer: Buf([0; 100], 0),
                 size: None,
             },
@@ -123,6 +173,12 @@ impl ShellState {
         self.interval = interval;
     }
 
+    /// The mutator method `set_click` change the time limit between
+    /// the clicks of a double or triple click.
+    pub fn set_click(&mut self, click: libc::c_long) {
+        self.click = click;
+    }
+
     /// The mutator method `set_idle` update the idle event status.
     pub fn set_idle(&mut self, entry: Option<()>) {
         self.idle = entry;
@@ -131,71 +187,66 @@ impl ShellState {
     /// The mutator method `set_input` update the `in_text`
     /// and save the old `in_text` to `in_text_past`.
     pub fn set_input(&mut self, out_screen: &mut Display, mut down: Option<Control>) {
-        match down 
-        { Some(is_mouse) =>
-            { match is_mouse.as_slice()
-              { &[b'\x1B', b'[', b'<', ref next..] =>
-                  { let (bonjour, coucou) =
-                    { catch_numbers(Vec::new(), next) };
-                    match coucou
-                    { &[b'M', ..] =>
-                      { if out_screen.get_mouse().3 == false && bonjour[0] > 2
-                        { down = None; }
-                        else if out_screen.get_mouse().3 == false && out_screen.get_mouse().0 == false && out_screen.get_mouse().1 == false
-                        { down = None; }
-                        else if out_screen.get_mouse().3 == false && (out_screen.get_mouse().1 == true || out_screen.get_mouse().0 == true) && bonjour[0] == 0 {
-                            let mut buf: In = In::default();
-
-                            buf[0] = b'\x1B';
-                            buf[1] = b'[';
-                            buf[2] = b'M';
-                            buf[3] = b' ';
-                            buf[4] = bonjour[1] as u8 + 32;
-                            buf[5] = bonjour[2] as u8 + 32;
-                            down = Some(Control::new(buf, 6));
-                        }
-                        else if out_screen.get_mouse().3 == false && out_screen.get_mouse().0 == true
-                        { down = None; }},
-                      &[b'm', ..] =>
-                      { if out_screen.get_mouse().0 == false && out_screen.get_mouse().1 == false
-                        { down = None; }
-                        else if out_screen.get_mouse().1 == true && out_screen.get_mouse().3 == false && bonjour[0] <= 2 {
-                            let mut buf: In = In::default();
-
-                            buf[0] = b'\x1B';
-                            buf[1] = b'[';
-                            buf[2] = b'M';
-                            buf[3] = b'#';
-                            buf[4] = bonjour[1] as u8 + 32;
-                            buf[5] = bonjour[2] as u8 + 32;
-                            down = Some(Control::new(buf, 6));
-                        }
-                        else if out_screen.get_mouse().0 == true
-                        { down = None; }},
-                      _ => {}, }},
-               _ => {}, }},
-          _ => {}, };
-          if out_screen.get_ss()
-          { let ss: libc::c_uchar = match down
-            { Some(after) =>
-              { match after.as_slice()
-                { &[b'\x1B', b'[', b'A', ..] => b'A',
-                  &[b'\x1B', b'[', b'B', ..] => b'B',
-                  &[b'\x1B', b'[', b'C', ..] => b'C',
-                  &[b'\x1B', b'[', b'D', ..] => b'D',
-                  _ => 0, }},
-              _ => 0, };
-            if ss > 0 {
-                let mut buf: In = In::default();
-
-                buf[0] = b'\x1B';
-                buf[1] = b'O';
-                buf[2] = ss;
-                down = Some(Control::new(buf, 3));
-            }}
+        // The gestures are recognized whatever the child's tracking.
+        self.in_gesture = match down.and_then(|control| control.is_mouse().map(|mouse| (mouse, control.as_time()))) {
+            Some((mouse, time)) => self.gesture.update(mouse, time, self.click),
+            None => None,
+        };
+        self.in_focus = down.and_then(|control| control.is_focus());
+        if self.in_focus.is_some() {
+            // The focus is forwarded only when the child has asked it.
+            self.in_down = if out_screen.get_focus_report() {
+                down
+            } else {
+                None
+            };
+            return ;
+        }
+        if self.set_paste(out_screen, down) {
+            return ;
+        }
+        // The mouse is reported with the child's tracking and encoding.
+        if let Some(mouse) = down.and_then(|control| control.is_mouse()) {
+            let bytes: Vec<libc::c_uchar> = mouse.encode(
+                out_screen.get_mouse_mode(),
+                out_screen.get_mouse_encoding(),
+                out_screen.get_window_size(),
+            );
+
+            down = if bytes.is_empty() {
+                None
+            } else {
+                Some(Control::from_mouse(mouse, bytes.as_slice()))
+            };
+        }
+
+        // The keys sent with their usual sequence (or with the kitty's
+        // protocol) by the host are encoded again for the child's modes.
+        let key: Option<Key> = down.and_then(|control| control.is_key());
+        if let (Some(key), Some(control)) = (key, down) {
+            let slice: &[libc::c_uchar] = control.as_slice();
+            let kitty: bool = slice.starts_with(b"\x1B[").bitand(slice.last().eq(&Some(&b'u')));
+
+            if kitty || key.is_repeat() || key.is_release() || key.as_bytes().as_slice().eq(slice) {
+                let bytes: Vec<libc::c_uchar> = key.encode(&out_screen.get_key_modes());
+
+                if bytes.is_empty() {
+                    down = None;
+                } else if bytes.as_slice().ne(slice) {
+                    down = Some(Control::new(In::from(bytes.as_slice()), bytes.len()));
+                }
+            }
+        }
 
         self.in_down = down;
-        if let Some(after) = down {
+        if let Some(Key::Release(_, _)) = key {
+            self.in_up = None;
+            self.in_repeat = None;
+            self.in_interval = None;
+        } else if let (Some(Key::Repeat(_, _)), Some(after)) = (key, down) {
+            self.in_repeat = Some(self.in_repeat.unwrap_or_default().add(&1));
+            self.in_up = Some(after);
+        } else if let Some(after) = down {
             if let Some(before) = self.in_up {
                 if before.eq(&after).bitand(
                     before.as_time().add(
@@ -220,6 +271,59 @@ impl ShellState {
         }
     }
 
+    /// The mutator method `set_paste` reads a text bracketed by the host,
+    /// maybe in many parts, and sends it bracketed or not according to
+    /// the child's mode; it returns false when the input isn't pasted.
+    /// A beginning of the end's marker waits for the next part, and
+    /// the keys which follow the end are read as usual.
+    fn set_paste(&mut self, out_screen: &mut Display, down: Option<Control>) -> bool {
+        let slice: &[libc::c_uchar] = match down.as_ref() {
+            Some(control) => control.as_slice(),
+            None => return false,
+        };
+        let first: bool = self.in_paste.not().bitand(slice.starts_with(PASTE_START));
+
+        if first || self.in_paste {
+            let (tail, len): ([libc::c_uchar; 5], libc::size_t) = self.in_paste_tail;
+            let mut body: Vec<libc::c_uchar> = tail[..len].to_vec();
+
+            body.extend_from_slice(if first {
+                &slice[PASTE_START.len()..]
+            } else {
+                slice
+            });
+            let end: Option<usize> = body.windows(PASTE_END.len())
+                                         .position(|window| window.eq(PASTE_END));
+            // The end's marker can be cut between two reads.
+            let kept: libc::size_t = match end {
+                Some(_) => 0,
+                None => (1..PASTE_END.len()).rev()
+                                            .find(|&len| body.ends_with(&PASTE_END[..len]))
+                                            .unwrap_or_default(),
+            };
+            let text: &[libc::c_uchar] = &body[..end.unwrap_or(body.len() - kept)];
+            let mut bytes: Vec<libc::c_uchar> = out_screen.get_key_modes()
+                                                          .paste_part(text, first, end.is_some());
+
+            self.in_paste = end.is_none();
+            self.in_paste_tail.1 = kept;
+            self.in_paste_tail.0[..kept].copy_from_slice(&body[body.len() - kept..]);
+            if let Some(rest) = end.map(|end| &body[end + PASTE_END.len()..])
+                                   .and_then(|rest| if rest.is_empty() { None } else { Some(rest) }) {
+                let input: In = In::from(rest);
+
+                self.set_input(out_screen, Some(Control::new(input, rest.len().min(input.len()))));
+                if let Some(keys) = self.in_down.as_ref().map(|control| control.as_slice()) {
+                    bytes.extend_from_slice(keys);
+                }
+            }
+            self.in_down = Some(Control::from_paste(text, &bytes));
+            true
+        } else {
+            false
+        }
+    }
+
     /// The mutator method `set_output` update the both `out_text`
     /// and `out_screen` variable.
     pub fn set_output(&mut self, out_screen: &mut Display, entry: Option<(Out, libc::size_t)>) {
@@ -270,8 +374,29 @@ impl ShellState {
               else if hs[0] & 0b11110000 == 0b11110000
               { coucou.write(&hs[0..]).unwrap(); }}
 
+            self.out_clipboard = out_screen.pop_clipboard();
+            self.out_cwd = out_screen.pop_cwd();
+            self.out_bell = if out_screen.pop_bell() { Some(()) } else { None };
+            self.out_notification = match out_screen.pop_notifications() {
+                0 => None,
+                count => Some(count),
+            };
         } else {
             self.out_last = None;
+            self.out_clipboard = None;
+            self.out_cwd = false;
+            self.out_bell = None;
+            self.out_notification = None;
+        }
+    }
+
+    /// The mutator method `set_task_cwd` reports the change of the
+    /// foreground's working directory when the child doesn't give
+    /// its own (OSC 7).
+    #[cfg(feature = "task")]
+    pub fn set_task_cwd(&mut self, out_screen: &Display, cwd: Option<()>) {
+        if cwd.is_some() && out_screen.get_cwd().is_none() {
+            self.out_cwd = true;
         }
     }
 
@@ -281,6 +406,36 @@ impl ShellState {
         self.task = task;
     }
 
+    /// The mutator method `set_limits` changes the limits of the session
+    /// whose hit is reported by `is_task_limit`.
+    #[cfg(feature = "task")]
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+        self.limit = None;
+    }
+
+    /// The mutator method `set_task_usage` updates the session's usage,
+    /// a limit is reported when it's hit for the first time.
+    #[cfg(feature = "task")]
+    pub fn set_task_usage(&mut self, usage: Option<Usage>) {
+        self.task_usage = usage;
+        self.task_limit = None;
+        if let Some(usage) = usage {
+            let limit: Option<Limit> = self.limits.exceeded(&usage);
+
+            if limit.ne(&self.limit) {
+                self.limit = limit;
+                self.task_limit = limit;
+            }
+        }
+    }
+
+    /// The mutator method `set_task_event` updates the descendant's event.
+    #[cfg(feature = "task")]
+    pub fn set_task_event(&mut self, event: Option<ProcEvent>) {
+        self.task_event = event;
+    }
+
     /// The accessor method `is_idle` returns the Idle event.
     pub fn is_idle(&self) -> Option<()> {
         self.idle
@@ -317,6 +472,21 @@ impl ShellState {
         }
     }
 
+    /// The accessor method `is_input_focus` returns the focus reported
+    /// by the host (true when gained).
+    pub fn is_input_focus(&self) -> Option<bool> {
+        self.in_focus
+    }
+
+    /// The accessor method `is_input_paste` returns the pasted text.
+    pub fn is_input_paste(&self) -> Option<&[libc::c_uchar]> {
+        if let Some(ref control) = self.in_down {
+            control.is_paste()
+        } else {
+            None
+        }
+    }
+
     /// The accessor method `is_input_mouse` returns the pressed Mouse event.
     pub fn is_input_mouse(&self) -> Option<Mouse> {
         if let Some(ref control) = self.in_down {
@@ -326,6 +496,12 @@ impl ShellState {
         }
     }
 
+    /// The accessor method `is_input_gesture` returns the recognized
+    /// mouse's gesture.
+    pub fn is_input_gesture(&self) -> Option<Gesture> {
+        self.in_gesture
+    }
+
     /// The accessor method `is_in_slice` returns the bytes for a Input event.
     pub fn is_input_slice(&self) -> Option<&[libc::c_uchar]> {
         if let Some(ref control) = self.in_down {
@@ -344,6 +520,30 @@ impl ShellState {
         }
     }
 
+    /// The accessor method `is_output_clipboard` returns the child's request
+    /// of clipboard (OSC 52), the content set is `Display::get_clipboard`.
+    pub fn is_output_clipboard(&self) -> Option<Clipboard> {
+        self.out_clipboard
+    }
+
+    /// The accessor method `is_cwd_changed` returns true when the working
+    /// directory has changed, the directory is `Shell::get_cwd`.
+    pub fn is_cwd_changed(&self) -> bool {
+        self.out_cwd
+    }
+
+    /// The accessor method `is_output_bell` returns the child's bell.
+    pub fn is_output_bell(&self) -> Option<()> {
+        self.out_bell
+    }
+
+    /// The accessor method `is_output_notification` returns the number of
+    /// child's notifications (OSC 9 and OSC 777), which are the last of
+    /// `Display::get_notifications`.
+    pub fn is_output_notification(&self) -> Option<libc::size_t> {
+        self.out_notification
+    }
+
     /// The accessor method `is_output_screen` returns the Output screen event.
     pub fn is_output_screen(&self) -> Option<()> {
         if self.is_output_last().is_some() {
@@ -363,6 +563,27 @@ impl ShellState {
         }
     }
 
+    /// The accessor method `is_task_usage` returns the periodic usage
+    /// of the session.
+    #[cfg(feature = "task")]
+    pub fn is_task_usage(&self) -> Option<Usage> {
+        self.task_usage
+    }
+
+    /// The accessor method `is_task_limit` returns the limit which has
+    /// just been hit.
+    #[cfg(feature = "task")]
+    pub fn is_task_limit(&self) -> Option<Limit> {
+        self.task_limit
+    }
+
+    /// The accessor method `is_task_event` returns the fork, the exec
+    /// or the exit of a descendant.
+    #[cfg(feature = "task")]
+    pub fn is_task_event(&self) -> Option<ProcEvent> {
+        self.task_event
+    }
+
     #[cfg(feature = "auto-resize")]
     pub fn is_resized(&self) -> Option<Winszed> {
         self.size
@@ -385,8 +606,11 @@ impl ShellState {
     #[cfg(all(feature = "task", not(feature = "auto-resize")))]
     pub fn update_from(&mut self, out_screen: &mut Display, event: DeviceState) {
         self.set_task(event.is_task());
+        self.set_task_event(event.is_task_event());
+        self.set_task_usage(event.is_task_usage());
         self.set_idle(event.is_idle());
         self.set_output(out_screen, event.is_out_text());
+        self.set_task_cwd(out_screen, event.is_task_cwd());
         self.set_input(out_screen, event.is_input());
     }
 
@@ -404,8 +628,11 @@ impl ShellState {
     #[cfg(all(feature = "task", feature = "auto-resize"))]
     pub fn update_from(&mut self, out_screen: &mut Display, event: DeviceState) {
         self.set_task(event.is_task());
+        self.set_task_event(event.is_task_event());
+        self.set_task_usage(event.is_task_usage());
         self.set_idle(event.is_idle());
         self.set_output(out_screen, event.is_out_text());
+        self.set_task_cwd(out_screen, event.is_task_cwd());
         self.set_input(out_screen, event.is_input());
         self.set_resized(out_screen);
     }
@@ -8,6 +8,9 @@ pub enum TermiosError {
     TcgGet,
     TcgSet,
     WriteMouseOn,
+    WritePasteOn,
+    WriteFocusOn,
+    WriteKeyboard,
 }
 
 impl fmt::Display for TermiosError {
@@ -26,6 +29,9 @@ impl Error for TermiosError {
             TermiosError::TcgGet => "ioctl(2) TCGETS has occured an error.",
             TermiosError::TcgSet => "ioctl(2) TCSETS has occured an error.",
             TermiosError::WriteMouseOn => "Can't write the MouseOn term.",
+            TermiosError::WritePasteOn => "Can't write the PasteOn term.",
+            TermiosError::WriteFocusOn => "Can't write the FocusOn term.",
+            TermiosError::WriteKeyboard => "Can't write the Keyboard term.",
         }
     }
 
@@ -11,6 +11,8 @@ pub struct Termios {
     pub fd: libc::c_int,
     /// Save the original configuration of terminal.
     pub config: libc::termios,
+    /// The kitty's keyboard flags are pushed.
+    pub keyboard: bool,
 }
 
 impl Termios {
@@ -30,12 +32,24 @@ impl Termios {
                 let setup: Termios = Termios {
                     fd: fd,
                     config: config,
+                    keyboard: false,
                 };
                 setup.enter_raw_mode().and(Ok(setup))
             }
         }
     }
 
+    /// The method `push_keyboard` pushes the kitty's keyboard flags,
+    /// they are popped by the drop.
+    pub fn push_keyboard(&mut self, flags: libc::c_uint) -> Result<(), TermiosError> {
+        if io::stdout().write_all(format!("\x1b[>{}u", flags).as_bytes()).is_err() {
+            Err(TermiosError::WriteKeyboard)
+        } else {
+            self.keyboard = true;
+            Ok(())
+        }
+    }
+
     fn enter_raw_mode(&self) -> Result<(), TermiosError> {
         unsafe {
             let mut new_termios: libc::termios = mem::zeroed();
@@ -60,6 +74,10 @@ impl Termios {
                 } else {
                     if io::stdout().write(super::SPEC_MOUSE_ON).is_err() {
                         Err(TermiosError::WriteMouseOn)
+                    } else if io::stdout().write(super::SPEC_PASTE_ON).is_err() {
+                        Err(TermiosError::WritePasteOn)
+                    } else if io::stdout().write(super::SPEC_FOCUS_ON).is_err() {
+                        Err(TermiosError::WriteFocusOn)
                     } else {
                         Ok(())
                     }
@@ -83,6 +101,11 @@ impl fmt::Debug for Termios {
 
 impl Drop for Termios {
     fn drop(&mut self) {
+        if self.keyboard {
+            let _ = io::stdout().write(super::SPEC_KEYBOARD_POP);
+        }
+        let _ = io::stdout().write(super::SPEC_PASTE_OFF);
+        let _ = io::stdout().write(super::SPEC_FOCUS_OFF);
         unsafe {
             if io::stdout().write(super::SPEC_MOUSE_OFF)
                            .is_err()
@@ -11,9 +11,19 @@ mod macos;
 pub use self::macos::*;
 
 /// The constante `SPEC_MOUSE_OFF` is the terms to mute the mouse.
-const SPEC_MOUSE_OFF: &'static [u8; 24] = b"\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+const SPEC_MOUSE_OFF: &'static [u8; 24] = b"\x1b[?1006l\x1b[?1015l\x1b[?1003l";
 /// The constante `SPEC_MOUSE_OFF` is the terms to listen the mouse.
 #[cfg(target_os = "linux")]
-const SPEC_MOUSE_ON: &'static [u8; 24] = b"\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const SPEC_MOUSE_ON: &'static [u8; 24] = b"\x1b[?1003h\x1b[?1015h\x1b[?1006h";
 #[cfg(target_os = "macos")]
-const SPEC_MOUSE_ON: &'static [u8; 24] = b"\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const SPEC_MOUSE_ON: &'static [u8; 24] = b"\x1b[?1003h\x1b[?1015h\x1b[?1006h";
+/// The constante `SPEC_PASTE_ON` is the term to bracket the pasted text.
+const SPEC_PASTE_ON: &'static [u8; 8] = b"\x1b[?2004h";
+/// The constante `SPEC_PASTE_OFF` is the term to stop to bracket the pasted text.
+const SPEC_PASTE_OFF: &'static [u8; 8] = b"\x1b[?2004l";
+/// The constante `SPEC_FOCUS_ON` is the term to listen the focus.
+const SPEC_FOCUS_ON: &'static [u8; 8] = b"\x1b[?1004h";
+/// The constante `SPEC_FOCUS_OFF` is the term to mute the focus.
+const SPEC_FOCUS_OFF: &'static [u8; 8] = b"\x1b[?1004l";
+/// The constante `SPEC_KEYBOARD_POP` is the term to pop the kitty's keyboard flags.
+const SPEC_KEYBOARD_POP: &'static [u8; 4] = b"\x1b[<u";
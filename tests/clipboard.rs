@@ -0,0 +1,81 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 4,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[test]
+fn test_clipboard_set() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.set_clipboard_policy(ClipboardPolicy::Write);
+    display.write(b"a\x1B]52;cp;aGVsbG8=\x07b").unwrap();
+    assert_eq!(display.get_clipboard(), b"hello");
+    assert_eq!(display.get_text().trim(), "ab");
+    let clipboard: Clipboard = display.pop_clipboard().unwrap();
+    assert!(!clipboard.is_query());
+    assert_eq!(clipboard.get_targets(), vec![ClipboardTarget::Clipboard, ClipboardTarget::Primary]);
+    assert!(display.pop_clipboard().is_none());
+
+    // The command is cut between two writes and ended by ST.
+    display.write(b"\x1B]52;;d29y").unwrap();
+    display.write(b"bGQ=\x1B").unwrap();
+    display.write(b"\\c").unwrap();
+    assert_eq!(display.get_clipboard(), b"world");
+    assert_eq!(display.pop_clipboard().unwrap().get_targets(), vec![ClipboardTarget::Select, ClipboardTarget::Cut(0)]);
+    assert_eq!(display.get_text().trim(), "abc");
+}
+
+#[test]
+fn test_clipboard_policy() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    // The requests are ignored by default.
+    assert_eq!(display.get_clipboard_policy(), ClipboardPolicy::Ignore);
+    display.write(b"\x1B]52;c;aGk=\x07").unwrap();
+    assert!(display.pop_clipboard().is_none());
+    assert!(display.get_clipboard().is_empty());
+    display.set_clipboard_policy(ClipboardPolicy::Write);
+    display.write(b"\x1B]52;c;?\x07").unwrap();
+    assert!(display.pop_clipboard().is_none());
+    display.set_clipboard_policy(ClipboardPolicy::ReadWrite);
+    display.write(b"\x1B]52;c;?\x07").unwrap();
+    let query: Clipboard = display.pop_clipboard().unwrap();
+    assert!(query.is_query());
+    assert_eq!(query.answer(b"hi"), b"\x1B]52;c;aGk=\x1B\\".to_vec());
+    display.set_clipboard_policy(ClipboardPolicy::Ignore);
+    display.write(b"\x1B]52;c;aGk=\x07").unwrap();
+    assert!(display.pop_clipboard().is_none());
+    assert!(display.get_clipboard().is_empty());
+}
+
+#[test]
+fn test_clipboard_oversized() {
+    let mut display: Display = Display::from_winszed(SIZE);
+    let payload: Vec<u8> = vec![b'A'; (1 << 20) + 4096];
+
+    display.set_clipboard_policy(ClipboardPolicy::Write);
+    // The command longer than the limit is swallowed until its ST.
+    display.write(b"a\x1B]52;c;").unwrap();
+    for chunk in payload.chunks(4096) {
+        display.write(chunk).unwrap();
+    }
+    display.write(b"\x1B").unwrap();
+    display.write(b"\\b").unwrap();
+    assert!(display.pop_clipboard().is_none());
+    assert!(display.get_clipboard().is_empty());
+    assert_eq!(display.get_text().trim(), "ab");
+
+    // The next command is applied.
+    display.write(b"\x1B]52;c;aGk=\x07c").unwrap();
+    assert_eq!(display.get_clipboard(), b"hi");
+    assert_eq!(display.get_text().trim(), "abc");
+}
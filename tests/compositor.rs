@@ -0,0 +1,109 @@
+extern crate pty_proc;
+extern crate libc;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 8,
+    ws_col: 11,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+fn rect(x: usize, y: usize, col: usize, row: usize) -> Rect {
+    Rect {
+        origin: Coordinate::from((x, y)),
+        size: Coordinate::from((col, row)),
+    }
+}
+
+#[test]
+fn test_compositor_split() {
+    let mut compositor: Compositor = Compositor::new(SIZE);
+
+    assert!(compositor.split(None, Split::Horizontal, 0).is_ok());
+    assert_eq!(compositor.get_panes(), vec![(0, rect(0, 0, 11, 8))]);
+    assert!(compositor.split(Some(0), Split::Horizontal, 1).is_ok());
+    assert_eq!(compositor.get_panes(), vec![(0, rect(0, 0, 5, 8)),
+                                            (1, rect(6, 0, 5, 8))]);
+    assert!(compositor.split(Some(1), Split::Vertical, 2).is_ok());
+    assert_eq!(compositor.get_panes(), vec![(0, rect(0, 0, 5, 8)),
+                                            (1, rect(6, 0, 5, 3)),
+                                            (2, rect(6, 4, 5, 4))]);
+    assert!(compositor.split(Some(42), Split::Vertical, 3).is_err());
+    assert!(compositor.split(Some(0), Split::Vertical, 2).is_err());
+}
+
+#[test]
+fn test_compositor_remove() {
+    let mut compositor: Compositor = Compositor::new(SIZE);
+
+    assert!(compositor.split(None, Split::Horizontal, 0).is_ok());
+    assert!(compositor.split(Some(0), Split::Horizontal, 1).is_ok());
+    assert!(compositor.split(Some(1), Split::Vertical, 2).is_ok());
+    assert!(compositor.remove(1).is_ok());
+    assert!(compositor.remove(1).is_err());
+    assert_eq!(compositor.get_panes(), vec![(0, rect(0, 0, 5, 8)),
+                                            (2, rect(6, 0, 5, 8))]);
+    assert!(compositor.remove(0).is_ok());
+    assert_eq!(compositor.get_panes(), vec![(2, rect(0, 0, 11, 8))]);
+}
+
+#[test]
+fn test_compositor_translate() {
+    let mut compositor: Compositor = Compositor::new(SIZE);
+
+    assert!(compositor.split(None, Split::Horizontal, 0).is_ok());
+    assert!(compositor.split(Some(0), Split::Horizontal, 1).is_ok());
+    assert_eq!(compositor.pane_at(5, 0), None);
+    assert_eq!(compositor.pane_at(7, 2), Some((1, Coordinate::from((1, 2)))));
+    assert_eq!(compositor.translate(Mouse::from((0u8, true, [8, 3]))),
+               Some((1, Mouse::from((0u8, true, [2, 3])))));
+    assert_eq!(compositor.translate(Mouse::from((0u8, true, [1, 1]))),
+               Some((0, Mouse::from((0u8, true, [1, 1])))));
+}
+
+#[test]
+fn test_compositor_render() {
+    let mut compositor: Compositor = Compositor::new(SIZE);
+    let mut manager: SessionManager = SessionManager::headless(None, None);
+    let first: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+    let second: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    assert!(compositor.split(None, Split::Horizontal, first).is_ok());
+    assert!(compositor.split(Some(first), Split::Horizontal, second).is_ok());
+    compositor.resize(&mut manager);
+    assert!(manager.set_focus(second).is_ok());
+
+    let frame: String = compositor.render(&manager);
+    let rows: Vec<Vec<&str>> = frame.split('\n').map(|row|
+                                   row.split("\x1B[m").skip(1).collect::<Vec<&str>>()
+                               ).collect::<Vec<Vec<&str>>>();
+
+    assert_eq!(rows.len(), 8);
+    assert!(rows.iter().all(|row| {
+        // The border between the panes is highlighted by the focus.
+        row.len() == 11 &&
+        row[5] == "\x1B[38;2;0;255;0m│" &&
+        row[..5].iter().chain(row[6..].iter()).all(|&cell| cell == " ")
+    }));
+
+    // Only the borders around the focused pane are highlighted.
+    let third: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    assert!(compositor.split(Some(second), Split::Vertical, third).is_ok());
+    compositor.resize(&mut manager);
+    assert!(manager.set_focus(third).is_ok());
+
+    let frame: String = compositor.render(&manager);
+    let rows: Vec<Vec<&str>> = frame.split('\n').map(|row|
+                                   row.split("\x1B[m").skip(1).collect::<Vec<&str>>()
+                               ).collect::<Vec<Vec<&str>>>();
+
+    assert_eq!(rows[0][5], "│");
+    assert_eq!(rows[2][5], "│");
+    assert_eq!(rows[3][5], "\x1B[38;2;0;255;0m│");
+    assert_eq!(rows[7][5], "\x1B[38;2;0;255;0m│");
+    assert!(rows[3][6..].iter().all(|&cell| cell == "\x1B[38;2;0;255;0m─"));
+    assert!(rows[2][6..].iter().all(|&cell| cell == " "));
+}
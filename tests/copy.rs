@@ -0,0 +1,119 @@
+extern crate pty_proc;
+
+use std::io::Write;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 4,
+    ws_col: 12,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+fn feed(copy: &mut CopyMode, display: &Display, keys: &str) -> CopyFeed {
+    keys.chars().map(|glyph| {
+        copy.feed(display, Key::Code(KeyCode::Char(glyph), Modifiers::default()))
+    }).last().unwrap_or(CopyFeed::Ignore)
+}
+
+fn cursor(copy: &CopyMode) -> (usize, usize) {
+    (copy.get_cursor().x, copy.get_cursor().y)
+}
+
+#[test]
+fn test_copy_vi() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"$ ls -l\r\nfoo.rs bar\r\n  baz").unwrap();
+    let mut copy: CopyMode = CopyMode::new(&display, CopyStyle::Vi);
+
+    // The cursor starts on the child's cursor.
+    assert_eq!(cursor(&copy), (5, 2));
+    assert_eq!(feed(&mut copy, &display, "k0"), CopyFeed::Redraw);
+    assert_eq!(cursor(&copy), (0, 1));
+    feed(&mut copy, &display, "w");
+    assert_eq!(cursor(&copy), (7, 1));
+    // The words continue on the next line.
+    feed(&mut copy, &display, "w");
+    assert_eq!(cursor(&copy), (2, 2));
+    feed(&mut copy, &display, "b");
+    assert_eq!(cursor(&copy), (7, 1));
+    feed(&mut copy, &display, "e");
+    assert_eq!(cursor(&copy), (9, 1));
+    feed(&mut copy, &display, "j^");
+    assert_eq!(cursor(&copy), (2, 2));
+    feed(&mut copy, &display, "g$");
+    assert_eq!(cursor(&copy), (6, 0));
+    feed(&mut copy, &display, "3h");
+    assert_eq!(cursor(&copy), (3, 0));
+    feed(&mut copy, &display, "G");
+    assert_eq!(cursor(&copy), (0, 3));
+    assert_eq!(feed(&mut copy, &display, "x"), CopyFeed::Ignore);
+
+    // The selection follows the cursor.
+    feed(&mut copy, &display, "k0v");
+    assert_eq!(feed(&mut copy, &display, "jll"), CopyFeed::Redraw);
+    assert_eq!(feed(&mut copy, &display, "y"), CopyFeed::Yank("  baz\n".to_string()));
+    feed(&mut copy, &display, "gkV");
+    assert!(copy.get_selection().is_some());
+    assert_eq!(feed(&mut copy, &display, "jy"), CopyFeed::Yank("$ ls -l\nfoo.rs bar".to_string()));
+    assert_eq!(copy.get_buffer(), "$ ls -l\nfoo.rs bar");
+    assert_eq!(copy.feed(&display, Key::from(22)), CopyFeed::Redraw);
+    feed(&mut copy, &display, "jllll");
+    assert_eq!(copy.get_selection().map(|selection| selection.get_mode()), Some(SelectionMode::Block));
+    assert_eq!(feed(&mut copy, &display, "y"), CopyFeed::Yank("foo.r\n  baz".to_string()));
+
+    // The escape clears the selection, then ends.
+    feed(&mut copy, &display, "v");
+    assert_eq!(copy.feed(&display, Key::Code(KeyCode::Escape, Modifiers::default())), CopyFeed::Redraw);
+    assert!(copy.get_selection().is_none());
+    assert_eq!(copy.feed(&display, Key::Code(KeyCode::Escape, Modifiers::default())), CopyFeed::Exit);
+    assert_eq!(feed(&mut copy, &display, "q"), CopyFeed::Exit);
+}
+
+#[test]
+fn test_copy_emacs() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"make all\r\nerror: x\r\nok").unwrap();
+    let mut copy: CopyMode = CopyMode::new(&display, CopyStyle::Emacs);
+
+    assert_eq!(copy.get_style(), CopyStyle::Emacs);
+    copy.feed(&display, Key::from(16));
+    copy.feed(&display, Key::from(1));
+    assert_eq!(cursor(&copy), (0, 1));
+    copy.feed(&display, Key::from(0));
+    copy.feed(&display, Key::Code(KeyCode::Char('f'), Modifiers { alt: true, ..Modifiers::default() }));
+    assert_eq!(cursor(&copy), (4, 1));
+    assert_eq!(copy.feed(&display, Key::Code(KeyCode::Char('w'), Modifiers { alt: true, ..Modifiers::default() })),
+               CopyFeed::Yank("error".to_string()));
+    assert_eq!(copy.feed(&display, Key::from(7)), CopyFeed::Exit);
+
+    // The search is repeated from the cursor.
+    copy.set_search(Some(Search::new("a", SearchCase::Sensitive).unwrap()));
+    copy.feed(&display, Key::from(19));
+    assert_eq!(cursor(&copy), (4, 1));
+    copy.feed(&display, Key::Code(KeyCode::Char('<'), Modifiers { alt: true, ..Modifiers::default() }));
+    copy.feed(&display, Key::from(19));
+    assert_eq!(cursor(&copy), (1, 0));
+    copy.feed(&display, Key::from(19));
+    assert_eq!(cursor(&copy), (5, 0));
+    copy.feed(&display, Key::from(19));
+    assert_eq!(cursor(&copy), (5, 0));
+    copy.feed(&display, Key::from(18));
+    assert_eq!(cursor(&copy), (1, 0));
+}
+
+#[test]
+fn test_copy_overlay() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"abc\r\ndef").unwrap();
+    let mut copy: CopyMode = CopyMode::new(&display, CopyStyle::Vi);
+
+    assert_eq!(copy.overlay(&display), b"\x1B[2;4H\x1B[?25h".to_vec());
+    feed(&mut copy, &display, "hvkh");
+    assert_eq!(String::from_utf8(copy.overlay(&display)).unwrap(),
+               "\x1B[1;2H\x1B[7mbc         \x1B[27m\x1B[2;1H\x1B[7mdef\x1B[27m\x1B[1;2H\x1B[?25h");
+}
@@ -0,0 +1,43 @@
+extern crate pty_proc;
+
+use std::io::Write;
+use std::path::Path;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 6,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[test]
+fn test_cwd_osc() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    assert_eq!(display.get_cwd(), None);
+    assert!(!display.pop_cwd());
+    display.write(b"\x1B]7;file://host/home/user\x07").unwrap();
+    assert_eq!(display.get_cwd(), Some(Path::new("/home/user")));
+    assert_eq!(display.get_cwd_host(), Some("host"));
+    assert!(display.pop_cwd());
+    assert!(!display.pop_cwd());
+
+    // The same directory isn't a change.
+    display.write(b"\x1B]7;file://host/home/user\x1B\\").unwrap();
+    assert!(!display.pop_cwd());
+
+    display.write(b"\x1B]7;file:///tmp/a%20b;c%2\x07").unwrap();
+    assert_eq!(display.get_cwd(), Some(Path::new("/tmp/a b;c%2")));
+    assert_eq!(display.get_cwd_host(), Some(""));
+    assert!(display.pop_cwd());
+
+    display.write(b"\x1B]7;/srv\x07").unwrap();
+    assert_eq!(display.get_cwd(), Some(Path::new("/srv")));
+
+    // The unknown schemes are ignored.
+    display.write(b"\x1B]7;http://host/var\x07\x1B]7;file://host\x07").unwrap();
+    assert_eq!(display.get_cwd(), Some(Path::new("/srv")));
+    assert_eq!(display.get_text(), "\n\n\n\n\n");
+}
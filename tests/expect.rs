@@ -0,0 +1,45 @@
+extern crate pty_proc;
+
+use std::time;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 5,
+    ws_col: 20,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[test]
+fn test_expect_stream() {
+    let timeout: time::Duration = time::Duration::from_secs(5);
+    let mut expect: Expect = Expect::spawn(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    expect.send_line("id=42 name=cat").unwrap();
+    let found: Match = expect.expect(r"id=(\d+) name=(\w+)", timeout).unwrap();
+
+    assert_eq!(found.get_matched(), "id=42 name=cat");
+    assert_eq!(found.get(1), Some("42"));
+    assert_eq!(found.get(2), Some("cat"));
+    assert_eq!(found.get(3), None);
+    // The echo and the copy of cat are both on the stream.
+    let found: Match = expect.expect(r"id=(\d+)", timeout).unwrap();
+
+    assert_eq!(found.get(1), Some("42"));
+    assert!(expect.expect("never", time::Duration::from_millis(50)).is_err());
+}
+
+#[test]
+fn test_expect_screen() {
+    let timeout: time::Duration = time::Duration::from_secs(5);
+    let mut expect: Expect = Expect::spawn(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    expect.send_line("hello").unwrap();
+    expect.expect_screen(|display| display.get_text().matches("hello").count() == 2, timeout).unwrap();
+    assert_eq!(expect.get_shell().get_screen().get_text().lines().next(), Some("hello"));
+    let found: Match = expect.expect_screen_regex(r"(h\w+)\n(h\w+)", timeout).unwrap();
+
+    assert_eq!(found.get(2), Some("hello"));
+    assert_eq!(found.get_before(), "");
+}
@@ -3,6 +3,13 @@ extern crate libc;
 
 use self::pty_proc::prelude::*;
 
+const SIZE: Winszed = Winszed {
+    ws_row: 5,
+    ws_col: 20,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
 #[test]
 fn test_arrow_left() {
     let mut buf: In = In::default();
@@ -14,3 +21,99 @@ fn test_arrow_left() {
         Control::new(buf, 3).is_key().unwrap().is_left()
     );
 }
+
+#[test]
+fn test_paste() {
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    let state: ShellState = shell.next_input(b"\x1B[200~echo a\nb");
+    assert_eq!(state.is_input_paste(), Some(&b"echo a\nb"[..]));
+    assert_eq!(state.is_input_slice(), Some(&b"echo a\nb"[..]));
+    assert!(state.is_input_keydown().is_none());
+    let state: ShellState = shell.next_input(b"\x1B[A\x1B[201~");
+    assert_eq!(state.is_input_paste(), Some(&b"\x1B[A"[..]));
+    let state: ShellState = shell.next_input(b"\x1B[A");
+    assert!(state.is_input_paste().is_none());
+    assert!(state.is_input_keydown().unwrap().is_up());
+
+    // The child asks a bracketed paste.
+    Parent::write(&mut shell, b"\x1B[?2004h").unwrap();
+    let state: ShellState = shell.next_input(b"\x1B[200~ls\x1B[201~");
+    assert_eq!(state.is_input_paste(), Some(&b"ls"[..]));
+    assert_eq!(state.is_input_slice(), Some(&b"\x1B[200~ls\x1B[201~"[..]));
+}
+
+#[test]
+fn test_paste_split() {
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    // The end's marker is cut between two reads.
+    let state: ShellState = shell.next_input(b"\x1B[200~ls\x1B[2");
+    assert_eq!(state.is_input_paste(), Some(&b"ls"[..]));
+    let state: ShellState = shell.next_input(b"01~");
+    assert_eq!(state.is_input_paste(), Some(&b""[..]));
+    let state: ShellState = shell.next_input(b"\x1B[A");
+    assert!(state.is_input_paste().is_none());
+    assert!(state.is_input_keydown().unwrap().is_up());
+
+    // The keys which follow the end are sent as usual.
+    Parent::write(&mut shell, b"\x1B[?2004h\x1B[?1h").unwrap();
+    let state: ShellState = shell.next_input(b"\x1B[200~ls\x1B[201~\x1B[A");
+    assert_eq!(state.is_input_paste(), Some(&b"ls"[..]));
+    assert_eq!(state.is_input_slice(), Some(&b"\x1B[200~ls\x1B[201~\x1BOA"[..]));
+    let state: ShellState = shell.next_input(b"a");
+    assert!(state.is_input_paste().is_none());
+    assert_eq!(state.is_input_slice(), Some(&b"a"[..]));
+}
+
+#[test]
+fn test_focus() {
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    let state: ShellState = shell.next_input(b"\x1B[O");
+    assert_eq!(state.is_input_focus(), Some(false));
+    assert!(state.is_input_slice().is_none());
+    let state: ShellState = shell.next_input(b"a");
+    assert_eq!(state.is_input_focus(), None);
+
+    // The child listens the focus.
+    Parent::write(&mut shell, b"\x1B[?1004h").unwrap();
+    let state: ShellState = shell.next_input(b"\x1B[I");
+    assert_eq!(state.is_input_focus(), Some(true));
+    assert_eq!(state.is_input_slice(), Some(&b"\x1B[I"[..]));
+}
+
+#[test]
+fn test_gesture() {
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    shell.set_click(60_000);
+    let state: ShellState = shell.next_input(b"\x1B[<0;3;2M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Click(0, 1, [3, 2])));
+    // The child doesn't track the mouse.
+    assert!(state.is_input_slice().is_none());
+    let state: ShellState = shell.next_input(b"\x1B[<0;3;2m");
+    assert_eq!(state.is_input_gesture(), None);
+    let state: ShellState = shell.next_input(b"\x1B[<0;3;2M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Click(0, 2, [3, 2])));
+    shell.next_input(b"\x1B[<0;3;2m");
+    let state: ShellState = shell.next_input(b"\x1B[<0;3;2M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Click(0, 3, [3, 2])));
+
+    let state: ShellState = shell.next_input(b"\x1B[<32;5;2M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::DragStart(0, [3, 2], [5, 2])));
+    let state: ShellState = shell.next_input(b"\x1B[<32;6;3M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::DragUpdate(0, [3, 2], [6, 3])));
+    let state: ShellState = shell.next_input(b"\x1B[<0;6;3m");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::DragEnd(0, [3, 2], [6, 3])));
+    let state: ShellState = shell.next_input(b"\x1B[<35;7;3M");
+    assert_eq!(state.is_input_gesture(), None);
+
+    // A click somewhere else is counted again.
+    let state: ShellState = shell.next_input(b"\x1B[<2;1;1M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Click(2, 1, [1, 1])));
+    let state: ShellState = shell.next_input(b"\x1B[<65;1;1M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Wheel(1, [1, 1])));
+    let state: ShellState = shell.next_input(b"\x1B[<64;1;1M");
+    assert_eq!(state.is_input_gesture(), Some(Gesture::Wheel(-1, [1, 1])));
+}
@@ -3,7 +3,140 @@ extern crate libc;
 
 use self::pty_proc::prelude::*;
 
+const SHIFT: Modifiers = Modifiers { shift: true, ctrl: false, alt: false, meta: false };
+const CTRL: Modifiers = Modifiers { shift: false, ctrl: true, alt: false, meta: false };
+const ALT: Modifiers = Modifiers { shift: false, ctrl: false, alt: true, meta: false };
+
+fn key(buf: &[u8]) -> Key {
+    Key::from((In::from(buf), buf.len()))
+}
+
 #[test]
 fn test_key_new() {
-    assert_eq!(Key::from(233u32), Key::Char(233));
+    assert_eq!(Key::from(233u32), Key::Code(KeyCode::Char('é'), Modifiers::default()));
+    assert_eq!(Key::from(63236u32), Key::Code(KeyCode::Function(1), Modifiers::default()));
+}
+
+#[test]
+fn test_key_modifiers() {
+    assert_eq!(key(b"\x1B[A"), Key::Code(KeyCode::Up, Modifiers::default()));
+    assert_eq!(key(b"\x1BOA"), Key::Code(KeyCode::Up, Modifiers::default()));
+    assert_eq!(key(b"\x1B[1;2A"), Key::Code(KeyCode::Up, SHIFT));
+    assert_eq!(key(b"\x1B[1;5D"), Key::Code(KeyCode::Left, CTRL));
+    assert_eq!(key(b"\x1B[1;6C"), Key::Code(KeyCode::Right, Modifiers { shift: true, ctrl: true, alt: false, meta: false }));
+    assert_eq!(key(b"\x1B[1;10B"), Key::Code(KeyCode::Down, Modifiers { shift: true, ctrl: false, alt: false, meta: true }));
+    assert_eq!(key(b"\x1B[1;3H"), Key::Code(KeyCode::Home, ALT));
+    assert_eq!(key(b"\x1B[1;2P"), Key::Code(KeyCode::Function(1), SHIFT));
+    assert_eq!(key(b"\x1B[24;5~"), Key::Code(KeyCode::Function(12), CTRL));
+    assert_eq!(key(b"\x1B[2;3~"), Key::Code(KeyCode::Insert, ALT));
+    assert_eq!(key(b"\x1B[6~"), Key::Code(KeyCode::PageDown, Modifiers::default()));
+    assert_eq!(key(b"\x1B[27;5;13~"), Key::Code(KeyCode::Enter, CTRL));
+    assert_eq!(key(b"\x1B[Z"), Key::Code(KeyCode::Tab, SHIFT));
+    assert_eq!(key(b"\x01"), Key::Code(KeyCode::Char('a'), CTRL));
+    assert_eq!(key(b"\x1Bx"), Key::Code(KeyCode::Char('x'), ALT));
+    assert_eq!(key(b"\x7F"), Key::Code(KeyCode::Backspace, Modifiers::default()));
+    assert!(key(b"\x1B[A").is_up());
+    assert!(key(b"\x07").is_bell());
+    assert!(key(b"\x1B[99;99;99X").is_code().is_none());
+}
+
+#[test]
+fn test_key_bytes() {
+    ["\x1B[A", "\x1B[1;2A", "\x1B[1;5D", "\x1BOP", "\x1B[1;2P",
+     "\x1B[24;5~", "\x1B[2;3~", "\x1B[6~", "\x1B[Z", "\x01", "\x1Bx", "é",
+     "\x1B[27;5;13~"].iter().all(|sequence| {
+        assert_eq!(key(sequence.as_bytes()).as_bytes(), sequence.as_bytes());
+        true
+    });
+    assert_eq!(Key::Code(KeyCode::Enter, Modifiers::default()).as_bytes(), b"\r");
+    // A full buffer doesn't end with a nul.
+    let full: In = In([b'a'; 496]);
+
+    assert_eq!(Key::Str(full).as_bytes(), vec![b'a'; 496]);
+}
+
+#[test]
+fn test_key_kitty() {
+    assert_eq!(key(b"\x1B[97;5u"), Key::Code(KeyCode::Char('a'), CTRL));
+    assert_eq!(key(b"\x1B[105;5u"), Key::Code(KeyCode::Char('i'), CTRL));
+    assert_eq!(key(b"\x1B[9u"), Key::Code(KeyCode::Tab, Modifiers::default()));
+    assert_eq!(key(b"\x1B[97;1:2u"), Key::Repeat(KeyCode::Char('a'), Modifiers::default()));
+    assert_eq!(key(b"\x1B[97;1:3u"), Key::Release(KeyCode::Char('a'), Modifiers::default()));
+    assert_eq!(key(b"\x1B[1;5:3A"), Key::Release(KeyCode::Up, CTRL));
+    assert_eq!(key(b"\x1B[57376u"), Key::Code(KeyCode::Function(13), Modifiers::default()));
+    assert!(key(b"\x1B[57441;2u").is_code().is_none());
+
+    // Legacy child.
+    assert_eq!(key(b"\x1B[97;5u").as_kitty(0), b"\x01");
+    assert_eq!(key(b"\x1B[97;1:3u").as_kitty(0), b"");
+    // Disambiguate.
+    assert_eq!(Key::Code(KeyCode::Char('i'), CTRL).as_kitty(1), b"\x1B[105;5u");
+    assert_eq!(Key::Code(KeyCode::Tab, Modifiers::default()).as_kitty(1), b"\t");
+    assert_eq!(Key::Code(KeyCode::Escape, Modifiers::default()).as_kitty(1), b"\x1B[27u");
+    assert_eq!(Key::Code(KeyCode::Char('a'), Modifiers::default()).as_kitty(1), b"a");
+    assert_eq!(Key::Code(KeyCode::Function(3), SHIFT).as_kitty(1), b"\x1B[13;2~");
+    assert_eq!(Key::Code(KeyCode::Up, Modifiers::default()).as_kitty(1), b"\x1B[A");
+    // Event types.
+    assert_eq!(Key::Release(KeyCode::Up, CTRL).as_kitty(3), b"\x1B[1;5:3A");
+    assert_eq!(Key::Release(KeyCode::Up, CTRL).as_kitty(1), b"");
+    assert_eq!(Key::Repeat(KeyCode::Char('A'), Modifiers::default()).as_kitty(11), b"\x1B[97;2:2u");
+}
+
+#[test]
+fn test_key_encode() {
+    let normal: KeyModes = KeyModes::default();
+    let application: KeyModes = KeyModes {
+        cursor: true,
+        keypad: true,
+        backspace: true,
+        ..KeyModes::default()
+    };
+
+    assert_eq!(Key::Code(KeyCode::Up, Modifiers::default()).encode(&normal), b"\x1B[A");
+    assert_eq!(Key::Code(KeyCode::Up, Modifiers::default()).encode(&application), b"\x1BOA");
+    assert_eq!(Key::Code(KeyCode::Up, CTRL).encode(&application), b"\x1B[1;5A");
+    assert_eq!(Key::Code(KeyCode::Up, ALT).encode(&normal), b"\x1B[1;3A");
+    assert_eq!(Key::Code(KeyCode::Keypad('5'), Modifiers::default()).encode(&normal), b"5");
+    assert_eq!(Key::Code(KeyCode::Keypad('5'), Modifiers::default()).encode(&application), b"\x1BOu");
+    assert_eq!(Key::Code(KeyCode::Keypad('\r'), Modifiers::default()).encode(&application), b"\x1BOM");
+    assert_eq!(key(b"\x1BOM"), Key::Code(KeyCode::Keypad('\r'), Modifiers::default()));
+    assert_eq!(Key::Code(KeyCode::Backspace, Modifiers::default()).encode(&normal), b"\x7F");
+    assert_eq!(Key::Code(KeyCode::Backspace, Modifiers::default()).encode(&application), b"\x08");
+    assert_eq!(Key::Code(KeyCode::Backspace, ALT).encode(&normal), b"\x1B\x7F");
+    // modifyOtherKeys.
+    assert_eq!(Key::Code(KeyCode::Char('1'), CTRL).encode(&normal), b"1");
+    assert_eq!(Key::Code(KeyCode::Char('a'), CTRL).encode(&normal), b"\x01");
+    assert_eq!(Key::Code(KeyCode::Enter, CTRL).encode(&normal), b"\r");
+    let other: KeyModes = KeyModes { modify_other_keys: 1, ..KeyModes::default() };
+
+    assert_eq!(Key::Code(KeyCode::Char('1'), CTRL).encode(&other), b"\x1B[27;5;49~");
+    assert_eq!(Key::Code(KeyCode::Char('a'), CTRL).encode(&other), b"\x01");
+    assert_eq!(Key::Code(KeyCode::Enter, CTRL).encode(&other), b"\x1B[27;5;13~");
+    let other: KeyModes = KeyModes { modify_other_keys: 2, ..KeyModes::default() };
+
+    assert_eq!(Key::Code(KeyCode::Char('a'), CTRL).encode(&other), b"\x1B[27;5;97~");
+    assert_eq!(Key::Code(KeyCode::Char('a'), SHIFT).encode(&other), b"a");
+    // Bracketed paste.
+    let paste: KeyModes = KeyModes { paste: true, ..KeyModes::default() };
+
+    assert_eq!(paste.paste(b"ls\x1B[201~ -l"), b"\x1B[200~ls -l\x1B[201~".to_vec());
+    // The removal of a inner marker doesn't join a outer one.
+    assert_eq!(paste.paste(b"\x1B[20\x1B[201~1~; rm -rf ~\n"), b"\x1B[200~; rm -rf ~\n\x1B[201~".to_vec());
+    assert_eq!(normal.paste(b"a\x1B[2\x1B[201~01~b"), b"ab".to_vec());
+    assert_eq!(normal.paste(b"ls"), b"ls".to_vec());
+}
+
+#[test]
+fn test_key_split() {
+    let keys: Vec<(Key, &[u8])> = Key::split(b"\x02[a\x1B[1;5A\x1BOP\xC3\xA9\x1Bx\x1B[<0;3;2M\x1B");
+
+    assert_eq!(keys.iter().map(|&(_, bytes)| bytes).collect::<Vec<&[u8]>>(),
+               vec![&b"\x02"[..], b"[", b"a", b"\x1B[1;5A", b"\x1BOP", "é".as_bytes(), b"\x1Bx", b"\x1B[<0;3;2M", b"\x1B"]);
+    assert_eq!(keys[0].0, Key::from(2));
+    assert_eq!(keys[3].0, Key::Code(KeyCode::Up, CTRL));
+    assert_eq!(keys[4].0, Key::Code(KeyCode::Function(1), Modifiers::default()));
+    assert_eq!(keys[5].0, Key::Code(KeyCode::Char('é'), Modifiers::default()));
+    assert_eq!(keys[6].0, Key::Code(KeyCode::Char('x'), ALT));
+    assert_eq!(keys[8].0, Key::Code(KeyCode::Escape, Modifiers::default()));
+    assert!(Key::split(b"").is_empty());
 }
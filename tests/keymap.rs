@@ -0,0 +1,48 @@
+extern crate pty_proc;
+
+use std::sync::{Arc, Mutex};
+
+use self::pty_proc::prelude::*;
+use self::pty_proc::keymap::parse_key;
+
+const CONFIG: &'static str = "
+# The prefix à la tmux.
+prefix C-B
+bind C-b send-key C-b
+bind [ copy-mode
+bind -n S-PageUp scroll-up 10
+bind x callback close
+";
+
+#[test]
+fn test_keymap_parse() {
+    assert_eq!(parse_key("C-b"), Some(Key::from(2)));
+    assert_eq!(parse_key("M-S-Up"), Some(Key::Code(KeyCode::Up, Modifiers { shift: true, ctrl: false, alt: true, meta: false })));
+    assert_eq!(parse_key("F12"), Some(Key::Code(KeyCode::Function(12), Modifiers::default())));
+    assert_eq!(parse_key("F36"), None);
+    assert_eq!(parse_key("X-a"), None);
+    assert!(Keymap::from_config("bind x dance").is_err());
+    assert!(Keymap::from_config("prefix\n").is_err());
+}
+
+#[test]
+fn test_keymap_feed() {
+    let mut keymap: Keymap = Keymap::from_config(CONFIG).unwrap();
+    let closed: Arc<Mutex<bool>> = Arc::new(Mutex::new(false));
+    let callback: Arc<Mutex<bool>> = closed.clone();
+
+    keymap.set_callback("close", Box::new(move || *callback.lock().unwrap() = true));
+    assert_eq!(keymap.feed(Key::from(b'a' as u32)), Feed::Forward);
+    assert_eq!(keymap.feed(Key::from(2)), Feed::Prefix);
+    assert!(keymap.is_pending());
+    assert_eq!(keymap.feed(Key::from(b'[' as u32)), Feed::Action(Action::CopyMode));
+    assert_eq!(keymap.feed(Key::from(2)), Feed::Prefix);
+    assert_eq!(keymap.feed(Key::from(2)), Feed::Action(Action::SendKey(Key::from(2))));
+    assert_eq!(keymap.feed(Key::from(2)), Feed::Prefix);
+    assert_eq!(keymap.feed(Key::from(b'q' as u32)), Feed::Ignore);
+    assert_eq!(keymap.feed(Key::Code(KeyCode::PageUp, Modifiers { shift: true, ctrl: false, alt: false, meta: false })),
+               Feed::Action(Action::ScrollUp(10)));
+    assert_eq!(keymap.feed(Key::from(2)), Feed::Prefix);
+    assert_eq!(keymap.feed(Key::from(b'x' as u32)), Feed::Action(Action::Callback("close".to_string())));
+    assert!(*closed.lock().unwrap());
+}
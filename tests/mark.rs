@@ -0,0 +1,62 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 6,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+const SESSION: &'static [u8] = b"\x1B]133;A\x07$ \x1B]133;B\x07ls\r\n\x1B]133;C\x07a b\r\nc\r\n\x1B]133;D;0\x07\
+\x1B]133;A\x07$ \x1B]133;B\x07false\r\n\x1B]133;C\x1B\\\x1B]133;D;1\x07\x1B]133;A\x07$ ";
+
+#[test]
+fn test_mark_commands() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(SESSION).unwrap();
+    assert_eq!(display.get_text(), "$ ls\na b\nc\n$ false\n$\n");
+    let commands: Vec<Command> = display.get_commands();
+
+    assert_eq!(commands.len(), 3);
+    assert_eq!(commands[0].get_exit(), Some(0));
+    assert_eq!(commands[1].get_exit(), Some(1));
+    assert_eq!(commands[2].get_command(), None);
+    assert_eq!(display.get_command_line(&commands[0]), Some("ls".to_string()));
+    assert_eq!(display.get_command_line(&commands[1]), Some("false".to_string()));
+    assert_eq!(display.get_output(&commands[0]), Some("a b\nc".to_string()));
+    assert_eq!(display.get_output(&commands[1]), Some(String::new()));
+    assert_eq!(display.get_last_output(), Some(String::new()));
+    assert_eq!(display.next_prompt(0).map(|mark| mark.get_line()), Some(3));
+    assert_eq!(display.previous_prompt(4).map(|mark| mark.get_line()), Some(3));
+    assert_eq!(display.previous_prompt(0), None);
+
+    // The running command's output ends at the cursor.
+    display.write(b"\x1B]133;B\x07yes\r\n\x1B]133;C\x07y\r\ny").unwrap();
+    assert!(display.get_commands()[2].is_running());
+    assert_eq!(display.get_last_output(), Some("y\ny".to_string()));
+}
+
+#[test]
+fn test_mark_scroll() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(SESSION).unwrap();
+    display.write(b"\x1B]133;B\x07seq 3\r\n\x1B]133;C\x071\r\n2\r\n3\r\n\x1B]133;D;0\x07\x1B]133;A\x07$ ").unwrap();
+    assert_eq!(display.get_scrolled(), 3);
+    assert_eq!(display.get_text(), "$ false\n$ seq 3\n1\n2\n3\n$");
+    let commands: Vec<Command> = display.get_commands();
+
+    assert_eq!(commands.len(), 4);
+    assert_eq!(commands[2].get_prompt().get_line(), 4);
+    assert_eq!(display.get_command_line(&commands[1]), Some("false".to_string()));
+    assert_eq!(display.get_command_line(&commands[2]), Some("seq 3".to_string()));
+    assert_eq!(display.get_last_output(), Some("1\n2\n3".to_string()));
+    // The output of the first command has scrolled out of the screen.
+    assert_eq!(display.get_output(&commands[0]), None);
+}
@@ -7,3 +7,48 @@ use self::pty_proc::prelude::*;
 fn test_mouse_new() {
     assert_eq!(Mouse::default().as_input(), (In::from(&[b'\x1B', b'[', b'<', 48, b';', 48, b'm'][..]), 7));
 }
+
+const SIZE: Winszed = Winszed {
+    ws_row: 10,
+    ws_col: 20,
+    ws_xpixel: 200,
+    ws_ypixel: 160,
+};
+
+#[test]
+fn test_mouse_decode() {
+    let press: Mouse = Mouse::from((Code::Right, true, [3, 4]));
+    let release: Mouse = Mouse::from((Code::Right, false, [3, 4]));
+
+    assert_eq!(Mouse::new(b"\x1B[<2;3;4M").ok(), Some(press));
+    assert_eq!(Mouse::new(b"\x1B[<2;3;4m").ok(), Some(release));
+    assert_eq!(Mouse::new(b"\x1B[M\x22\x23\x24").ok(), Some(press));
+    assert_eq!(Mouse::new(b"\x1B[M\x23\x23\x24").ok(), Some(Mouse::from((Code::Left, false, [3, 4]))));
+    assert_eq!(Mouse::new(b"\x1B[34;3;4M").ok(), Some(press));
+    assert_eq!(Mouse::new("\x1B[M\x22\u{12C}\x24".as_bytes()).ok(), Some(Mouse::from((Code::Right, true, [268, 4]))));
+    assert_eq!(Mouse::new(b"\x1B[<35;300;4M").ok(), Some(Mouse::from((Code::Moved, true, [300, 4]))));
+    assert!(Mouse::new(b"\x1B[1;5A").is_err());
+}
+
+#[test]
+fn test_mouse_encode() {
+    let press: Mouse = Mouse::from((Code::Right, true, [3, 4]));
+    let release: Mouse = Mouse::from((Code::Right, false, [3, 4]));
+    let drag: Mouse = Mouse::from((Code::LeftDrag, true, [3, 4]));
+    let moved: Mouse = Mouse::from((Code::Moved, true, [3, 4]));
+    let far: Mouse = Mouse::from((Code::Left, true, [300, 4]));
+
+    assert!(press.encode(MouseMode::Off, MouseEncoding::Sgr, &SIZE).is_empty());
+    assert_eq!(press.encode(MouseMode::X10, MouseEncoding::Default, &SIZE), b"\x1B[M\x22\x23\x24".to_vec());
+    assert!(release.encode(MouseMode::X10, MouseEncoding::Default, &SIZE).is_empty());
+    assert_eq!(release.encode(MouseMode::Normal, MouseEncoding::Default, &SIZE), b"\x1B[M\x23\x23\x24".to_vec());
+    assert!(drag.encode(MouseMode::Normal, MouseEncoding::Sgr, &SIZE).is_empty());
+    assert_eq!(drag.encode(MouseMode::Button, MouseEncoding::Sgr, &SIZE), b"\x1B[<32;3;4M".to_vec());
+    assert!(moved.encode(MouseMode::Button, MouseEncoding::Sgr, &SIZE).is_empty());
+    assert_eq!(moved.encode(MouseMode::Any, MouseEncoding::Urxvt, &SIZE), b"\x1B[67;3;4M".to_vec());
+    assert_eq!(release.encode(MouseMode::Normal, MouseEncoding::Sgr, &SIZE), b"\x1B[<2;3;4m".to_vec());
+    assert_eq!(press.encode(MouseMode::Normal, MouseEncoding::SgrPixels, &SIZE), b"\x1B[<2;21;49M".to_vec());
+    assert!(far.encode(MouseMode::Normal, MouseEncoding::Default, &SIZE).is_empty());
+    assert_eq!(far.encode(MouseMode::Normal, MouseEncoding::Utf8, &SIZE), "\x1B[M\x20\u{14C}\x24".as_bytes().to_vec());
+    assert_eq!(far.encode(MouseMode::Normal, MouseEncoding::Sgr, &SIZE), b"\x1B[<0;300;4M".to_vec());
+}
@@ -0,0 +1,113 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::{thread, time};
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 4,
+    ws_col: 20,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[derive(Debug, Default)]
+struct Alerts {
+    bells: usize,
+    notifications: Vec<Notification>,
+}
+
+#[derive(Debug)]
+struct Handler(Arc<Mutex<Alerts>>);
+
+impl NotificationHandler for Handler {
+    fn bell(&mut self) {
+        self.0.lock().unwrap().bells += 1;
+    }
+
+    fn notify(&mut self, notification: &Notification) {
+        self.0.lock().unwrap().notifications.push(notification.clone());
+    }
+}
+
+#[test]
+fn test_notification_osc() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"a\x1B]9;build done\x07b\x1B]777;notify;make;1 error\x1B\\").unwrap();
+    assert_eq!(display.get_text().trim(), "ab");
+    assert_eq!(display.get_notifications(), &[
+        Notification::new(None, "build done".to_string()),
+        Notification::new(Some("make".to_string()), "1 error".to_string()),
+    ]);
+    assert_eq!(display.pop_notifications(), 2);
+    assert_eq!(display.pop_notifications(), 0);
+
+    // The progress of ConEmu and the unknown commands of OSC 777 are ignored.
+    display.write(b"\x1B]9;4;1;50\x07\x1B]777;preexec\x07\x1B]9;a;b\x07").unwrap();
+    assert_eq!(display.pop_notifications(), 1);
+    assert_eq!(display.get_notifications().last().unwrap().get_body(), "a;b");
+    assert!(!display.pop_bell());
+
+    display.write(b"\x07c\x07").unwrap();
+    assert_eq!(display.get_bell(), 2);
+    assert!(display.pop_bell());
+    assert!(!display.pop_bell());
+
+    (0..20).all(|index| display.write(format!("\x1B]9;{}x\x07", index).as_bytes()).is_ok());
+    assert_eq!(display.get_notifications().len(), 16);
+    assert_eq!(display.get_notifications()[0].get_body(), "4x");
+    assert_eq!(display.pop_notifications(), 16);
+}
+
+#[test]
+fn test_notification_handler() {
+    let alerts: Arc<Mutex<Alerts>> = Arc::new(Mutex::new(Alerts::default()));
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+    let mut state: Option<ShellState> = None;
+
+    shell.set_notification_handler(Some(Box::new(Handler(alerts.clone()))));
+    shell.set_notification_interval(time::Duration::from_millis(500));
+    Write::write_all(&mut shell, b"\x1B]9;first\x07\x1B]9;second\x07\x07\n").unwrap();
+    assert!((0..100).any(|_| {
+        match shell.next_evented() {
+            Ok(Some(next)) => {
+                state = state.or(next.is_output_notification().map(|_| next));
+                false
+            },
+            _ => {
+                thread::sleep(time::Duration::from_millis(10));
+                state.is_some()
+            },
+        }
+    }));
+    // The second notification is held back by the interval, the bell has its own.
+    {
+        let alerts = alerts.lock().unwrap();
+
+        assert_eq!(alerts.notifications, vec![Notification::new(None, "first".to_string())]);
+        assert_eq!(alerts.bells, 1);
+    }
+    // The held back notification is given at the first update after the interval.
+    thread::sleep(time::Duration::from_millis(600));
+    Write::write_all(&mut shell, b"a\n").unwrap();
+    assert!((0..100).any(|_| {
+        match shell.next_evented() {
+            Ok(Some(_)) => alerts.lock().unwrap().notifications.len() > 1,
+            _ => {
+                thread::sleep(time::Duration::from_millis(10));
+                false
+            },
+        }
+    }));
+    let alerts = alerts.lock().unwrap();
+
+    assert_eq!(alerts.notifications, vec![
+        Notification::new(None, "first".to_string()),
+        Notification::new(None, "second".to_string()),
+    ]);
+    assert_eq!(alerts.bells, 1);
+}
@@ -20,3 +20,62 @@ fn test_output_set()
     display.write(&[97u8, 100, 106, 105, 118, 97, 115, 64, 37, 109, 58, 37, 126, 47, 32, 40, 109, 97, 115, 116, 101,
     114, 41, 32]);
 }
+
+#[test]
+fn test_output_keyboard()
+{
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    assert_eq!(display.get_keyboard(), 0);
+    display.write(b"\x1B[>1u\x1B[>3u").unwrap();
+    assert_eq!(display.get_keyboard(), 3);
+    display.write(b"\x1B[?u").unwrap();
+    assert_eq!(display.pop_answer(), b"\x1B[?3u".to_vec());
+    assert_eq!(display.pop_answer(), Vec::new());
+    display.write(b"\x1B[=8;2u").unwrap();
+    assert_eq!(display.get_keyboard(), 11);
+    display.write(b"\x1B[<u").unwrap();
+    assert_eq!(display.get_keyboard(), 1);
+    display.write(b"\x1B[<5u").unwrap();
+    assert_eq!(display.get_keyboard(), 0);
+    assert_eq!(display.get_text().trim(), "");
+}
+
+#[test]
+fn test_output_key_modes()
+{
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    assert_eq!(display.get_key_modes(), KeyModes::default());
+    display.write(b"\x1B[?1h\x1B=\x1B[?67h\x1B[?2004h\x1B[>4;2m").unwrap();
+    assert_eq!(display.get_key_modes(), KeyModes {
+        cursor: true,
+        keypad: true,
+        backspace: true,
+        paste: true,
+        modify_other_keys: 2,
+        keyboard: 0,
+    });
+    display.write(b"\x1B[?1l\x1B>\x1B[?67l\x1B[?2004l\x1B[>4m").unwrap();
+    assert_eq!(display.get_key_modes(), KeyModes::default());
+    assert_eq!(display.get_text().trim(), "");
+}
+
+#[test]
+fn test_output_mouse_modes()
+{
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    assert_eq!(display.get_mouse_mode(), MouseMode::Off);
+    display.write(b"\x1B[?1002h\x1B[?1015h").unwrap();
+    assert_eq!(display.get_mouse_mode(), MouseMode::Button);
+    assert_eq!(display.get_mouse_encoding(), MouseEncoding::Urxvt);
+    display.write(b"\x1B[?1003h\x1B[?1016h\x1B[?1002l").unwrap();
+    assert_eq!(display.get_mouse_mode(), MouseMode::Any);
+    assert_eq!(display.get_mouse_encoding(), MouseEncoding::SgrPixels);
+    display.write(b"\x1B[?1003l\x1B[?1016l").unwrap();
+    assert_eq!(display.get_mouse_mode(), MouseMode::Off);
+    assert_eq!(display.get_mouse_encoding(), MouseEncoding::Default);
+    display.write(b"\x1B[?9h").unwrap();
+    assert_eq!(display.get_mouse_mode(), MouseMode::X10);
+}
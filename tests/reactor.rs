@@ -0,0 +1,48 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+use std::time;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 8,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[test]
+fn test_reactor_pipe() {
+    let mut reactor: Reactor = Reactor::new();
+    let mut fds: [libc::c_int; 2] = [0; 2];
+
+    unsafe {
+        assert_eq!(libc::pipe(fds.as_mut_ptr()), 0);
+    }
+    reactor.register_fd(fds[0], 42);
+    assert_eq!(reactor.poll(Some(time::Duration::from_millis(10))).ok(), Some(vec![]));
+    unsafe {
+        assert_eq!(libc::write(fds[1], b"a".as_ptr() as *const libc::c_void, 1), 1);
+    }
+    assert_eq!(reactor.poll(Some(time::Duration::from_millis(10))).ok(), Some(vec![42]));
+    assert_eq!(reactor.deregister_fd(fds[0]).ok(), Some(42));
+    assert!(reactor.is_empty());
+    unsafe {
+        libc::close(fds[0]);
+        libc::close(fds[1]);
+    }
+}
+
+#[test]
+fn test_reactor_evented_shell() {
+    let mut shell: Shell = Shell::new_evented(None, None, Some("/bin/cat"), Some(SIZE)).unwrap();
+    let mut reactor: Reactor = Reactor::new();
+
+    assert!(shell.is_evented());
+    reactor.register(&shell, 0);
+    assert!(<Shell as Write>::write(&mut shell, b"hello\n").is_ok());
+    assert_eq!(reactor.poll(Some(time::Duration::from_millis(1000))).ok(), Some(vec![0]));
+    assert!(shell.next_evented().unwrap().unwrap().is_output_screen().is_some());
+}
@@ -0,0 +1,76 @@
+extern crate pty_proc;
+
+use std::io::Write;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 4,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+fn cells(ranges: Vec<(Coordinate, Coordinate)>) -> Vec<((usize, usize), (usize, usize))> {
+    ranges.into_iter().map(|(start, end)| ((start.x, start.y), (end.x, end.y))).collect()
+}
+
+#[test]
+fn test_search_literal() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"Error: a.b").unwrap();
+    display.write(b"\r\nerror 12\r\n").unwrap();
+    assert_eq!(display.get_text(), "Error: a.b\n\nerror 12\n");
+    let search: Search = Search::new("error", SearchCase::Sensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((0, 2), (4, 2))]);
+    let search: Search = Search::new("error", SearchCase::Smart).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((0, 0), (4, 0)), ((0, 2), (4, 2))]);
+    let search: Search = Search::new("Error", SearchCase::Smart).unwrap();
+
+    assert_eq!(search.find_all(&display).len(), 1);
+    // The literal doesn't interpret the dot.
+    let search: Search = Search::new("a.b", SearchCase::Insensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((7, 0), (9, 0))]);
+    assert_eq!(Search::new("a?b", SearchCase::Sensitive).unwrap().find_all(&display).len(), 0);
+}
+
+#[test]
+fn test_search_regex() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    // The first line continues on the second one.
+    display.write("ab 1234567".as_bytes()).unwrap();
+    display.write("8 é字 99".as_bytes()).unwrap();
+    let search: Search = Search::from_regex(r"\d+", SearchCase::Sensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((3, 0), (0, 1)), ((5, 1), (6, 1))]);
+    // A glyph takes a cell.
+    let search: Search = Search::from_regex("é字", SearchCase::Sensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((2, 1), (3, 1))]);
+    assert!(Search::from_regex("(", SearchCase::Sensitive).is_err());
+
+    let search: Search = Search::from_regex(r"\d", SearchCase::Sensitive).unwrap();
+    let from: Coordinate = Coordinate::from((5, 0));
+
+    assert_eq!(search.find(&display, &from, SearchDirection::Forward).map(|(start, _)| (start.x, start.y)), Some((6, 0)));
+    assert_eq!(search.find(&display, &from, SearchDirection::Backward).map(|(start, _)| (start.x, start.y)), Some((4, 0)));
+    assert_eq!(search.find(&display, &Coordinate::from((6, 1)), SearchDirection::Forward), None);
+}
+
+#[test]
+fn test_search_wrapped() {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"$ tail -f build.log").unwrap();
+    let search: Search = Search::new("build.log", SearchCase::Sensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((0, 1), (8, 1))]);
+    let search: Search = Search::new("-f build", SearchCase::Sensitive).unwrap();
+
+    assert_eq!(cells(search.find_all(&display)), vec![((7, 0), (4, 1))]);
+}
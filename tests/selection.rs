@@ -0,0 +1,84 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 4,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+fn display() -> Display {
+    let mut display: Display = Display::from_winszed(SIZE);
+
+    display.write(b"hello wonderful\r\nbar baz").unwrap();
+    display
+}
+
+#[test]
+fn test_selection_wrap() {
+    let display: Display = display();
+
+    assert!(display.is_wrapped(0));
+    assert!(!display.is_wrapped(1));
+    assert!(!display.is_wrapped(3));
+    assert_eq!(display.get_line(2).map(|line| line.len()), Some(10));
+}
+
+#[test]
+fn test_selection_linear() {
+    let display: Display = display();
+    let mut selection: Selection = Selection::new(SelectionMode::Linear, Coordinate::from((6, 0)));
+
+    selection.extend(Coordinate::from((2, 2)));
+    assert_eq!(selection.get_text(&display), "wonderful\nbar");
+    selection.extend(Coordinate::from((9, 0)));
+    assert_eq!(selection.get_text(&display), "wond");
+}
+
+#[test]
+fn test_selection_word() {
+    let display: Display = display();
+    let selection: Selection = Selection::new(SelectionMode::Word, Coordinate::from((2, 1)));
+
+    // The word continues on the soft-wrapped line.
+    assert_eq!(selection.get_text(&display), "wonderful");
+    let mut selection: Selection = Selection::new(SelectionMode::Word, Coordinate::from((6, 2)));
+    assert_eq!(selection.get_text(&display), "baz");
+    selection.set_separators("a");
+    assert_eq!(selection.get_text(&display), "z");
+}
+
+#[test]
+fn test_selection_line() {
+    let display: Display = display();
+    let selection: Selection = Selection::new(SelectionMode::Line, Coordinate::from((3, 1)));
+
+    assert_eq!(selection.get_text(&display), "hello wonderful");
+    assert_eq!(selection.get_range(&display), (Coordinate::from((0, 0)), Coordinate::from((9, 1))));
+}
+
+#[test]
+fn test_selection_block() {
+    let display: Display = display();
+    let mut selection: Selection = Selection::new(SelectionMode::Block, Coordinate::from((2, 2)));
+
+    selection.extend(Coordinate::from((0, 0)));
+    assert_eq!(selection.get_text(&display), "hel\nerf\nbar");
+    assert!(selection.contains(&display, &Coordinate::from((1, 1))));
+    assert!(!selection.contains(&display, &Coordinate::from((3, 1))));
+}
+
+#[test]
+fn test_selection_gesture() {
+    let mut selection: Selection = Selection::from_gesture(Gesture::Click(0, 2, [3, 1])).unwrap();
+
+    assert_eq!(selection.get_mode(), SelectionMode::Word);
+    assert!(selection.extend_with(Gesture::DragUpdate(0, [3, 1], [4, 3])));
+    assert_eq!(selection.get_cursor(), &Coordinate::from((3, 2)));
+    assert!(Selection::from_gesture(Gesture::Click(2, 1, [3, 1])).is_none());
+}
@@ -0,0 +1,111 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::os::unix::net::UnixStream;
+use std::io::{Read, Write};
+use std::{env, thread, time};
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 8,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+fn receive(stream: &mut UnixStream, buffer: &mut Vec<u8>) -> Frame {
+    loop {
+        if let Some((frame, len)) = Frame::decode(&buffer).unwrap() {
+            buffer.drain(..len);
+            return frame;
+        }
+        let mut bytes: [u8; 4096] = [0; 4096];
+        let len: usize = stream.read(&mut bytes).unwrap();
+
+        assert!(len > 0);
+        buffer.extend_from_slice(&bytes[..len]);
+    }
+}
+
+#[test]
+fn test_frame_codec() {
+    let frames: Vec<Frame> = vec![Frame::Attach(None, SIZE), Frame::Attach(Some(3), SIZE),
+                                  Frame::Input(b"ls\n".to_vec()), Frame::Resize(SIZE),
+                                  Frame::Detach, Frame::Attached(3),
+                                  Frame::Output(b"hello".to_vec()), Frame::Closed];
+    let mut buf: Vec<u8> = frames.iter().flat_map(|frame| frame.encode()).collect::<Vec<u8>>();
+
+    frames.into_iter().all(|frame| {
+        let (decoded, len): (Frame, usize) = Frame::decode(&buf).unwrap().unwrap();
+
+        assert_eq!(decoded, frame);
+        buf.drain(..len);
+        true
+    });
+    assert!(Frame::decode(&[2, 0, 0, 0, 5, b'l']).unwrap().is_none());
+    assert!(Frame::decode(&[4, 0, 0, 0, 1, 0]).is_err());
+    assert!(Frame::decode(&[42, 0, 0, 0, 0]).is_err());
+    // The length is refused before the payload is received.
+    assert!(Frame::decode(&[2, 0xFF, 0xFF, 0xFF, 0xFF]).is_err());
+}
+
+#[test]
+fn test_server_attach() {
+    let path = env::temp_dir().join(format!("pty-proc-{}.sock", unsafe { libc::getpid() }));
+    let mut server: Server = Server::bind(&path, Some("/bin/cat")).unwrap();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    thread::spawn(move || server.run());
+    {
+        let mut stream: UnixStream = UnixStream::connect(&path).unwrap();
+
+        assert!(stream.write_all(&Frame::Attach(None, SIZE).encode()).is_ok());
+        assert_eq!(receive(&mut stream, &mut buffer), Frame::Attached(0));
+        assert!(stream.write_all(&Frame::Input(b"hi\n".to_vec()).encode()).is_ok());
+        while let Frame::Output(output) = receive(&mut stream, &mut buffer) {
+            if String::from_utf8_lossy(&output).replace("\x1B[m", "").contains("hi") {
+                break ;
+            }
+        }
+        assert!(stream.write_all(&Frame::Detach.encode()).is_ok());
+    }
+    buffer.clear();
+    {
+        let mut stream: UnixStream = UnixStream::connect(&path).unwrap();
+
+        assert!(stream.write_all(&Frame::Attach(Some(0), SIZE).encode()).is_ok());
+        assert_eq!(receive(&mut stream, &mut buffer), Frame::Attached(0));
+        match receive(&mut stream, &mut buffer) {
+            Frame::Output(output) => assert!(String::from_utf8_lossy(&output).replace("\x1B[m", "").contains("hi")),
+            frame => panic!("{:?}", frame),
+        }
+    }
+}
+
+#[test]
+fn test_server_slow_client() {
+    let path = env::temp_dir().join(format!("pty-proc-slow-{}.sock", unsafe { libc::getpid() }));
+    let mut server: Server = Server::bind(&path, Some("/bin/cat")).unwrap();
+    let size: Winszed = Winszed { ws_row: 100, ws_col: 200, ..SIZE };
+    let mut buffer: Vec<u8> = Vec::new();
+
+    thread::spawn(move || server.run());
+    // The first client never reads its socket.
+    let mut slow: UnixStream = UnixStream::connect(&path).unwrap();
+
+    assert!(slow.write_all(&Frame::Attach(None, size).encode()).is_ok());
+    let mut stream: UnixStream = UnixStream::connect(&path).unwrap();
+
+    stream.set_read_timeout(Some(time::Duration::from_secs(5))).unwrap();
+    assert!(stream.write_all(&Frame::Attach(Some(0), size).encode()).is_ok());
+    assert_eq!(receive(&mut stream, &mut buffer), Frame::Attached(0));
+    // The redraws sent to the slow client don't stop the server.
+    for _ in 0..100 {
+        assert!(stream.write_all(&Frame::Input(b"a".to_vec()).encode()).is_ok());
+        match receive(&mut stream, &mut buffer) {
+            Frame::Output(_) => {},
+            frame => panic!("{:?}", frame),
+        }
+    }
+}
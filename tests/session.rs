@@ -0,0 +1,55 @@
+extern crate pty_proc;
+extern crate libc;
+
+use std::io::Write;
+use std::time;
+
+use self::pty_proc::prelude::*;
+
+const SIZE: Winszed = Winszed {
+    ws_row: 8,
+    ws_col: 10,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+};
+
+#[test]
+fn test_session_focus() {
+    let mut manager: SessionManager = SessionManager::headless(None, None);
+    let first: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+    let second: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    assert_eq!(manager.len(), 2);
+    assert_eq!(manager.get_focus(), Some(first));
+    assert!(manager.set_focus(second).is_ok());
+    assert!(manager.set_focus(42).is_err());
+    assert_eq!(manager.get_focus(), Some(second));
+    assert!(manager.close(second).is_ok());
+    assert!(manager.close(second).is_err());
+    assert_eq!(manager.get_focus(), Some(first));
+    assert_eq!(manager.get_ids(), vec![first]);
+}
+
+#[test]
+fn test_session_route() {
+    let mut manager: SessionManager = SessionManager::headless(None, None);
+    let first: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+    let second: SessionId = manager.create(Some("/bin/cat"), Some(SIZE)).unwrap();
+
+    assert!(manager.set_focus(second).is_ok());
+    manager.input(b"a");
+    {
+        let (id, event): (SessionId, SessionEvent) = manager.next().unwrap();
+
+        assert_eq!(id, second);
+        assert_eq!(event.is_state().and_then(|state| state.is_input_slice()), Some(&b"a"[..]));
+    }
+    assert!(<Shell as Write>::write(manager.get_mut(first).unwrap(), b"hello\n").is_ok());
+    assert!(manager.poll(Some(time::Duration::from_millis(1000))).is_ok());
+    {
+        let (id, event): (SessionId, SessionEvent) = manager.next().unwrap();
+
+        assert_eq!(id, first);
+        assert!(event.is_state().and_then(|state| state.is_output_screen()).is_some());
+    }
+}
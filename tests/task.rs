@@ -11,7 +11,7 @@ use self::std::env;
 #[cfg(feature = "task")]
 use self::std::io::Write;
 #[cfg(feature = "task")]
-use self::std::{thread, time};
+use self::std::{process, thread, time};
 
 #[cfg(feature = "task")]
 const SIZE: Winszed = Winszed {
@@ -31,6 +31,164 @@ fn test_proc_new() {
     );
 }
 
+#[test]
+#[cfg(feature = "task")]
+fn test_proc_job() {
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let mut task: Proc = Proc::from_master(pid, -1);
+    let (leader, pgid, sid, _): BufProc = task.get_job(pid).unwrap();
+
+    assert_eq!(leader, pid);
+    assert_eq!(pgid, unsafe { libc::getpgid(0) });
+    assert_eq!(sid, unsafe { libc::getsid(0) });
+    assert_eq!(task.get_foreground(), None);
+    assert_eq!(task.next(), None);
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_process_tree() {
+    let list: Vec<(libc::pid_t, libc::pid_t, libc::c_uchar, String)> = vec![
+        (1, 0, b'S', "init".to_string()),
+        (10, 1, b'S', "bash".to_string()),
+        (11, 10, b'R', "vim".to_string()),
+        (12, 10, b'S', "make".to_string()),
+        (13, 12, b'R', "cc".to_string()),
+    ];
+    let tree: ProcessTree = ProcessTree::new(&list, 10, None).unwrap();
+
+    assert_eq!(tree.get_name(), "bash");
+    assert_eq!(tree.get_children().iter().map(|child| child.get_pid()).collect::<Vec<libc::pid_t>>(), vec![11, 12]);
+    assert_eq!(tree.get_descendants().iter().map(|child| child.get_name()).collect::<Vec<&str>>(), vec!["vim", "make", "cc"]);
+    assert_eq!(tree.get_background().len(), 3);
+    assert!(tree.is_busy());
+    assert!(!tree.get_children()[0].is_busy());
+    assert!(ProcessTree::new(&list, 42, None).is_none());
+
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let tree: ProcessTree = Proc::new(pid).unwrap().get_tree(pid).unwrap();
+
+    assert_eq!(tree.get_ppid(), unsafe { libc::getppid() });
+    assert!(!tree.is_foreground());
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_monitor() {
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let mut monitor: Monitor = Monitor::new(pid, -1, time::Duration::from_millis(100));
+    let mut child = process::Command::new("sleep").arg("10").spawn().unwrap();
+    let child_pid: libc::pid_t = child.id() as libc::pid_t;
+
+    assert!((0..50).any(|_| {
+        monitor.next_events().contains(&ProcEvent::Fork(child_pid, pid))
+    }));
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!((0..50).any(|_| {
+        monitor.next_events().iter().any(|event| match *event {
+            ProcEvent::Exit(exited, _) => exited.eq(&child_pid),
+            _ => false,
+        })
+    }));
+    assert!(!monitor.watch(ProcEvent::Exec(child_pid)));
+    assert!(monitor.watch(ProcEvent::Fork(1_000_000, pid)));
+    assert!(monitor.watch(ProcEvent::Exec(1_000_000)));
+    assert!(monitor.watch(ProcEvent::Exit(1_000_000, Some(0))));
+    assert!(!monitor.watch(ProcEvent::Exit(1_000_000, Some(0))));
+
+    thread::sleep(time::Duration::from_millis(100));
+    assert!(monitor.next_refresh());
+    assert!(!monitor.next_refresh());
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_monitor_name() {
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let mut monitor: Monitor = Monitor::new(pid, -1, time::Duration::from_millis(100));
+    let mut child = process::Command::new("sleep").arg("10").spawn().unwrap();
+    let child_pid: libc::pid_t = child.id() as libc::pid_t;
+
+    thread::sleep(time::Duration::from_millis(100));
+    // The descendant given by the connector is named, the polling
+    // doesn't see a exec.
+    assert!(monitor.watch(ProcEvent::Fork(child_pid, pid)));
+    if !monitor.is_evented() {
+        assert!((0..5).all(|_| {
+            !monitor.next_events().contains(&ProcEvent::Exec(child_pid))
+        }));
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_limits() {
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let usage: Usage = Usage::from_pids(&[pid, 0]);
+    let limits: Limits = Limits::new(Some(time::Duration::from_millis(1500)), Some(1 << 40), Some(2));
+    let mut state: ShellState = ShellState::default();
+
+    assert_eq!(usage.get_processes(), 1);
+    assert!(usage.get_rss() > 0);
+    assert_eq!(limits.as_rlimits(), vec![(libc::RLIMIT_CPU as libc::c_int, 2)]);
+    assert_eq!(limits.exceeded(&usage), None);
+    assert_eq!(limits.exceeded(&Usage { processes: 2, ..usage }), Some(Limit::Processes));
+    assert_eq!(limits.exceeded(&Usage { cpu: time::Duration::from_secs(2), ..usage }), Some(Limit::Cpu));
+
+    state.set_limits(limits);
+    state.set_task_usage(Some(usage));
+    assert_eq!(state.is_task_limit(), None);
+    state.set_task_usage(Some(Usage { processes: 3, ..usage }));
+    assert_eq!(state.is_task_limit(), Some(Limit::Processes));
+    state.set_task_usage(Some(Usage { processes: 4, ..usage }));
+    assert_eq!(state.is_task_limit(), None);
+    state.set_task_usage(None);
+    state.set_task_usage(Some(usage));
+    state.set_task_usage(Some(Usage { processes: 3, ..usage }));
+    assert_eq!(state.is_task_limit(), Some(Limit::Processes));
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_process_info() {
+    let info: ProcessInfo = ProcessInfo::new(unsafe {
+        libc::getpid()
+    }).unwrap();
+
+    assert_eq!(info.get_ppid(), unsafe { libc::getppid() });
+    assert_eq!(info.get_uid(), unsafe { libc::getuid() });
+    assert_eq!(info.get_cwd(), env::current_dir().ok().as_ref().map(|cwd| cwd.as_path()));
+    assert_eq!(info.get_exe(), env::current_exe().ok().as_ref().map(|exe| exe.as_path()));
+    assert_eq!(info.get_cmdline().len(), env::args().count());
+    assert!(info.get_command().starts_with(&env::args().next().unwrap()));
+    assert!(info.get_rss() > 0);
+    assert!(info.get_vsz() >= info.get_rss());
+    assert!(info.get_start().sec as u64 <= time::SystemTime::now().duration_since(time::UNIX_EPOCH).unwrap().as_secs());
+}
+
+#[test]
+#[cfg(feature = "task")]
+fn test_proc_cwd() {
+    let pid: libc::pid_t = unsafe { libc::getpid() };
+    let mut monitor: Monitor = Monitor::new(pid, -1, time::Duration::from_millis(100));
+    let mut display: Display = Display::from_winszed(SIZE);
+    let mut state: ShellState = ShellState::default();
+
+    assert_eq!(Proc::from_master(pid, -1).get_foreground_cwd(), env::current_dir().ok());
+    assert_eq!(monitor.get_cwd(), env::current_dir().ok().as_ref());
+    assert!(!monitor.next_cwd());
+
+    state.set_task_cwd(&display, Some(()));
+    assert!(state.is_cwd_changed());
+    display.write(b"\x1B]7;file://host/tmp\x07").unwrap();
+    state.set_output(&mut display, None);
+    state.set_task_cwd(&display, Some(()));
+    assert!(!state.is_cwd_changed());
+}
+
 #[test]
 #[ignore]
 #[cfg(feature = "task")]